@@ -0,0 +1,58 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ingreedy_rs::Ingredient;
+
+const SHORT_LINES: &[&str] = &[
+    "1 cup flour",
+    "2lb 4oz potatoes",
+    "1 (28 ounce) can crushed tomatoes",
+    "1 cup (240 ml) milk",
+    "pinch salt",
+    "3 eggs",
+];
+
+/// Lines like those of scraped recipes, most without a unit or amount where the grammar first tries one
+const CORPUS: &[&str] = &[
+    "2 tablespoons olive oil, divided",
+    "1/2 teaspoon ground black pepper",
+    "salt and pepper to taste",
+    "1 1/2 cups chopped onion",
+    "4 cloves garlic, minced",
+    "2-3 large carrots, peeled",
+    "two dozen eggs",
+    "fresh parsley, for garnish",
+    "1 (15 oz) can black beans, rinsed",
+    "3 eggs",
+];
+
+const LONG_LINE: &str = "2 cups all-purpose flour, sifted twice and spooned lightly into the measuring \
+    cup before leveling off with the back of a knife, plus more for dusting the counter, the rolling pin \
+    and your hands while you shape the dough into a rough rectangle about one inch thick";
+
+fn short_lines(c: &mut Criterion) {
+    c.bench_function("short lines", |b| {
+        b.iter(|| {
+            for line in SHORT_LINES {
+                black_box(Ingredient::parse(black_box(line)).unwrap());
+            }
+        })
+    });
+}
+
+fn corpus(c: &mut Criterion) {
+    c.bench_function("corpus", |b| {
+        b.iter(|| {
+            for line in CORPUS {
+                black_box(Ingredient::parse(black_box(line)).unwrap());
+            }
+        })
+    });
+}
+
+fn long_line(c: &mut Criterion) {
+    c.bench_function("long line", |b| {
+        b.iter(|| black_box(Ingredient::parse(black_box(LONG_LINE)).unwrap()))
+    });
+}
+
+criterion_group!(benches, short_lines, corpus, long_line);
+criterion_main!(benches);
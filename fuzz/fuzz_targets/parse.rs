@@ -0,0 +1,16 @@
+//! Every entry point must return, successfully or not, on any UTF-8 input
+
+#![no_main]
+use ingreedy_rs::{Amount, Ingredient, Locale, Quantity, Recipe};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    for locale in Locale::all() {
+        let _ = Ingredient::parse_with_locale(input, *locale);
+    }
+    let _ = Ingredient::parse_lossy(input).to_string();
+    let _ = Ingredient::parse_with_spans(input);
+    let _ = Amount::parse(input);
+    let _ = Quantity::parse_str(input);
+    let _ = Recipe::parse(input).scale(2.).to_string();
+});
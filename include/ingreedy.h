@@ -0,0 +1,27 @@
+#ifndef INGREEDY_H
+#define INGREEDY_H
+
+/* Generated with cbindgen from src/ffi.rs, do not edit by hand */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Parse a NUL-terminated UTF-8 ingredient line into a JSON string.
+//
+// Lines that can't be parsed give `{"input": ..., "error": ...}`.
+// Returns NULL if `input` is NULL or not valid UTF-8.
+// The result must be released with `ingreedy_free_string`.
+//
+// # Safety
+// `input` must be NULL or point to a NUL-terminated string.
+char *ingreedy_parse_json(const char *input);
+
+// Free a string returned by `ingreedy_parse_json`. Passing NULL does nothing.
+//
+// # Safety
+// `string` must be NULL or a pointer returned by `ingreedy_parse_json` that wasn't freed yet.
+void ingreedy_free_string(char *string);
+
+#endif  /* INGREEDY_H */
@@ -0,0 +1,33 @@
+//! Node.js bindings through napi-rs, matching the ingreedy-js API
+
+use napi_derive::napi;
+
+/// Ingredient in the shape ingreedy-js returns
+/// (e.g. `{ amount: 2, unit: "cup", ingredient: "flour" }`)
+#[napi(object)]
+pub struct Ingredient {
+    pub amount: Option<f64>,
+    pub unit: Option<String>,
+    pub ingredient: Option<String>,
+}
+
+impl From<ingreedy_rs::Ingredient> for Ingredient {
+    fn from(ingredient: ingreedy_rs::Ingredient) -> Self {
+        let quantity = ingredient.quantities.into_iter().next();
+        Self {
+            amount: quantity.as_ref().map(|quantity| quantity.amount.value()),
+            unit: quantity
+                .and_then(|quantity| quantity.unit)
+                .map(|unit| unit.to_string()),
+            ingredient: ingredient.ingredient,
+        }
+    }
+}
+
+/// Parse an ingredient line, throwing a JavaScript error if it can't be parsed
+#[napi]
+pub fn parse(input: String) -> napi::Result<Ingredient> {
+    ingreedy_rs::Ingredient::parse(&input)
+        .map(Ingredient::from)
+        .map_err(|error| napi::Error::from_reason(error.to_string()))
+}
@@ -0,0 +1,6 @@
+//! WebAssembly build of ingreedy-rs's `wasm` feature, matching the ingreedy-js API
+//!
+//! The bindings live in ingreedy-rs so they're built and tested with it;
+//! this crate only links them into a cdylib for `wasm-pack build`.
+
+extern crate ingreedy_rs;
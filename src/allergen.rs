@@ -0,0 +1,248 @@
+//! Flagging ingredients that contain common allergens
+
+use crate::lexicon::PhraseLexicon;
+use crate::Ingredient;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A major food allergen
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Allergen {
+    Gluten,
+    Dairy,
+    TreeNuts,
+    Peanuts,
+    Soy,
+    Egg,
+    Shellfish,
+    Sesame,
+}
+
+impl Allergen {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Gluten => "gluten",
+            Self::Dairy => "dairy",
+            Self::TreeNuts => "tree nuts",
+            Self::Peanuts => "peanuts",
+            Self::Soy => "soy",
+            Self::Egg => "egg",
+            Self::Shellfish => "shellfish",
+            Self::Sesame => "sesame",
+        }
+    }
+}
+
+impl fmt::Display for Allergen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Words and phrases of ingredient names, and the allergens they contain
+const BUILTIN_TERMS: &[(&str, &[Allergen])] = {
+    use Allergen::*;
+    &[
+        // gluten
+        ("wheat", &[Gluten]),
+        ("flour", &[Gluten]),
+        ("bread", &[Gluten]),
+        ("breadcrumbs", &[Gluten]),
+        ("bread crumbs", &[Gluten]),
+        ("panko", &[Gluten]),
+        ("pasta", &[Gluten]),
+        ("spaghetti", &[Gluten]),
+        ("macaroni", &[Gluten]),
+        ("noodles", &[Gluten]),
+        ("egg noodles", &[Gluten, Egg]),
+        ("couscous", &[Gluten]),
+        ("barley", &[Gluten]),
+        ("rye", &[Gluten]),
+        ("semolina", &[Gluten]),
+        ("bulgur", &[Gluten]),
+        ("farro", &[Gluten]),
+        ("spelt", &[Gluten]),
+        ("seitan", &[Gluten]),
+        ("puff pastry", &[Gluten, Dairy]),
+        // dairy
+        ("milk", &[Dairy]),
+        ("buttermilk", &[Dairy]),
+        ("butter", &[Dairy]),
+        ("ghee", &[Dairy]),
+        ("cream", &[Dairy]),
+        ("cheese", &[Dairy]),
+        ("parmesan", &[Dairy]),
+        ("mozzarella", &[Dairy]),
+        ("cheddar", &[Dairy]),
+        ("ricotta", &[Dairy]),
+        ("feta", &[Dairy]),
+        ("mascarpone", &[Dairy]),
+        ("yogurt", &[Dairy]),
+        ("yoghurt", &[Dairy]),
+        ("whey", &[Dairy]),
+        ("half-and-half", &[Dairy]),
+        // tree nuts
+        ("almond", &[TreeNuts]),
+        ("walnut", &[TreeNuts]),
+        ("pecan", &[TreeNuts]),
+        ("cashew", &[TreeNuts]),
+        ("pistachio", &[TreeNuts]),
+        ("hazelnut", &[TreeNuts]),
+        ("macadamia", &[TreeNuts]),
+        ("pine nuts", &[TreeNuts]),
+        ("brazil nuts", &[TreeNuts]),
+        ("almond milk", &[TreeNuts]),
+        ("cashew milk", &[TreeNuts]),
+        ("almond flour", &[TreeNuts]),
+        ("almond butter", &[TreeNuts]),
+        // peanuts
+        ("peanut", &[Peanuts]),
+        ("peanut butter", &[Peanuts]),
+        // soy
+        ("soy", &[Soy]),
+        ("soybeans", &[Soy]),
+        ("tofu", &[Soy]),
+        ("tempeh", &[Soy]),
+        ("edamame", &[Soy]),
+        ("miso", &[Soy]),
+        ("tamari", &[Soy]),
+        ("soy sauce", &[Soy, Gluten]),
+        ("soy milk", &[Soy]),
+        // egg
+        ("egg", &[Egg]),
+        ("mayonnaise", &[Egg]),
+        ("meringue", &[Egg]),
+        // shellfish
+        ("shrimp", &[Shellfish]),
+        ("prawns", &[Shellfish]),
+        ("crab", &[Shellfish]),
+        ("lobster", &[Shellfish]),
+        ("crayfish", &[Shellfish]),
+        ("scallops", &[Shellfish]),
+        ("clams", &[Shellfish]),
+        ("mussels", &[Shellfish]),
+        ("oysters", &[Shellfish]),
+        // sesame
+        ("sesame", &[Sesame]),
+        ("tahini", &[Sesame]),
+        // phrases free of the allergens their words suggest
+        ("coconut milk", &[]),
+        ("coconut cream", &[]),
+        ("oat milk", &[]),
+        ("rice milk", &[]),
+        ("cocoa butter", &[]),
+        ("cream of tartar", &[]),
+        ("rice flour", &[]),
+        ("corn flour", &[]),
+        ("coconut flour", &[]),
+        ("buckwheat flour", &[]),
+        ("chickpea flour", &[]),
+        ("gluten-free flour", &[]),
+        ("gluten-free bread", &[]),
+        ("gluten-free pasta", &[]),
+    ]
+};
+
+lazy_static! {
+    static ref BUILTIN_LEXICON: AllergenLexicon = AllergenLexicon::builtin();
+}
+
+/// Words and phrases of ingredient names mapped to the allergens they contain.
+///
+/// Terms are matched as whole words by their `ingredient_key`, so "Eggs" matches "egg",
+/// and the longest term wins where several start at the same word
+/// ("peanut butter" contains peanuts, not dairy).
+#[derive(Default, Debug, Clone)]
+pub struct AllergenLexicon(PhraseLexicon<Allergen>);
+
+impl AllergenLexicon {
+    /// Empty lexicon
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lexicon of common ingredients containing gluten, dairy, tree nuts, peanuts, soy,
+    /// egg, shellfish and sesame
+    pub fn builtin() -> Self {
+        let mut lexicon = Self::new();
+        for (term, allergens) in BUILTIN_TERMS {
+            lexicon.insert(term, allergens);
+        }
+        lexicon
+    }
+
+    /// Set the allergens of a word or phrase, replacing any it had.
+    ///
+    /// An empty list marks a phrase as free of the allergens its words suggest
+    /// (e.g. "coconut milk").
+    pub fn insert(&mut self, term: &str, allergens: &[Allergen]) {
+        self.0.insert(term, allergens);
+    }
+
+    /// Allergens an ingredient name contains, sorted and without duplicates
+    pub fn allergens(&self, name: &str) -> Vec<Allergen> {
+        self.0.tags(name)
+    }
+}
+
+impl Ingredient {
+    /// Allergens in the ingredient, from the built-in `AllergenLexicon`
+    pub fn allergens(&self) -> Vec<Allergen> {
+        self.allergens_with(&BUILTIN_LEXICON)
+    }
+
+    /// Allergens in the ingredient, from the given lexicon
+    pub fn allergens_with(&self, lexicon: &AllergenLexicon) -> Vec<Allergen> {
+        self.ingredient
+            .as_deref()
+            .map(|name| lexicon.allergens(name))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allergens(input: &str) -> Vec<Allergen> {
+        Ingredient::parse(input).unwrap().allergens()
+    }
+
+    #[test]
+    fn test_allergens() {
+        use Allergen::*;
+        assert_eq!(allergens("2 cups all-purpose flour"), [Gluten]);
+        assert_eq!(allergens("3 large Eggs, beaten"), [Egg]);
+        assert_eq!(allergens("1 cup whole milk"), [Dairy]);
+        assert_eq!(allergens("1 can coconut milk"), []);
+        assert_eq!(allergens("2 tbsp peanut butter"), [Peanuts]);
+        assert_eq!(allergens("2 tbsp soy sauce"), [Gluten, Soy]);
+        assert_eq!(allergens("1/2 cup chopped walnuts"), [TreeNuts]);
+        assert_eq!(allergens("1 lb shrimp, peeled"), [Shellfish]);
+        assert_eq!(allergens("1 tbsp toasted sesame oil"), [Sesame]);
+        assert_eq!(allergens("1 eggplant"), []);
+        assert_eq!(allergens("1 tsp nutmeg"), []);
+        assert_eq!(allergens("1/2 cup butter and parmesan"), [Dairy]);
+        assert_eq!(Ingredient::parse("2 cups").unwrap().allergens(), []);
+    }
+
+    #[test]
+    fn test_custom_lexicon() {
+        let mut lexicon = AllergenLexicon::builtin();
+        lexicon.insert("pesto", &[Allergen::TreeNuts, Allergen::Dairy]);
+        lexicon.insert("butter", &[]);
+        let ingredient = Ingredient::parse("2 tbsp pesto and butter").unwrap();
+        assert_eq!(
+            ingredient.allergens_with(&lexicon),
+            [Allergen::Dairy, Allergen::TreeNuts]
+        );
+        let empty = AllergenLexicon::new();
+        assert_eq!(
+            Ingredient::parse("2 eggs").unwrap().allergens_with(&empty),
+            []
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! Amounts of quantities: exact, ranges, approximate or unspecified
+
+use crate::{
+    get_next_inner_pair, normalize, number_word_value, parse_amount, parser::IngredientParser,
+    IngreedyError, Normalization, Rule,
+};
+use pest::iterators::Pair;
+use pest::Parser;
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Mul};
+
+/// Numeric amount of a quantity
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Default, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Amount {
+    /// A single amount (e.g. "2 cups")
+    Exact(f64),
+    /// A range of amounts (e.g. "2-3 cups" or "2 to 3 cups"), approximate in "about 2-3 cups"
+    Range {
+        min: f64,
+        max: f64,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        approximate: bool,
+    },
+    /// An amount marked as approximate (e.g. "about 2 cups", "~200 g")
+    Approximate(f64),
+    /// No amount given (e.g. "salt to taste")
+    #[default]
+    Unspecified,
+}
+
+impl Amount {
+    /// Parse a standalone amount (e.g. "1 1/2", "¾", "two" or "2-3")
+    pub fn parse(input: &str) -> Result<Self, IngreedyError> {
+        let input = normalize::normalize_unicode(input, Normalization::default());
+        for pair in IngredientParser::parse(Rule::amount_input, &input)? {
+            match pair.as_rule() {
+                Rule::amount | Rule::amount_range => return Self::from_pair(pair),
+                Rule::written_number => return Ok(Self::Exact(number_word_value(pair.as_str())?)),
+                _ => {}
+            }
+        }
+        Err(IngreedyError::InnerRuleNoneError)
+    }
+
+    /// Amount of an `amount` or `amount_range` rule
+    pub(crate) fn from_pair(pair: Pair<Rule>) -> Result<Self, IngreedyError> {
+        if crate::rule_of(&pair) == Rule::amount_range {
+            let mut bounds = pair.into_inner();
+            let mut bound = || -> Result<f64, IngreedyError> {
+                let amount = bounds.next().ok_or(IngreedyError::InnerRuleNoneError)?;
+                parse_amount(get_next_inner_pair(amount)?)
+            };
+            let (min, max) = (bound()?, bound()?);
+            return Ok(Self::Range {
+                min,
+                max,
+                approximate: false,
+            });
+        }
+        Ok(Self::Exact(parse_amount(get_next_inner_pair(pair)?)?))
+    }
+
+    /// Amount as a single number: the midpoint of a range, and 0 if unspecified
+    pub fn value(&self) -> f64 {
+        match *self {
+            Self::Exact(value) | Self::Approximate(value) => value,
+            Self::Range { min, max, .. } => (min + max) / 2.,
+            Self::Unspecified => 0.,
+        }
+    }
+
+    /// Smallest and largest values of the amount, equal unless it is a range
+    pub fn bounds(&self) -> (f64, f64) {
+        match *self {
+            Self::Range { min, max, .. } => (min, max),
+            amount => (amount.value(), amount.value()),
+        }
+    }
+
+    pub fn is_approximate(&self) -> bool {
+        matches!(
+            self,
+            Self::Approximate(_)
+                | Self::Range {
+                    approximate: true,
+                    ..
+                }
+        )
+    }
+
+    pub fn is_unspecified(&self) -> bool {
+        matches!(self, Self::Unspecified)
+    }
+
+    /// The same amount marked as approximate; unspecified amounts are kept as they are
+    pub fn approximate(self) -> Self {
+        match self {
+            Self::Exact(value) => Self::Approximate(value),
+            Self::Range { min, max, .. } => Self::Range {
+                min,
+                max,
+                approximate: true,
+            },
+            amount => amount,
+        }
+    }
+
+    /// Apply a function to each value of the amount
+    pub fn map(self, f: impl Fn(f64) -> f64) -> Self {
+        match self {
+            Self::Exact(value) => Self::Exact(f(value)),
+            Self::Range {
+                min,
+                max,
+                approximate,
+            } => Self::Range {
+                min: f(min),
+                max: f(max),
+                approximate,
+            },
+            Self::Approximate(value) => Self::Approximate(f(value)),
+            Self::Unspecified => Self::Unspecified,
+        }
+    }
+
+    /// Combine two amounts bound by bound, keeping a range if either is one
+    /// and marking the result approximate if either is
+    fn combine(self, other: Self, f: impl Fn(f64, f64) -> f64) -> Self {
+        match (self, other) {
+            (Self::Unspecified, amount) | (amount, Self::Unspecified) => amount,
+            (Self::Range { .. }, _) | (_, Self::Range { .. }) => {
+                let ((min, max), (other_min, other_max)) = (self.bounds(), other.bounds());
+                Self::Range {
+                    min: f(min, other_min),
+                    max: f(max, other_max),
+                    approximate: self.is_approximate() || other.is_approximate(),
+                }
+            }
+            (Self::Approximate(value), other) | (other, Self::Approximate(value)) => {
+                Self::Approximate(f(value, other.value()))
+            }
+            (Self::Exact(value), Self::Exact(other)) => Self::Exact(f(value, other)),
+        }
+    }
+}
+
+impl Add for Amount {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.combine(other, |a, b| a + b)
+    }
+}
+
+impl Mul for Amount {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.combine(other, |a, b| a * b)
+    }
+}
+
+impl Mul<f64> for Amount {
+    type Output = Self;
+
+    fn mul(self, factor: f64) -> Self {
+        self.map(|value| value * factor)
+    }
+}
+
+impl From<f64> for Amount {
+    fn from(value: f64) -> Self {
+        Self::Exact(value)
+    }
+}
+
+impl From<Amount> for f64 {
+    fn from(amount: Amount) -> Self {
+        amount.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Amount::parse("2-3").unwrap(),
+            Amount::Range {
+                min: 2.,
+                max: 3.,
+                approximate: false
+            }
+        );
+        assert_eq!(
+            Amount::parse("1 to 1 1/2").unwrap(),
+            Amount::Range {
+                min: 1.,
+                max: 1.5,
+                approximate: false
+            }
+        );
+        assert_eq!(Amount::parse("1-1/2").unwrap(), Amount::Exact(1.5));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let range = Amount::Range {
+            min: 2.,
+            max: 3.,
+            approximate: false,
+        };
+        assert_relative_eq!(range.value(), 2.5);
+        assert_eq!(
+            range * 2.,
+            Amount::Range {
+                min: 4.,
+                max: 6.,
+                approximate: false
+            }
+        );
+        assert_eq!(
+            range + Amount::Exact(1.),
+            Amount::Range {
+                min: 3.,
+                max: 4.,
+                approximate: false
+            }
+        );
+        assert_eq!(
+            Amount::Approximate(2.) * Amount::Exact(3.),
+            Amount::Approximate(6.)
+        );
+        assert_eq!(Amount::Exact(2.) * Amount::Exact(3.), Amount::Exact(6.));
+        assert!((range.approximate() + Amount::Exact(1.)).is_approximate());
+        assert_eq!(Amount::Unspecified + Amount::Exact(1.), Amount::Exact(1.));
+        assert_eq!(Amount::Unspecified * 2., Amount::Unspecified);
+    }
+}
@@ -0,0 +1,83 @@
+//! Parsing into a caller-provided bump arena, for batch jobs that free everything at once
+
+use crate::{
+    normalize, parser::IngredientParser, walk_pairs, IngreedyError, Locale, Normalization, Quantity,
+};
+use bumpalo::collections::Vec as BumpVec;
+use bumpalo::Bump;
+use pest::Parser;
+
+/// Ingredient information allocated in a bump arena.
+///
+/// Unlike `Ingredient`, no warnings are computed.
+#[derive(Debug)]
+pub struct ArenaIngredient<'bump> {
+    /// quantities for ingredient, in the order they appear in the input
+    pub quantities: BumpVec<'bump, Quantity>,
+    /// ingredient name
+    pub ingredient: Option<&'bump str>,
+}
+
+impl<'bump> ArenaIngredient<'bump> {
+    /// Parse a single line of input, allocating the result in `bump`
+    pub fn parse(input: &str, bump: &'bump Bump) -> Result<Self, IngreedyError> {
+        let input = normalize::normalize_unicode(input, Normalization::default());
+        let pairs = IngredientParser::parse(Locale::En.ingredient_rule(), &input)?;
+        let mut quantities = BumpVec::new_in(bump);
+        let mut ingredient = None;
+        walk_pairs(
+            pairs,
+            |quantity, _, replace_first| {
+                if replace_first {
+                    quantities.clear();
+                }
+                quantities.push(quantity);
+            },
+            |_| {},
+            |_| {},
+            |name, _| ingredient = Some(&*bump.alloc_str(name)),
+        )?;
+        Ok(Self {
+            quantities,
+            ingredient,
+        })
+    }
+
+    /// Parse newline-delimited ingredient lines, skipping blank lines,
+    /// allocating the results in `bump`
+    pub fn parse_lines(
+        text: &str,
+        bump: &'bump Bump,
+    ) -> BumpVec<'bump, Result<Self, IngreedyError>> {
+        let mut ingredients = BumpVec::new_in(bump);
+        ingredients.extend(
+            text.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| Self::parse(line, bump)),
+        );
+        ingredients
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Unit;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_arena_parse() {
+        let bump = Bump::new();
+        let ingredients =
+            ArenaIngredient::parse_lines("2 cups flour\n\n2 1 cup milk\npinch salt", &bump);
+        assert_eq!(ingredients.len(), 3);
+        let flour = ingredients[0].as_ref().unwrap();
+        assert_relative_eq!(flour.quantities[0].amount.value(), 2.);
+        assert_eq!(flour.quantities[0].unit, Some(Unit::Cup));
+        assert_eq!(flour.ingredient, Some("flour"));
+        let milk = ingredients[1].as_ref().unwrap();
+        assert_eq!(milk.quantities.len(), 1);
+        assert_relative_eq!(milk.quantities[0].amount.value(), 2.);
+        assert_eq!(ingredients[2].as_ref().unwrap().ingredient, Some("salt"));
+    }
+}
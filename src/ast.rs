@@ -0,0 +1,218 @@
+//! A syntax tree of parsed lines owned by the crate, so the grammar can change without
+//! changing the public API
+
+use crate::{
+    normalize, parser::IngredientParser, rule_of, IngreedyError, ParserConfig, Rule, Span,
+};
+use pest::iterators::{Pair, Pairs};
+use pest::Parser;
+use serde::{Deserialize, Serialize};
+
+/// Kind of a node of an `Ast`
+#[derive(Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum NodeKind {
+    /// The quantities of the line, in order (e.g. "2lb 4oz")
+    Quantities,
+    /// "plus" or "and" between the parts of a quantity
+    Plus,
+    /// Equivalent quantities after a "/" or "or" (e.g. "/ 2lb")
+    Alternative,
+    /// One quantity, with or without a unit (e.g. "about 2 cups")
+    Quantity,
+    /// "about", "approx." or "~"
+    Approximation,
+    /// Two amounts with a range between them (e.g. "2-3")
+    Range,
+    /// A number, fraction or spelled-out amount (e.g. "1 1/2", "two", "a dozen")
+    Amount,
+    /// How the measure was filled (e.g. "heaping")
+    Modifier,
+    /// A unit (e.g. "cups", "pinch")
+    Unit,
+    /// A parenthesized quantity (e.g. "(28 ounce)" or the conversion "(240 ml)")
+    Parenthesized,
+    /// A size multiplied by the amount before it (e.g. "x 400g")
+    Multiplied,
+    /// Size of the pieces (e.g. "large")
+    Size,
+    /// Part of a fruit (e.g. "juice" in "juice of 2 lemons")
+    Part,
+    /// The ingredient name and anything after it
+    Name,
+}
+
+impl NodeKind {
+    fn from_rule(rule: Rule) -> Option<Self> {
+        let kind = match rule {
+            Rule::multipart_quantity => Self::Quantities,
+            Rule::plus => Self::Plus,
+            Rule::alternative_quantity => Self::Alternative,
+            Rule::quantity_fragment => Self::Quantity,
+            Rule::approximation => Self::Approximation,
+            Rule::amount_range => Self::Range,
+            Rule::amount => Self::Amount,
+            Rule::quantity_modifier => Self::Modifier,
+            Rule::unit | Rule::imprecise_unit | Rule::count_unit => Self::Unit,
+            Rule::parenthesized_quantity => Self::Parenthesized,
+            Rule::multiplied_quantity => Self::Multiplied,
+            Rule::ingredient_size => Self::Size,
+            Rule::fruit_part => Self::Part,
+            Rule::ingredient | Rule::leading_ingredient => Self::Name,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    /// Whether the node's text is read as a whole, without children
+    fn is_leaf(self) -> bool {
+        matches!(
+            self,
+            Self::Approximation
+                | Self::Amount
+                | Self::Modifier
+                | Self::Unit
+                | Self::Size
+                | Self::Part
+                | Self::Name
+        )
+    }
+}
+
+/// A node of an `Ast`, with the span of its text in `Ast::text`
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone)]
+pub struct AstNode {
+    pub kind: NodeKind,
+    pub span: Span,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<AstNode>,
+}
+
+/// Syntax tree of an ingredient line, the low-level view of what the grammar matched.
+///
+/// Unlike pest's pairs, its node kinds don't change when the grammar is refactored.
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone)]
+pub struct Ast {
+    /// The line after normalization, which spans refer to
+    pub text: String,
+    /// Top-level nodes in input order
+    pub nodes: Vec<AstNode>,
+}
+
+/// Nodes of the pairs, lifting the children of pairs without a kind into their parent
+fn nodes(pairs: Pairs<Rule>) -> Vec<AstNode> {
+    let mut nodes = Vec::new();
+    for pair in pairs {
+        push_node(pair, &mut nodes);
+    }
+    nodes
+}
+
+fn push_node(pair: Pair<Rule>, nodes: &mut Vec<AstNode>) {
+    match NodeKind::from_rule(rule_of(&pair)) {
+        Some(kind) => nodes.push(AstNode {
+            kind,
+            span: pair.as_span().into(),
+            children: if kind.is_leaf() {
+                Vec::new()
+            } else {
+                self::nodes(pair.into_inner())
+            },
+        }),
+        None => {
+            for inner in pair.into_inner() {
+                push_node(inner, nodes);
+            }
+        }
+    }
+}
+
+impl Ast {
+    /// Parse a single line of input into its syntax tree
+    pub fn parse(input: &str) -> Result<Self, IngreedyError> {
+        Self::parse_with_config(input, &ParserConfig::default())
+    }
+
+    /// Parse a single line of input into its syntax tree, with the locale, normalization
+    /// and decimal separator of the given configuration
+    pub fn parse_with_config(input: &str, config: &ParserConfig) -> Result<Self, IngreedyError> {
+        let numbers = normalize::normalize_numbers(input, config.options.decimal_separator);
+        let text = normalize::normalize_unicode(&numbers, config.normalization).into_owned();
+        let nodes = nodes(IngredientParser::parse(
+            config.locale.ingredient_rule(),
+            &text,
+        )?);
+        Ok(Self { text, nodes })
+    }
+
+    /// Text of a node
+    pub fn text_of(&self, node: &AstNode) -> &str {
+        node.span.as_str(&self.text)
+    }
+
+    /// Every node, depth first in input order
+    pub fn iter(&self) -> impl Iterator<Item = &AstNode> {
+        let mut stack: Vec<&AstNode> = self.nodes.iter().rev().collect();
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.iter().rev());
+            Some(node)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Locale;
+
+    fn kinds_and_text(ast: &Ast) -> Vec<(NodeKind, &str)> {
+        ast.iter()
+            .map(|node| (node.kind, ast.text_of(node)))
+            .collect()
+    }
+
+    #[test]
+    fn test_ast() {
+        let ast = Ast::parse("about 2-3 cups (500 ml) flour").unwrap();
+        assert_eq!(
+            kinds_and_text(&ast),
+            [
+                (NodeKind::Quantities, "about 2-3 cups (500 ml) "),
+                (NodeKind::Quantity, "about 2-3 cups (500 ml)"),
+                (NodeKind::Approximation, "about "),
+                (NodeKind::Range, "2-3"),
+                (NodeKind::Amount, "2"),
+                (NodeKind::Amount, "3"),
+                (NodeKind::Unit, "cups"),
+                (NodeKind::Parenthesized, "(500 ml)"),
+                (NodeKind::Amount, "500"),
+                (NodeKind::Unit, "ml"),
+                (NodeKind::Name, "flour"),
+            ]
+        );
+        let ast = Ast::parse("2 large eggs").unwrap();
+        assert!(kinds_and_text(&ast).contains(&(NodeKind::Size, "large")));
+        let json = serde_json::to_string(&ast).unwrap();
+        assert_eq!(serde_json::from_str::<Ast>(&json).unwrap(), ast);
+
+        let config = ParserConfig {
+            locale: Locale::Es,
+            ..ParserConfig::default()
+        };
+        let ast = Ast::parse_with_config("250 g de harina", &config).unwrap();
+        let kinds: Vec<_> = ast.iter().map(|node| node.kind).collect();
+        assert!(kinds.contains(&NodeKind::Unit));
+        assert!(kinds.contains(&NodeKind::Name));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_pest_api() {
+        let pairs =
+            crate::IngredientParser::parse(Locale::En.ingredient_rule(), "2 cups flour").unwrap();
+        let ingredient = crate::Ingredient::parse_pairs(pairs).unwrap();
+        assert_eq!(ingredient.ingredient.as_deref(), Some("flour"));
+    }
+}
@@ -0,0 +1,193 @@
+//! Parsing many ingredient lines at once, keeping track of which line each result is for
+
+#[cfg(feature = "rayon")]
+use crate::ParserContext;
+use crate::{Ingredient, IngreedyError, ParserConfig};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::io::BufRead;
+
+/// Parse newline-delimited ingredient lines, skipping blank lines,
+/// pairing each result with its 1-based line number in `text`
+pub fn parse_lines(text: &str) -> Vec<(usize, Result<Ingredient, IngreedyError>)> {
+    parse_lines_iter(text).collect()
+}
+
+/// Parse newline-delimited ingredient lines like `parse_lines`, one line at a time
+pub fn parse_lines_iter(
+    text: &str,
+) -> impl Iterator<Item = (usize, Result<Ingredient, IngreedyError>)> + '_ {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| (index + 1, Ingredient::parse(line)))
+}
+
+/// Ingredients parsed lazily from newline-delimited lines of a reader, one line in memory
+/// at a time, each paired with its 1-based line number; blank lines are skipped.
+///
+/// A read error (including invalid UTF-8) is yielded as `IngreedyError::Io` and ends the stream.
+pub struct IngredientStream<R> {
+    reader: R,
+    config: ParserConfig,
+    line: String,
+    line_number: usize,
+    done: bool,
+}
+
+impl<R: BufRead> IngredientStream<R> {
+    pub fn from_reader(reader: R) -> Self {
+        Self::with_config(reader, ParserConfig::default())
+    }
+
+    /// Stream parsing lines with the given configuration
+    pub fn with_config(reader: R, config: ParserConfig) -> Self {
+        Self {
+            reader,
+            config,
+            line: String::new(),
+            line_number: 0,
+            done: false,
+        }
+    }
+
+    /// Line number of the last line read
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// Last line read, without its line ending
+    pub fn line(&self) -> &str {
+        self.line.trim_end_matches(&['\n', '\r'][..])
+    }
+}
+
+impl<R: BufRead> Iterator for IngredientStream<R> {
+    type Item = (usize, Result<Ingredient, IngreedyError>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.line.clear();
+            let read = self.reader.read_line(&mut self.line);
+            self.line_number += 1;
+            match read {
+                Ok(0) => self.done = true,
+                Ok(_) if self.line.trim().is_empty() => continue,
+                Ok(_) => {
+                    let result = Ingredient::parse_with_config(self.line(), &self.config);
+                    return Some((self.line_number, result));
+                }
+                Err(error) => {
+                    self.done = true;
+                    return Some((self.line_number, Err(error.into())));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Non-blank lines of `text` with their 1-based line numbers
+#[cfg(feature = "rayon")]
+fn numbered_lines(text: &str) -> Vec<(usize, &str)> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| (index + 1, line))
+        .collect()
+}
+
+/// Parse newline-delimited ingredient lines like `parse_lines`, spread across rayon's
+/// thread pool; results stay in line order
+#[cfg(feature = "rayon")]
+pub fn parse_lines_par(text: &str) -> Vec<(usize, Result<Ingredient, IngreedyError>)> {
+    numbered_lines(text)
+        .into_par_iter()
+        .map(|(number, line)| (number, Ingredient::parse(line)))
+        .collect()
+}
+
+#[cfg(feature = "rayon")]
+impl ParserContext {
+    /// Parse newline-delimited ingredient lines with this context like `parse_lines_par`,
+    /// sharing the context between threads
+    pub fn parse_lines_par(&self, text: &str) -> Vec<(usize, Result<Ingredient, IngreedyError>)> {
+        numbered_lines(text)
+            .into_par_iter()
+            .map(|(number, line)| (number, self.parse(line)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines() {
+        let results = parse_lines("2 cups flour\n\n1 tsp salt\r\n   \n3 eggs");
+        let numbers: Vec<_> = results.iter().map(|(number, _)| *number).collect();
+        assert_eq!(numbers, [1, 3, 5]);
+        assert_eq!(
+            results[1].1.as_ref().unwrap().ingredient.as_deref(),
+            Some("salt")
+        );
+        let mut lines = parse_lines_iter("1 cup milk");
+        assert!(matches!(lines.next(), Some((1, Ok(_)))));
+        assert!(lines.next().is_none());
+    }
+
+    #[test]
+    fn test_ingredient_stream() {
+        let reader = std::io::Cursor::new("2 cups flour\r\n\n1 tsp salt\n\n");
+        let mut stream = IngredientStream::from_reader(reader);
+        let (number, result) = stream.next().unwrap();
+        assert_eq!(number, 1);
+        assert_eq!(result.unwrap().ingredient.as_deref(), Some("flour"));
+        assert_eq!(stream.line(), "2 cups flour");
+        assert!(matches!(stream.next(), Some((3, Ok(_)))));
+        assert!(stream.next().is_none());
+        assert!(stream.next().is_none());
+
+        let reader = std::io::Cursor::new(&b"1 cup milk\n\xff eggs\n2 eggs"[..]);
+        let results: Vec<_> = IngredientStream::from_reader(reader).collect();
+        assert_eq!(results.len(), 2);
+        assert!(matches!(results[1], (2, Err(IngreedyError::Io(_)))));
+    }
+
+    #[test]
+    fn test_parser_state_is_shareable() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Ingredient>();
+        assert_send_sync::<IngreedyError>();
+        assert_send_sync::<crate::ParserConfig>();
+        assert_send_sync::<crate::ParserContext>();
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parse_lines_par() {
+        let text: String = (0..500)
+            .map(|i| format!("{} cups flour\n\n", i + 1))
+            .collect();
+        let ingredients = |results: Vec<(usize, Result<Ingredient, IngreedyError>)>| {
+            results
+                .into_iter()
+                .map(|(number, result)| {
+                    (
+                        number,
+                        result.ok().and_then(|ingredient| ingredient.ingredient),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ingredients(parse_lines_par(&text)),
+            ingredients(parse_lines(&text))
+        );
+        let context = ParserContext::default();
+        let results = context.parse_lines_par("1 cup milk\n\n2 eggs");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].0, 3);
+    }
+}
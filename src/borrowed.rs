@@ -0,0 +1,186 @@
+//! Parsing into output that borrows its text from the input line
+
+use crate::{
+    normalize, parser::IngredientParser, walk_pairs, Amount, DecimalSeparator, Ingredient,
+    IngreedyError, Locale, Normalization, Quantity, QuantityModifier, QuantitySource, Size, Span,
+    Unit, UnitType,
+};
+use pest::Parser;
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// A quantity whose text is borrowed from the input line
+#[derive(Debug, Serialize, Clone)]
+pub struct QuantityRef<'a> {
+    pub amount: Amount,
+    pub unit: Option<Unit>,
+    pub unit_type: Option<UnitType>,
+    pub source: QuantitySource,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modifier: Option<QuantityModifier>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container_size: Option<Box<Quantity>>,
+    /// text the quantity was parsed from, owned only if normalization changed it
+    pub raw: Cow<'a, str>,
+    #[cfg(feature = "rational")]
+    #[serde(skip)]
+    pub exact_amount: Option<num_rational::Rational64>,
+}
+
+impl<'a> QuantityRef<'a> {
+    fn new(quantity: Quantity, raw: Cow<'a, str>) -> Self {
+        Self {
+            amount: quantity.amount,
+            unit: quantity.unit,
+            unit_type: quantity.unit_type,
+            source: quantity.source,
+            modifier: quantity.modifier,
+            container_size: quantity.container_size,
+            raw,
+            #[cfg(feature = "rational")]
+            exact_amount: quantity.exact_amount,
+        }
+    }
+
+    /// The quantity with its text copied into an owned `Quantity`
+    pub fn to_owned(&self) -> Quantity {
+        Quantity {
+            amount: self.amount,
+            unit: self.unit.clone(),
+            unit_type: self.unit_type.clone(),
+            source: self.source,
+            modifier: self.modifier,
+            container_size: self.container_size.clone(),
+            raw: self.raw.clone().into_owned(),
+            #[cfg(feature = "unit-string")]
+            unit_string: self.unit.as_ref().map(|unit| unit.name().to_owned()),
+            #[cfg(feature = "rational")]
+            exact_amount: self.exact_amount,
+        }
+    }
+}
+
+/// Ingredient information whose name and text are borrowed from the input line,
+/// for pipelines that read the fields and move on without keeping the lines.
+///
+/// Text is only copied where normalization (e.g. of "½" or full-width digits) changed it.
+/// Unlike `Ingredient`, the name isn't split into footnote, usage note or preparation,
+/// and no warnings are computed until converted with `to_owned`.
+#[derive(Debug, Serialize, Clone)]
+pub struct IngredientRef<'a> {
+    /// quantities for ingredient, in the order they appear in the input
+    pub quantities: Vec<QuantityRef<'a>>,
+    /// equivalent measurements given after a "/" or "or"
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub alternatives: Vec<QuantityRef<'a>>,
+    /// ingredient name, without a leading size
+    pub ingredient: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<Size>,
+    /// the line as it was given to the parser
+    pub raw: &'a str,
+}
+
+/// Text of a span of `text`, borrowed if `text` is
+fn slice<'a>(text: &Cow<'a, str>, span: Span) -> Cow<'a, str> {
+    match text {
+        Cow::Borrowed(text) => Cow::Borrowed(span.as_str(text)),
+        Cow::Owned(text) => Cow::Owned(span.as_str(text).to_owned()),
+    }
+}
+
+impl<'a> IngredientRef<'a> {
+    /// Parse a single line of input, borrowing text from it
+    pub fn parse(input: &'a str) -> Result<Self, IngreedyError> {
+        let numbers = normalize::normalize_numbers(input, DecimalSeparator::default());
+        let text = match normalize::normalize_unicode(&numbers, Normalization::default()) {
+            Cow::Owned(text) => Cow::Owned(text),
+            Cow::Borrowed(_) => numbers,
+        };
+        let pairs = IngredientParser::parse(Locale::En.ingredient_rule(), &text)?;
+        let (mut quantities, mut alternatives) = (Vec::new(), Vec::new());
+        let (mut size, mut name_span) = (None, None);
+        walk_pairs(
+            pairs,
+            |quantity, span, replace_first| {
+                if replace_first {
+                    quantities.clear();
+                }
+                quantities.push((quantity, span.quantity));
+            },
+            |alternative| alternatives.push(alternative),
+            |piece_size| size = Some(piece_size),
+            |_, span| name_span = Some(span),
+        )?;
+        Ok(Self {
+            quantities: quantities
+                .into_iter()
+                .map(|(quantity, span)| QuantityRef::new(quantity, slice(&text, span)))
+                .collect(),
+            alternatives: alternatives
+                .into_iter()
+                .map(|mut quantity| {
+                    let raw = std::mem::take(&mut quantity.raw);
+                    QuantityRef::new(quantity, Cow::Owned(raw))
+                })
+                .collect(),
+            ingredient: name_span.map(|span| slice(&text, span)),
+            size,
+            raw: input,
+        })
+    }
+
+    /// The ingredient with its text copied into an owned `Ingredient`
+    pub fn to_owned(&self) -> Ingredient {
+        let mut ingredient = Ingredient {
+            quantities: self.quantities.iter().map(QuantityRef::to_owned).collect(),
+            alternatives: self
+                .alternatives
+                .iter()
+                .map(QuantityRef::to_owned)
+                .collect(),
+            ingredient: self.ingredient.clone().map(Cow::into_owned),
+            size: self.size,
+            raw: self.raw.to_owned(),
+            ..Ingredient::default()
+        };
+        ingredient.check_warnings();
+        ingredient
+    }
+}
+
+impl From<IngredientRef<'_>> for Ingredient {
+    fn from(ingredient: IngredientRef<'_>) -> Self {
+        ingredient.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ingredient_ref() {
+        let input = String::from("2 large eggs, beaten");
+        let ingredient = IngredientRef::parse(&input).unwrap();
+        assert!(matches!(
+            ingredient.ingredient,
+            Some(Cow::Borrowed("eggs, beaten"))
+        ));
+        assert!(matches!(ingredient.quantities[0].raw, Cow::Borrowed(_)));
+        let owned = ingredient.to_owned();
+        let parsed = Ingredient::parse(&input).unwrap();
+        assert_eq!(
+            serde_json::to_value(owned).unwrap(),
+            serde_json::to_value(parsed).unwrap()
+        );
+
+        let ingredient = IngredientRef::parse("\u{ff12} cups milk").unwrap();
+        assert!(matches!(&ingredient.quantities[0].raw, Cow::Owned(raw) if raw == "2 cups"));
+        assert_eq!(ingredient.raw, "\u{ff12} cups milk");
+        assert_eq!(
+            Ingredient::from(ingredient).ingredient.as_deref(),
+            Some("milk")
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Memoizing parses of repeated lines ("salt", "1 cup sugar") in large datasets
+
+use crate::{Ingredient, IngreedyError, ParserContext};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// Lookups of a `CachedParser` so far
+#[derive(Debug, Default, Eq, PartialEq, Serialize, Clone, Copy)]
+pub struct CacheStats {
+    /// Lines answered from the cache
+    pub hits: u64,
+    /// Lines parsed, including lines that failed to parse
+    pub misses: u64,
+    /// Entries dropped to stay within capacity
+    pub evictions: u64,
+}
+
+impl CacheStats {
+    /// Share of lookups answered from the cache, from 0 to 1
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// A parser that keeps the results of the most recently used lines, returning clones
+/// of them when a line repeats.
+///
+/// Only lines that parse are kept; failures are parsed again each time.
+#[derive(Debug)]
+pub struct CachedParser {
+    context: ParserContext,
+    capacity: usize,
+    /// Parsed ingredient and last use of each cached line
+    entries: HashMap<String, (Ingredient, u64)>,
+    /// Cached lines by last use, least recent first
+    recency: BTreeMap<u64, String>,
+    clock: u64,
+    stats: CacheStats,
+}
+
+impl CachedParser {
+    /// Parser with the default configuration, keeping at most `capacity` lines
+    pub fn new(capacity: usize) -> Self {
+        Self::with_context(ParserContext::default(), capacity)
+    }
+
+    /// Parser with the given context, keeping at most `capacity` lines
+    pub fn with_context(context: impl Into<ParserContext>, capacity: usize) -> Self {
+        Self {
+            context: context.into(),
+            capacity,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            clock: 0,
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Parse a single line of input, or clone its earlier result
+    pub fn parse(&mut self, input: &str) -> Result<Ingredient, IngreedyError> {
+        self.clock += 1;
+        if let Some((ingredient, last_use)) = self.entries.get_mut(input) {
+            self.stats.hits += 1;
+            let line = self.recency.remove(last_use).unwrap_or_default();
+            *last_use = self.clock;
+            self.recency.insert(self.clock, line);
+            return Ok(ingredient.clone());
+        }
+        self.stats.misses += 1;
+        let ingredient = self.context.parse(input)?;
+        if self.capacity == 0 {
+            return Ok(ingredient);
+        }
+        if self.entries.len() >= self.capacity {
+            if let Some((_, line)) = self.recency.pop_first() {
+                self.entries.remove(&line);
+                self.stats.evictions += 1;
+            }
+        }
+        self.entries
+            .insert(input.to_owned(), (ingredient.clone(), self.clock));
+        self.recency.insert(self.clock, input.to_owned());
+        Ok(ingredient)
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Number of lines cached
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Drop every cached line, keeping the stats
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    pub fn context(&self) -> &ParserContext {
+        &self.context
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParseOptions, ParserConfig};
+
+    #[test]
+    fn test_cached_parser() {
+        let mut parser = CachedParser::new(2);
+        for line in [
+            "salt",
+            "1 cup sugar",
+            "salt",
+            "2 eggs",
+            "1 cup sugar",
+            "salt",
+        ] {
+            parser.parse(line).unwrap();
+        }
+        // "1 cup sugar" was the least recently used line when "2 eggs" came in
+        assert_eq!(
+            parser.stats(),
+            CacheStats {
+                hits: 1,
+                misses: 5,
+                evictions: 3,
+            }
+        );
+        assert_eq!(parser.len(), 2);
+        let sugar = parser.parse("1 cup sugar").unwrap();
+        assert_eq!(sugar.ingredient.as_deref(), Some("sugar"));
+        assert_eq!(parser.stats().hits, 2);
+        assert_eq!(parser.stats().hit_rate(), 2. / 7.);
+
+        let config = ParserConfig {
+            options: ParseOptions::new().strict(true),
+            ..ParserConfig::default()
+        };
+        let mut parser = CachedParser::with_context(config, 10);
+        assert!(parser.parse("salt").is_err());
+        assert!(parser.parse("salt").is_err());
+        assert_eq!(parser.stats().misses, 2);
+        assert!(parser.is_empty());
+    }
+}
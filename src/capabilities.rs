@@ -0,0 +1,116 @@
+//! Runtime description of what this build of the parser supports
+
+use crate::locale::LOCALES;
+use crate::unit::BUILTIN_UNITS;
+use crate::{Locale, Unit, UnitCategory, UnitType};
+use serde::Serialize;
+
+/// Version of the grammar, bumped in each release that changes how existing input parses
+pub const GRAMMAR_VERSION: u32 = 1;
+
+/// Version of the serialized output, bumped in each release that renames fields or values
+/// (2: `UnitType` names are lowercase)
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Cargo features of the crate, and whether this build was compiled with them
+const FEATURES: &[(&str, bool)] = &[
+    ("cli", cfg!(feature = "cli")),
+    ("unit-string", cfg!(feature = "unit-string")),
+    ("reference-tables", cfg!(feature = "reference-tables")),
+    ("arena", cfg!(feature = "arena")),
+    ("stemmer", cfg!(feature = "stemmer")),
+    ("rational", cfg!(feature = "rational")),
+    ("schema", cfg!(feature = "schema")),
+    ("python", cfg!(feature = "python")),
+    ("ffi", cfg!(feature = "ffi")),
+    ("miette", cfg!(feature = "miette")),
+    ("allergens", cfg!(feature = "allergens")),
+    ("dietary", cfg!(feature = "dietary")),
+    ("html", cfg!(feature = "html")),
+    ("rayon", cfg!(feature = "rayon")),
+    ("wasm", cfg!(feature = "wasm")),
+];
+
+/// A unit the grammar recognizes
+#[derive(Debug, Serialize, Clone)]
+pub struct UnitCapability {
+    pub unit: Unit,
+    pub abbreviation: String,
+    /// Spellings the parser accepts, see `Unit::aliases`
+    pub aliases: Vec<&'static str>,
+    pub unit_type: UnitType,
+    pub category: UnitCategory,
+}
+
+/// What this build of the parser supports, for reporting in diagnostics
+/// or negotiating behavior with plugins and services
+#[non_exhaustive]
+#[derive(Debug, Serialize, Clone)]
+pub struct Capabilities {
+    /// Crate version (e.g. "0.2.0")
+    pub version: &'static str,
+    /// See `GRAMMAR_VERSION`
+    pub grammar_version: u32,
+    /// See `SCHEMA_VERSION`
+    pub schema_version: u32,
+    /// Built-in units, not counting those registered at runtime
+    pub units: Vec<UnitCapability>,
+    /// Languages with a grammar
+    pub locales: Vec<Locale>,
+    /// Cargo features enabled in this build
+    pub features: Vec<&'static str>,
+}
+
+/// Describe the units, locales and features supported by this build
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        version: env!("CARGO_PKG_VERSION"),
+        grammar_version: GRAMMAR_VERSION,
+        schema_version: SCHEMA_VERSION,
+        units: BUILTIN_UNITS
+            .iter()
+            .map(|unit| UnitCapability {
+                unit: unit.clone(),
+                abbreviation: unit.abbreviation().to_owned(),
+                aliases: unit.aliases().collect(),
+                unit_type: unit.unit_type(),
+                category: unit.category(),
+            })
+            .collect(),
+        locales: LOCALES.to_vec(),
+        features: FEATURES
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| *name)
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities() {
+        let capabilities = capabilities();
+        assert_eq!(capabilities.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            capabilities.locales,
+            vec![Locale::En, Locale::Es, Locale::Fr, Locale::Zh, Locale::Ja]
+        );
+        let cup = capabilities
+            .units
+            .iter()
+            .find(|capability| capability.unit == Unit::Cup)
+            .unwrap();
+        assert_eq!(cup.abbreviation, "c");
+        assert!(cup.aliases.contains(&"cups"));
+        assert_eq!(cup.category, UnitCategory::Volume);
+        assert_eq!(
+            capabilities.features.contains(&"arena"),
+            cfg!(feature = "arena")
+        );
+        let json = serde_json::to_value(&capabilities).unwrap();
+        assert_eq!(json["units"][0]["unit"], "calorie");
+    }
+}
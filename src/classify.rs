@@ -0,0 +1,233 @@
+//! Telling ingredient lines from instructions and headers in recipe text
+
+use crate::recipe::{header, parse_yield};
+use crate::Ingredient;
+use serde::{Deserialize, Serialize};
+
+/// What a line of recipe text is
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum LineKind {
+    /// An ingredient (e.g. "2 cups flour" or "salt")
+    Ingredient,
+    /// A step of the method (e.g. "Preheat the oven to 180C.")
+    Instruction,
+    /// A section header (e.g. "For the sauce:")
+    Header,
+    /// How much the recipe makes (e.g. "Serves 4")
+    Yield,
+    /// A blank line or anything else
+    Other,
+}
+
+/// The kind of a line, with how sure `classify_line_with_confidence` is of it
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Copy)]
+pub struct LineClassification {
+    pub kind: LineKind,
+    /// From 0 (a guess) to 1 (certain)
+    pub confidence: f64,
+}
+
+/// Words that start instructions ("Preheat the oven", "In a large bowl, whisk")
+const INSTRUCTION_OPENERS: &[&str] = &[
+    "add", "allow", "arrange", "bake", "beat", "blend", "boil", "bring", "brush", "chill", "chop",
+    "combine", "cook", "cool", "cover", "cut", "dice", "divide", "drain", "drizzle", "fold", "fry",
+    "garnish", "grate", "grease", "grill", "heat", "in", "knead", "let", "line", "melt", "mix",
+    "once", "place", "pour", "preheat", "put", "reduce", "remove", "repeat", "rinse", "roast",
+    "roll", "saute", "sauté", "season", "serve", "set", "simmer", "slice", "spoon", "spread",
+    "sprinkle", "stir", "then", "toss", "transfer", "when", "whisk",
+];
+
+/// Words that come up in instructions but rarely in ingredient lines
+const INSTRUCTION_CUES: &[&str] = &[
+    "minutes", "minute", "hours", "hour", "until", "oven", "degrees", "bowl", "pan", "skillet",
+];
+
+/// Minimum score for a line to be an ingredient or instruction rather than `LineKind::Other`
+const MIN_SCORE: f64 = 0.3;
+
+/// Strip step numbering off the start of a line ("1.", "2)", "Step 3:")
+fn strip_step_number(line: &str) -> &str {
+    let line = line.trim_start();
+    let after_step = match line.get(..4) {
+        Some(word) if word.eq_ignore_ascii_case("step") => Some(line[4..].trim_start()),
+        _ => None,
+    };
+    let numbered = after_step.unwrap_or(line);
+    let rest = numbered.trim_start_matches(|c: char| c.is_ascii_digit());
+    if rest.len() == numbered.len() {
+        return line;
+    }
+    let rest = match rest.strip_prefix(['.', ')', ':']) {
+        Some(rest) => rest,
+        None if after_step.is_some() => rest,
+        None => return line,
+    };
+    // "1.5 cups" is an amount, not step 1
+    if rest.starts_with(char::is_whitespace) {
+        rest.trim_start()
+    } else {
+        line
+    }
+}
+
+fn words(line: &str) -> impl Iterator<Item = String> + '_ {
+    line.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+}
+
+/// How much a line looks like an ingredient and like an instruction, each from 0 to 1
+fn scores(line: &str) -> (f64, f64) {
+    let step = strip_step_number(line);
+    let word_count = words(step).count();
+    let starts_with_verb = words(step)
+        .next()
+        .is_some_and(|word| INSTRUCTION_OPENERS.contains(&word.as_str()));
+    let ends_sentence = step.ends_with(['.', '!']);
+    let sentences = step
+        .split(". ")
+        .filter(|sentence| !sentence.trim().is_empty())
+        .count();
+    let has_cues =
+        words(step).any(|word| INSTRUCTION_CUES.contains(&word.as_str())) || step.contains('°');
+
+    let mut instruction = 0.;
+    if starts_with_verb {
+        instruction += 0.4;
+    }
+    if word_count > 10 {
+        instruction += 0.2;
+    }
+    if ends_sentence {
+        instruction += 0.15;
+    }
+    if sentences > 1 {
+        instruction += 0.1;
+    }
+    if has_cues {
+        instruction += 0.15;
+    }
+
+    let mut ingredient = 0.;
+    if let Ok(parsed) = Ingredient::parse(line) {
+        let mut quantities = parsed
+            .quantities
+            .iter()
+            .filter(|quantity| quantity.measures_ingredient());
+        if quantities.clone().next().is_some() {
+            ingredient += 0.45;
+        }
+        if quantities.any(|quantity| quantity.unit.is_some()) {
+            ingredient += 0.2;
+        }
+        if parsed.ingredient.is_some() && !starts_with_verb {
+            ingredient += 0.2;
+        }
+    }
+    if word_count <= 8 {
+        ingredient += 0.15;
+    }
+    if !ends_sentence && !has_cues {
+        ingredient += 0.1;
+    }
+    (f64::min(ingredient, 1.), f64::min(instruction, 1.))
+}
+
+/// Classify a line of recipe text, to decide whether to run the ingredient parser on it.
+///
+/// Yields and headers are recognized like in `Recipe::parse`; other lines are scored on
+/// quantity and unit cues, length, and whether they start with a cooking verb.
+pub fn classify_line(line: &str) -> LineKind {
+    classify_line_with_confidence(line).kind
+}
+
+/// Classify a line of recipe text like `classify_line`, with a confidence score
+pub fn classify_line_with_confidence(line: &str) -> LineClassification {
+    let classification = |kind, confidence| LineClassification { kind, confidence };
+    let line = line.trim();
+    if line.is_empty() {
+        return classification(LineKind::Other, 1.);
+    }
+    if parse_yield(line).is_some() {
+        return classification(LineKind::Yield, 0.95);
+    }
+    if header(line).is_some() {
+        let confidence = if words(line).count() <= 6 { 0.9 } else { 0.6 };
+        return classification(LineKind::Header, confidence);
+    }
+    let (ingredient, instruction) = scores(line);
+    let (kind, score, other_score) = if ingredient >= instruction {
+        (LineKind::Ingredient, ingredient, instruction)
+    } else {
+        (LineKind::Instruction, instruction, ingredient)
+    };
+    if score < MIN_SCORE {
+        return classification(LineKind::Other, 1. - score);
+    }
+    classification(kind, (score - other_score / 2.).clamp(0., 1.))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_line() {
+        for (line, kind) in [
+            ("2 cups flour", LineKind::Ingredient),
+            (
+                "1 (14 oz) can diced tomatoes, drained",
+                LineKind::Ingredient,
+            ),
+            ("salt", LineKind::Ingredient),
+            (
+                "Freshly ground black pepper, to taste",
+                LineKind::Ingredient,
+            ),
+            ("Preheat the oven to 350°F.", LineKind::Instruction),
+            (
+                "2. In a large bowl, whisk together the flour and sugar.",
+                LineKind::Instruction,
+            ),
+            (
+                "Add 2 tbsp oil and cook for 5 minutes until golden",
+                LineKind::Instruction,
+            ),
+            ("Step 3: Bake for 25 minutes.", LineKind::Instruction),
+            ("For the sauce:", LineKind::Header),
+            ("Serves 4", LineKind::Yield),
+            ("", LineKind::Other),
+            ("----", LineKind::Other),
+        ] {
+            assert_eq!(classify_line(line), kind, "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_confidence() {
+        let sure = classify_line_with_confidence("2 cups flour");
+        let unsure = classify_line_with_confidence("salt");
+        assert_eq!(sure.kind, LineKind::Ingredient);
+        assert_eq!(unsure.kind, LineKind::Ingredient);
+        assert!(sure.confidence > 0.9);
+        assert!(unsure.confidence < sure.confidence);
+        let instruction = classify_line_with_confidence("Preheat the oven to 350°F.");
+        assert!(instruction.confidence > 0.5);
+    }
+
+    #[test]
+    fn test_strip_step_number() {
+        assert_eq!(strip_step_number("1. Mix"), "Mix");
+        assert_eq!(strip_step_number("12) Mix"), "Mix");
+        assert_eq!(strip_step_number("Step 3: Mix"), "Mix");
+        assert_eq!(strip_step_number("2 cups flour"), "2 cups flour");
+        assert_eq!(strip_step_number("1.5 cups flour"), "1.5 cups flour");
+        assert_eq!(strip_step_number("Stephanie's pie"), "Stephanie's pie");
+    }
+}
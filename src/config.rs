@@ -0,0 +1,437 @@
+//! Runtime configuration of the parser
+
+use crate::options::COUNT_UNITS;
+use crate::{
+    normalize, Amount, DensityTable, Expected, Ingredient, IngredientResolver, IngreedyError,
+    Locale, MeasurementSystem, Normalization, ParseOptions, ParsedLine, PieceWeights, Quantity,
+    QuantitySource, Recipe, Unit, UnitType,
+};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Unit names registered at runtime, for units the grammar doesn't know ("dollop", "rasher", "nub").
+///
+/// Registered names are resolved in a second pass over the parsed ingredient:
+/// "2 dollops cream" parses as 2 of "dollops cream", and the leading "dollops" is then
+/// moved into the quantity's unit.
+///
+/// Deserializes from a table of names to unit names (e.g. `tbspn = "tablespoon"`);
+/// names that aren't built-in units become imprecise `Unit::Other`s (e.g. `dollop = "dollop"`).
+#[derive(Default, Debug, Clone, Deserialize)]
+#[serde(from = "HashMap<String, Unit>")]
+pub struct UnitRegistry {
+    /// Lowercase name to unit and unit type
+    units: HashMap<String, (Unit, UnitType)>,
+}
+
+impl UnitRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a unit name, either as a synonym of a known unit
+    /// (e.g. "tbspn" as `Unit::Tablespoon`) or as a new `Unit::Other`.
+    ///
+    /// Plurals ending in "s" or "es" are matched automatically.
+    pub fn insert(&mut self, name: &str, unit: Unit, unit_type: UnitType) {
+        self.units
+            .insert(name.trim().to_lowercase(), (unit, unit_type));
+    }
+
+    /// Register a new imprecise unit under its own name (e.g. "dollop")
+    pub fn insert_imprecise(&mut self, name: &str) {
+        let name = name.trim().to_lowercase();
+        self.insert(&name, Unit::Other(name.clone()), UnitType::Imprecise);
+    }
+
+    /// Look up a registered unit name, or its singular form
+    pub fn get(&self, name: &str) -> Option<&(Unit, UnitType)> {
+        let name = name.trim().to_lowercase();
+        self.units.get(&name).or_else(|| {
+            ["es", "s"]
+                .iter()
+                .filter_map(|suffix| name.strip_suffix(suffix))
+                .find_map(|singular| self.units.get(singular))
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.units.is_empty()
+    }
+
+    /// Longest registered unit at the start of `text`, with the number of bytes it spans
+    fn match_prefix(&self, text: &str) -> Option<(&(Unit, UnitType), usize)> {
+        let word_ends = text
+            .char_indices()
+            .filter(|(_, c)| c.is_whitespace())
+            .map(|(index, _)| index)
+            .chain(std::iter::once(text.len()));
+        word_ends
+            .filter_map(|end| self.get(&text[..end]).map(|unit| (unit, end)))
+            .next_back()
+    }
+
+    /// Move a registered unit at the start of the ingredient name into its unitless quantity
+    pub(crate) fn resolve(&self, ingredient: &mut Ingredient) {
+        let name = match &ingredient.ingredient {
+            Some(name) => name,
+            None => return,
+        };
+        if ingredient.quantities.iter().any(|q| q.unit.is_some()) {
+            return;
+        }
+        if let Some(((unit, unit_type), end)) = self.match_prefix(name) {
+            let rest = normalize::strip_of(name[end..].trim_start());
+            let ingredient_name = if rest.is_empty() {
+                None
+            } else {
+                Some(rest.to_owned())
+            };
+            let (unit, unit_type) = (unit.clone(), unit_type.clone());
+            let word = &name[..end];
+            if ingredient.quantities.is_empty() {
+                ingredient.quantities.push(Quantity {
+                    amount: Amount::Exact(1.),
+                    ..Quantity::default()
+                });
+            }
+            let quantity = ingredient.quantities.last_mut().unwrap();
+            quantity.set_unit(unit);
+            quantity.unit_type = Some(unit_type);
+            quantity.source = QuantitySource::RegisteredUnit;
+            quantity.raw = if quantity.raw.is_empty() {
+                word.to_owned()
+            } else {
+                format!("{} {}", quantity.raw, word)
+            };
+            ingredient.ingredient = ingredient_name;
+        }
+    }
+}
+
+impl From<HashMap<String, Unit>> for UnitRegistry {
+    fn from(units: HashMap<String, Unit>) -> Self {
+        let mut registry = Self::new();
+        for (name, unit) in units {
+            let unit_type = unit.unit_type();
+            registry.insert(&name, unit, unit_type);
+        }
+        registry
+    }
+}
+
+/// Parser settings beyond what the grammar fixes.
+///
+/// Deserializes with every field optional, e.g. from a TOML file shared by a team.
+#[derive(Default, Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ParserConfig {
+    /// Language of the ingredient lines
+    pub locale: Locale,
+    /// Extra unit names to recognize
+    pub units: UnitRegistry,
+    /// Unicode normalization applied before parsing
+    pub normalization: Normalization,
+    /// Words dropped from ingredient names, case-insensitively (e.g. "organic")
+    pub stopwords: Vec<String>,
+    /// Parser behavior toggles, given alongside the other settings when deserialized
+    #[serde(flatten)]
+    pub options: ParseOptions,
+}
+
+impl ParserConfig {
+    /// Ingredient name without stopwords, or `None` if nothing is left
+    fn remove_stopwords(&self, name: &str) -> Option<String> {
+        let name = name
+            .split_whitespace()
+            .filter(|word| {
+                !self
+                    .stopwords
+                    .iter()
+                    .any(|stopword| stopword.eq_ignore_ascii_case(word))
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        if name.is_empty() {
+            None
+        } else {
+            Some(name)
+        }
+    }
+}
+
+/// Everything a parser needs: settings, reference tables and an optional `IngredientResolver`.
+///
+/// A context is immutable, `Send + Sync`, and cheap to clone (clones share the same data),
+/// so one built at startup can be shared by every thread that parses.
+#[derive(Default, Debug, Clone)]
+pub struct ParserContext(Arc<ContextData>);
+
+#[derive(Default, Debug, Clone)]
+struct ContextData {
+    config: ParserConfig,
+    densities: DensityTable,
+    piece_weights: PieceWeights,
+    resolver: Option<SharedResolver>,
+}
+
+#[derive(Clone)]
+struct SharedResolver(Arc<dyn IngredientResolver + Send + Sync>);
+
+impl fmt::Debug for SharedResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("IngredientResolver")
+    }
+}
+
+impl ParserContext {
+    pub fn new(config: ParserConfig, densities: DensityTable, piece_weights: PieceWeights) -> Self {
+        Self(Arc::new(ContextData {
+            config,
+            densities,
+            piece_weights,
+            resolver: None,
+        }))
+    }
+
+    /// The same context, also setting `Ingredient::id` of parsed ingredients with a resolver
+    pub fn with_resolver(&self, resolver: impl IngredientResolver + Send + Sync + 'static) -> Self {
+        let mut data = ContextData::clone(&self.0);
+        data.resolver = Some(SharedResolver(Arc::new(resolver)));
+        Self(Arc::new(data))
+    }
+
+    pub fn config(&self) -> &ParserConfig {
+        &self.0.config
+    }
+
+    pub fn densities(&self) -> &DensityTable {
+        &self.0.densities
+    }
+
+    pub fn piece_weights(&self) -> &PieceWeights {
+        &self.0.piece_weights
+    }
+
+    /// Parse a single line of input
+    pub fn parse(&self, input: &str) -> Result<Ingredient, IngreedyError> {
+        let mut ingredient = Ingredient::parse_with_config(input, self.config())?;
+        if let Some(SharedResolver(resolver)) = &self.0.resolver {
+            ingredient.resolve(resolver.as_ref());
+        }
+        Ok(ingredient)
+    }
+
+    /// Parse a block of ingredient lines
+    pub fn parse_recipe(&self, text: &str) -> Recipe {
+        let mut recipe = Recipe::parse_with_config(text, self.config());
+        if let Some(SharedResolver(resolver)) = &self.0.resolver {
+            for line in &mut recipe.lines {
+                if let ParsedLine::Ingredient(ingredient) = line {
+                    ingredient.resolve(resolver.as_ref());
+                }
+            }
+        }
+        recipe
+    }
+
+    /// Resolve a quantity of an ingredient to grams with the context's reference tables
+    pub fn to_grams(
+        &self,
+        quantity: &Quantity,
+        ingredient_name: &str,
+    ) -> Result<f64, IngreedyError> {
+        quantity.to_grams(ingredient_name, self.densities(), self.piece_weights())
+    }
+}
+
+impl From<ParserConfig> for ParserContext {
+    fn from(config: ParserConfig) -> Self {
+        Self::new(config, DensityTable::default(), PieceWeights::default())
+    }
+}
+
+impl Ingredient {
+    /// Parse a single line of input with the given configuration
+    pub fn parse_with_config(input: &str, config: &ParserConfig) -> Result<Self, IngreedyError> {
+        let options = &config.options;
+        let normalized = normalize::normalize_numbers(input, options.decimal_separator);
+        let mut ingredient =
+            Self::parse_normalized(&normalized, config.locale, config.normalization, options)?;
+        if let Cow::Owned(_) = normalized {
+            ingredient.raw = input.to_owned();
+        }
+        if options.count_units {
+            COUNT_UNITS.resolve(&mut ingredient);
+        }
+        if !config.units.is_empty() {
+            config.units.resolve(&mut ingredient);
+        }
+        let imperial = options.measurement_system == MeasurementSystem::Imperial;
+        if imperial {
+            for quantity in ingredient
+                .quantities
+                .iter_mut()
+                .chain(&mut ingredient.alternatives)
+            {
+                quantity.use_imperial();
+            }
+        }
+        if options.count_units || !config.units.is_empty() || imperial {
+            ingredient.check_warnings();
+        }
+        if !config.stopwords.is_empty() {
+            ingredient.ingredient = ingredient
+                .ingredient
+                .as_deref()
+                .and_then(|name| config.remove_stopwords(name));
+        }
+        if options.strict {
+            if ingredient.quantities.is_empty() {
+                return Err(IngreedyError::Incomplete(Expected::Quantity));
+            }
+            if ingredient.ingredient.is_none() {
+                return Err(IngreedyError::Incomplete(Expected::Ingredient));
+            }
+        }
+        Ok(ingredient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DecimalSeparator;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_context_shared_across_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ParserContext>();
+
+        let mut densities = DensityTable::new();
+        densities.insert("flour", 0.53);
+        let context = ParserContext::new(config(), densities, PieceWeights::new());
+        let handles: Vec<_> = ["2 dollops cream", "1 cup flour"]
+            .iter()
+            .map(|input| {
+                let context = context.clone();
+                std::thread::spawn(move || context.parse(input).unwrap())
+            })
+            .collect();
+        let ingredients: Vec<_> = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect();
+        assert_eq!(
+            ingredients[0].quantities[0].unit,
+            Some(Unit::Other("dollop".into()))
+        );
+        let grams = context
+            .to_grams(&ingredients[1].quantities[0], "flour")
+            .unwrap();
+        assert_relative_eq!(grams, 125.39, epsilon = 0.01);
+    }
+
+    fn config() -> ParserConfig {
+        let mut config = ParserConfig::default();
+        config.units.insert_imprecise("dollop");
+        config.units.insert_imprecise("rasher");
+        config
+            .units
+            .insert("tbspn", Unit::Tablespoon, UnitType::English);
+        config
+    }
+
+    #[test]
+    fn test_registered_units() {
+        let config = config();
+        let ingredient = Ingredient::parse_with_config("2 dollops of cream", &config).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.);
+        assert_eq!(
+            ingredient.quantities[0].unit,
+            Some(Unit::Other("dollop".into()))
+        );
+        assert_eq!(
+            ingredient.quantities[0].unit_type,
+            Some(UnitType::Imprecise)
+        );
+        assert_eq!(ingredient.ingredient, Some("cream".to_string()));
+        assert_eq!(
+            ingredient.quantities[0].source,
+            QuantitySource::RegisteredUnit
+        );
+
+        let ingredient = Ingredient::parse_with_config("a rasher bacon", &config).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.);
+        assert_eq!(
+            ingredient.quantities[0].unit,
+            Some(Unit::Other("rasher".into()))
+        );
+        assert_eq!(ingredient.ingredient, Some("bacon".to_string()));
+
+        let ingredient = Ingredient::parse_with_config("3 tbspn sugar", &config).unwrap();
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Tablespoon));
+        assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
+        assert_eq!(ingredient.ingredient, Some("sugar".to_string()));
+    }
+
+    #[test]
+    fn test_config_deserialize() {
+        let config: ParserConfig = serde_json::from_value(serde_json::json!({
+            "locale": "es",
+            "units": { "dollop": "dollop", "tbspn": "tablespoon" },
+            "stopwords": ["organic", "Fresh"],
+        }))
+        .unwrap();
+        assert_eq!(config.locale, Locale::Es);
+        assert_eq!(config.normalization, Normalization::Nfc);
+        assert_eq!(
+            config.units.get("dollops"),
+            Some(&(Unit::Other("dollop".into()), UnitType::Imprecise))
+        );
+        assert_eq!(
+            config.units.get("tbspn"),
+            Some(&(Unit::Tablespoon, UnitType::English))
+        );
+
+        let config = ParserConfig {
+            locale: Locale::En,
+            ..config
+        };
+        let ingredient =
+            Ingredient::parse_with_config("1 dollop fresh organic cream", &config).unwrap();
+        assert_eq!(ingredient.ingredient, Some("cream".to_string()));
+    }
+
+    #[test]
+    fn test_decimal_separator() {
+        let ingredient =
+            Ingredient::parse_with_config("1,000 g flour", &ParserConfig::default()).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1000.);
+        let config = ParserConfig {
+            options: ParseOptions::new().decimal_separator(DecimalSeparator::Comma),
+            ..ParserConfig::default()
+        };
+        let ingredient = Ingredient::parse_with_config("1,5 kg Mehl", &config).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.5);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Kilogram));
+        assert_eq!(ingredient.raw, "1,5 kg Mehl");
+        let ingredient = Ingredient::parse_with_config("1.000 g Mehl", &config).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1000.);
+    }
+
+    #[test]
+    fn test_registered_units_leave_others_alone() {
+        let config = config();
+        let ingredient = Ingredient::parse_with_config("2 cups knobbly carrots", &config).unwrap();
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
+        assert_eq!(ingredient.ingredient, Some("knobbly carrots".to_string()));
+        let ingredient = Ingredient::parse_with_config("2 knobbly carrots", &config).unwrap();
+        assert_eq!(ingredient.quantities[0].unit, None);
+        assert_eq!(ingredient.ingredient, Some("knobbly carrots".to_string()));
+    }
+}
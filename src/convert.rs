@@ -0,0 +1,306 @@
+//! Unit conversion between metric and English quantities
+
+use crate::{IngreedyError, Quantity, Temperature, TemperatureScale, Unit, UnitCategory, UnitType};
+use serde::{Deserialize, Serialize};
+
+/// Which pints, quarts, gallons, fluid ounces and cups a recipe means
+#[derive(Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum MeasurementSystem {
+    /// US customary measures (a 473 ml pint)
+    #[default]
+    Us,
+    /// British imperial measures (a 568 ml pint), parsed with `UnitType::Imperial`
+    Imperial,
+}
+
+impl Unit {
+    /// Number of base units (gram, milliliter, joule, second or centimeter) in one of this unit.
+    ///
+    /// Count units (pinch, dash, ...) and temperatures, which don't start at zero,
+    /// have no conversion factor.
+    pub(crate) fn factor(&self) -> Option<f64> {
+        let factor = match self {
+            // mass, in grams
+            Self::Microgram => 0.000_001,
+            Self::Milligram => 0.001,
+            Self::Gram => 1.,
+            Self::Kilogram => 1000.,
+            Self::Ounce => 28.349_523_125,
+            Self::Pound => 453.592_37,
+            Self::Liang => 50.,
+            Self::Jin => 500.,
+            // volume, in milliliters
+            Self::Milliliter => 1.,
+            Self::Centiliter => 10.,
+            Self::Deciliter => 100.,
+            Self::Liter => 1000.,
+            Self::Teaspoon => 4.928_921_593_75,
+            Self::Tablespoon => 14.786_764_781_25,
+            Self::FluidOunce => 29.573_529_562_5,
+            Self::Cup => 236.588_236_5,
+            Self::Pint => 473.176_473,
+            Self::Quart => 946.352_946,
+            Self::Gallon => 3_785.411_784,
+            Self::Go => 180.39,
+            // energy, in joules (a food calorie is a kilocalorie)
+            Self::Joule => 1.,
+            Self::Kilojoule => 1000.,
+            Self::Calorie => 4184.,
+            // time, in seconds
+            Self::Second => 1.,
+            Self::Minute => 60.,
+            Self::Hour => 3600.,
+            // length, in centimeters
+            Self::Millimeter => 0.1,
+            Self::Centimeter => 1.,
+            Self::Inch => 2.54,
+            Self::Fahrenheit
+            | Self::Celsius
+            | Self::Dash
+            | Self::Drop
+            | Self::Smidgen
+            | Self::Handful
+            | Self::Pinch
+            | Self::Touch
+            | Self::Splash
+            | Self::Knob
+            | Self::Glug
+            | Self::Serving
+            | Self::Scoop
+            | Self::Square
+            | Self::Can
+            | Self::Jar
+            | Self::Package
+            | Self::Stick
+            | Self::Bag
+            | Self::Bottle
+            | Self::Bar
+            | Self::Tub
+            | Self::Clove
+            | Self::Sprig
+            | Self::Slice
+            | Self::Stalk
+            | Self::Rib
+            | Self::Head
+            | Self::Ear
+            | Self::Fillet
+            | Self::Bunch
+            | Self::Piece
+            | Self::Other(_) => return None,
+        };
+        Some(factor)
+    }
+
+    /// Number of milliliters in one British imperial unit, for the volumes that differ
+    /// from their US counterparts
+    pub(crate) fn imperial_factor(&self) -> Option<f64> {
+        let factor = match self {
+            Self::FluidOunce => 28.413_062_5,
+            Self::Cup => 284.130_625,
+            Self::Pint => 568.261_25,
+            Self::Quart => 1_136.522_5,
+            Self::Gallon => 4_546.09,
+            _ => return None,
+        };
+        Some(factor)
+    }
+}
+
+impl Quantity {
+    /// Number of base units in one of the quantity's unit, in imperial measure
+    /// if the quantity was parsed as one
+    pub(crate) fn factor(&self) -> Option<f64> {
+        let unit = self.unit.as_ref()?;
+        Some(unit)
+            .filter(|_| self.unit_type == Some(UnitType::Imperial))
+            .and_then(Unit::imperial_factor)
+            .or_else(|| unit.factor())
+    }
+
+    /// Mark US customary volumes that have imperial counterparts as imperial,
+    /// along with the container size
+    pub(crate) fn use_imperial(&mut self) {
+        let has_imperial = self.unit.as_ref().and_then(Unit::imperial_factor).is_some();
+        if has_imperial && self.unit_type == Some(UnitType::English) {
+            self.unit_type = Some(UnitType::Imperial);
+        }
+        if let Some(size) = &mut self.container_size {
+            size.use_imperial();
+        }
+    }
+
+    /// Convert the quantity to another unit of the same category (e.g. cups to milliliters).
+    ///
+    /// Imperial quantities convert from their imperial size; the converted quantity
+    /// is always in US customary or metric units.
+    pub fn convert_to(&self, unit: Unit) -> Result<Self, IngreedyError> {
+        let from_unit = self.unit.as_ref().ok_or(IngreedyError::MissingUnit)?;
+        if let (Some(from_scale), Some(scale)) = (
+            TemperatureScale::from_unit(from_unit),
+            TemperatureScale::from_unit(&unit),
+        ) {
+            return Ok(self.convert_temperature(from_scale, scale));
+        }
+        let from_factor = self
+            .factor()
+            .ok_or_else(|| IngreedyError::UnconvertibleUnit(from_unit.to_string()))?;
+        let to_factor = unit
+            .factor()
+            .ok_or_else(|| IngreedyError::UnconvertibleUnit(unit.to_string()))?;
+        if from_unit.category() != unit.category() {
+            return Err(IngreedyError::IncompatibleUnits {
+                from: from_unit.to_string(),
+                to: unit.to_string(),
+            });
+        }
+        let mut quantity = Self {
+            amount: self.amount * (from_factor / to_factor),
+            #[cfg(feature = "rational")]
+            exact_amount: crate::rational::scale(self.exact_amount, from_factor / to_factor),
+            ..self.clone()
+        };
+        #[cfg(feature = "rational")]
+        if let Some(exact) = quantity.exact_amount {
+            quantity.amount = quantity.amount.map(|_| crate::rational::to_f64(exact));
+        }
+        quantity.set_unit(unit);
+        Ok(quantity)
+    }
+
+    fn convert_temperature(&self, from_scale: TemperatureScale, scale: TemperatureScale) -> Self {
+        let mut quantity = Self {
+            amount: self.amount.map(|degrees| {
+                Temperature::new(degrees, from_scale)
+                    .to_scale(scale)
+                    .degrees
+            }),
+            #[cfg(feature = "rational")]
+            exact_amount: None,
+            ..self.clone()
+        };
+        quantity.set_unit(scale.unit());
+        quantity
+    }
+
+    /// Convert the quantity to grams, milliliters, kilojoules, degrees Celsius or centimeters,
+    /// switching to kilograms or liters for amounts of a thousand or more; durations are kept
+    pub fn to_metric(&self) -> Result<Self, IngreedyError> {
+        let from_unit = self.unit.as_ref().ok_or(IngreedyError::MissingUnit)?;
+        // units without a factor are either temperatures or can't be converted
+        let base_amount = self.amount.value() * self.factor().unwrap_or(1.);
+        let unit = match from_unit.category() {
+            UnitCategory::Mass if base_amount >= 1000. => Unit::Kilogram,
+            UnitCategory::Mass => Unit::Gram,
+            UnitCategory::Volume if base_amount >= 1000. => Unit::Liter,
+            UnitCategory::Volume => Unit::Milliliter,
+            UnitCategory::Energy => Unit::Kilojoule,
+            UnitCategory::Temperature => Unit::Celsius,
+            UnitCategory::Time => from_unit.clone(),
+            UnitCategory::Length => Unit::Centimeter,
+            UnitCategory::Count => {
+                return Err(IngreedyError::UnconvertibleUnit(from_unit.to_string()))
+            }
+        };
+        self.convert_to(unit)
+    }
+
+    /// Convert the quantity to English units: cups, tablespoons or teaspoons for volume
+    /// (whichever gives an amount of at least a quarter cup, one tablespoon or any teaspoons),
+    /// ounces or pounds for mass, calories for energy, degrees Fahrenheit for temperature
+    /// and inches for length;
+    /// durations are kept
+    pub fn to_english(&self) -> Result<Self, IngreedyError> {
+        let from_unit = self.unit.as_ref().ok_or(IngreedyError::MissingUnit)?;
+        // units without a factor are either temperatures or can't be converted
+        let base_amount = self.amount.value() * self.factor().unwrap_or(1.);
+        let at_least = |amount: f64, unit: &Unit| base_amount >= amount * unit.factor().unwrap();
+        let unit = match from_unit.category() {
+            UnitCategory::Mass if at_least(1., &Unit::Pound) => Unit::Pound,
+            UnitCategory::Mass => Unit::Ounce,
+            UnitCategory::Volume if at_least(0.25, &Unit::Cup) => Unit::Cup,
+            UnitCategory::Volume if at_least(1., &Unit::Tablespoon) => Unit::Tablespoon,
+            UnitCategory::Volume => Unit::Teaspoon,
+            UnitCategory::Energy => Unit::Calorie,
+            UnitCategory::Temperature => Unit::Fahrenheit,
+            UnitCategory::Time => from_unit.clone(),
+            UnitCategory::Length => Unit::Inch,
+            UnitCategory::Count => {
+                return Err(IngreedyError::UnconvertibleUnit(from_unit.to_string()))
+            }
+        };
+        self.convert_to(unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UnitType;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_convert_volume() {
+        let quantity = Quantity::parse_str("1 cup").unwrap();
+        let converted = quantity.convert_to(Unit::Milliliter).unwrap();
+        assert_relative_eq!(converted.amount.value(), 236.5882365);
+        assert_eq!(converted.unit, Some(Unit::Milliliter));
+        assert_eq!(converted.unit_type, Some(UnitType::Metric));
+        let converted = Quantity::parse_str("3 tsp")
+            .unwrap()
+            .convert_to(Unit::Tablespoon)
+            .unwrap();
+        assert_relative_eq!(converted.amount.value(), 1.);
+        assert_eq!(converted.unit_type, Some(UnitType::English));
+    }
+
+    #[test]
+    fn test_to_metric() {
+        let converted = Quantity::parse_str("3lb").unwrap().to_metric().unwrap();
+        assert_relative_eq!(converted.amount.value(), 1.36077711);
+        assert_eq!(converted.unit, Some(Unit::Kilogram));
+        let converted = Quantity::parse_str("4 oz").unwrap().to_metric().unwrap();
+        assert_relative_eq!(converted.amount.value(), 113.3980925);
+        assert_eq!(converted.unit, Some(Unit::Gram));
+        let converted = Quantity::parse_str("100 kcal")
+            .unwrap()
+            .to_metric()
+            .unwrap();
+        assert_relative_eq!(converted.amount.value(), 418.4);
+        assert_eq!(converted.unit, Some(Unit::Kilojoule));
+    }
+
+    #[test]
+    fn test_to_english() {
+        let quantity = Quantity::parse_str("500 ml").unwrap().to_english().unwrap();
+        assert_eq!(quantity.unit, Some(Unit::Cup));
+        let quantity = Quantity::parse_str("15 ml").unwrap().to_english().unwrap();
+        assert_eq!(quantity.unit, Some(Unit::Tablespoon));
+        let quantity = Quantity::parse_str("1 kg").unwrap().to_english().unwrap();
+        assert_eq!(quantity.unit, Some(Unit::Pound));
+        assert_relative_eq!(quantity.amount.value(), 2.204_6, epsilon = 1e-4);
+        let quantity = Quantity::parse_str("100 g").unwrap().to_english().unwrap();
+        assert_eq!(quantity.unit, Some(Unit::Ounce));
+    }
+
+    #[test]
+    fn test_convert_errors() {
+        let cup = Quantity::parse_str("1 cup").unwrap();
+        assert!(matches!(
+            cup.convert_to(Unit::Gram),
+            Err(IngreedyError::IncompatibleUnits { .. })
+        ));
+        assert!(matches!(
+            cup.convert_to(Unit::Pinch),
+            Err(IngreedyError::UnconvertibleUnit(_))
+        ));
+        assert!(matches!(
+            Quantity::parse_str("pinch").unwrap().to_metric(),
+            Err(IngreedyError::UnconvertibleUnit(_))
+        ));
+        assert!(matches!(
+            Quantity::parse_str("3").unwrap().to_metric(),
+            Err(IngreedyError::MissingUnit)
+        ));
+    }
+}
@@ -0,0 +1,314 @@
+//! Tagging ingredients as vegan or vegetarian, or as containing meat or alcohol
+
+use crate::lexicon::PhraseLexicon;
+use crate::Ingredient;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Kind of food in an ingredient that dietary flags depend on
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum FoodCategory {
+    /// Meat and poultry, including fats and stocks made from them
+    Meat,
+    /// Fish and shellfish
+    Seafood,
+    Dairy,
+    Egg,
+    Honey,
+    /// Gelatin and other animal-derived thickeners
+    Gelatin,
+    Alcohol,
+}
+
+/// A dietary tag of an ingredient
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum DietaryFlag {
+    /// No animal products found
+    Vegan,
+    /// No meat, seafood or gelatin found
+    Vegetarian,
+    /// Meat, poultry or seafood
+    ContainsMeat,
+    ContainsAlcohol,
+}
+
+impl DietaryFlag {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Vegan => "vegan",
+            Self::Vegetarian => "vegetarian",
+            Self::ContainsMeat => "contains-meat",
+            Self::ContainsAlcohol => "contains-alcohol",
+        }
+    }
+}
+
+impl fmt::Display for DietaryFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Words and phrases of ingredient names, and the kinds of food they are
+const BUILTIN_TERMS: &[(&str, &[FoodCategory])] = {
+    use FoodCategory::*;
+    &[
+        // meat
+        ("meat", &[Meat]),
+        ("beef", &[Meat]),
+        ("steak", &[Meat]),
+        ("veal", &[Meat]),
+        ("pork", &[Meat]),
+        ("bacon", &[Meat]),
+        ("ham", &[Meat]),
+        ("pancetta", &[Meat]),
+        ("prosciutto", &[Meat]),
+        ("sausage", &[Meat]),
+        ("chorizo", &[Meat]),
+        ("salami", &[Meat]),
+        ("pepperoni", &[Meat]),
+        ("lamb", &[Meat]),
+        ("mutton", &[Meat]),
+        ("goat", &[Meat]),
+        ("venison", &[Meat]),
+        ("chicken", &[Meat]),
+        ("turkey", &[Meat]),
+        ("duck", &[Meat]),
+        ("mince", &[Meat]),
+        ("lard", &[Meat]),
+        ("suet", &[Meat]),
+        ("bone broth", &[Meat]),
+        // seafood
+        ("fish", &[Seafood]),
+        ("salmon", &[Seafood]),
+        ("tuna", &[Seafood]),
+        ("cod", &[Seafood]),
+        ("anchovies", &[Seafood]),
+        ("sardines", &[Seafood]),
+        ("shrimp", &[Seafood]),
+        ("prawns", &[Seafood]),
+        ("crab", &[Seafood]),
+        ("lobster", &[Seafood]),
+        ("scallops", &[Seafood]),
+        ("clams", &[Seafood]),
+        ("mussels", &[Seafood]),
+        ("oysters", &[Seafood]),
+        ("squid", &[Seafood]),
+        ("fish sauce", &[Seafood]),
+        ("worcestershire sauce", &[Seafood]),
+        // dairy
+        ("milk", &[Dairy]),
+        ("buttermilk", &[Dairy]),
+        ("butter", &[Dairy]),
+        ("ghee", &[Dairy]),
+        ("cream", &[Dairy]),
+        ("cheese", &[Dairy]),
+        ("parmesan", &[Dairy]),
+        ("mozzarella", &[Dairy]),
+        ("cheddar", &[Dairy]),
+        ("ricotta", &[Dairy]),
+        ("feta", &[Dairy]),
+        ("yogurt", &[Dairy]),
+        ("yoghurt", &[Dairy]),
+        ("whey", &[Dairy]),
+        // egg and honey
+        ("egg", &[Egg]),
+        ("mayonnaise", &[Egg]),
+        ("meringue", &[Egg]),
+        ("honey", &[Honey]),
+        // gelatin
+        ("gelatin", &[Gelatin]),
+        ("gelatine", &[Gelatin]),
+        // alcohol
+        ("wine", &[Alcohol]),
+        ("beer", &[Alcohol]),
+        ("ale", &[Alcohol]),
+        ("stout", &[Alcohol]),
+        ("cider", &[Alcohol]),
+        ("sherry", &[Alcohol]),
+        ("port", &[Alcohol]),
+        ("vermouth", &[Alcohol]),
+        ("sake", &[Alcohol]),
+        ("mirin", &[Alcohol]),
+        ("rum", &[Alcohol]),
+        ("vodka", &[Alcohol]),
+        ("gin", &[Alcohol]),
+        ("tequila", &[Alcohol]),
+        ("whiskey", &[Alcohol]),
+        ("whisky", &[Alcohol]),
+        ("bourbon", &[Alcohol]),
+        ("brandy", &[Alcohol]),
+        ("cognac", &[Alcohol]),
+        ("liqueur", &[Alcohol]),
+        ("kirsch", &[Alcohol]),
+        // phrases without the kinds of food their words suggest
+        ("coconut milk", &[]),
+        ("coconut cream", &[]),
+        ("almond milk", &[]),
+        ("oat milk", &[]),
+        ("soy milk", &[]),
+        ("rice milk", &[]),
+        ("peanut butter", &[]),
+        ("almond butter", &[]),
+        ("cocoa butter", &[]),
+        ("vegan butter", &[]),
+        ("vegan cheese", &[]),
+        ("cream of tartar", &[]),
+        ("eggless mayonnaise", &[]),
+        ("vegan mayonnaise", &[]),
+        ("vegetarian worcestershire sauce", &[]),
+        ("wine vinegar", &[]),
+        ("red wine vinegar", &[]),
+        ("white wine vinegar", &[]),
+        ("cider vinegar", &[]),
+        ("apple cider vinegar", &[]),
+        ("rice wine vinegar", &[]),
+        ("sherry vinegar", &[]),
+        ("malt vinegar", &[]),
+        ("ginger ale", &[]),
+        ("ginger beer", &[]),
+        ("root beer", &[]),
+        ("plant-based meat", &[]),
+        ("meat substitute", &[]),
+    ]
+};
+
+lazy_static! {
+    static ref BUILTIN_LEXICON: DietaryLexicon = DietaryLexicon::builtin();
+}
+
+/// Words and phrases of ingredient names mapped to the kinds of food they are.
+///
+/// Terms are matched as whole words by their `ingredient_key`, and the longest term wins
+/// where several start at the same word ("peanut butter" isn't dairy).
+#[derive(Default, Debug, Clone)]
+pub struct DietaryLexicon(PhraseLexicon<FoodCategory>);
+
+impl DietaryLexicon {
+    /// Empty lexicon
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lexicon of common meats, seafood, dairy, eggs, honey, gelatin and alcoholic drinks
+    pub fn builtin() -> Self {
+        let mut lexicon = Self::new();
+        for (term, categories) in BUILTIN_TERMS {
+            lexicon.insert(term, categories);
+        }
+        lexicon
+    }
+
+    /// Set the kinds of food a word or phrase is, replacing any it had.
+    ///
+    /// An empty list marks a phrase as free of the kinds of food its words suggest
+    /// (e.g. "peanut butter").
+    pub fn insert(&mut self, term: &str, categories: &[FoodCategory]) {
+        self.0.insert(term, categories);
+    }
+
+    /// Kinds of food in an ingredient name, sorted and without duplicates
+    pub fn categories(&self, name: &str) -> Vec<FoodCategory> {
+        self.0.tags(name)
+    }
+
+    /// Dietary flags of an ingredient name, sorted
+    pub fn flags(&self, name: &str) -> Vec<DietaryFlag> {
+        let categories = self.categories(name);
+        let contains = |category| categories.contains(&category);
+        let meat = contains(FoodCategory::Meat) || contains(FoodCategory::Seafood);
+        let vegetarian = !meat && !contains(FoodCategory::Gelatin);
+        let vegan = vegetarian
+            && !contains(FoodCategory::Dairy)
+            && !contains(FoodCategory::Egg)
+            && !contains(FoodCategory::Honey);
+        [
+            (vegan, DietaryFlag::Vegan),
+            (vegetarian, DietaryFlag::Vegetarian),
+            (meat, DietaryFlag::ContainsMeat),
+            (
+                contains(FoodCategory::Alcohol),
+                DietaryFlag::ContainsAlcohol,
+            ),
+        ]
+        .iter()
+        .filter(|(is_set, _)| *is_set)
+        .map(|(_, flag)| *flag)
+        .collect()
+    }
+}
+
+impl Ingredient {
+    /// Dietary flags of the ingredient from the built-in `DietaryLexicon`,
+    /// or none for an ingredient without a name.
+    ///
+    /// Ingredients the lexicon doesn't know are taken to be vegan.
+    pub fn dietary_flags(&self) -> Vec<DietaryFlag> {
+        self.dietary_flags_with(&BUILTIN_LEXICON)
+    }
+
+    /// Dietary flags of the ingredient from the given lexicon
+    pub fn dietary_flags_with(&self, lexicon: &DietaryLexicon) -> Vec<DietaryFlag> {
+        self.ingredient
+            .as_deref()
+            .map(|name| lexicon.flags(name))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use DietaryFlag::*;
+
+    fn flags(input: &str) -> Vec<DietaryFlag> {
+        Ingredient::parse(input).unwrap().dietary_flags()
+    }
+
+    #[test]
+    fn test_dietary_flags() {
+        assert_eq!(flags("2 cups flour"), [Vegan, Vegetarian]);
+        assert_eq!(flags("1 cup whole milk"), [Vegetarian]);
+        assert_eq!(flags("1 can coconut milk"), [Vegan, Vegetarian]);
+        assert_eq!(flags("2 tbsp peanut butter"), [Vegan, Vegetarian]);
+        assert_eq!(flags("3 eggs"), [Vegetarian]);
+        assert_eq!(flags("1 tbsp honey"), [Vegetarian]);
+        assert_eq!(flags("1 lb boneless chicken thighs"), [ContainsMeat]);
+        assert_eq!(flags("2 tbsp fish sauce"), [ContainsMeat]);
+        assert_eq!(flags("1 packet gelatin"), []);
+        assert_eq!(
+            flags("1 cup dry red wine"),
+            [Vegan, Vegetarian, ContainsAlcohol]
+        );
+        assert_eq!(flags("2 tbsp red wine vinegar"), [Vegan, Vegetarian]);
+        assert_eq!(
+            flags("1/2 cup beef stock and sherry"),
+            [ContainsMeat, ContainsAlcohol]
+        );
+        assert_eq!(flags("1 eggplant"), [Vegan, Vegetarian]);
+        assert_eq!(Ingredient::parse("2 cups").unwrap().dietary_flags(), []);
+    }
+
+    #[test]
+    fn test_custom_lexicon() {
+        let mut lexicon = DietaryLexicon::builtin();
+        lexicon.insert("pesto", &[FoodCategory::Dairy]);
+        lexicon.insert("vegan pesto", &[]);
+        let flags = |input| {
+            Ingredient::parse(input)
+                .unwrap()
+                .dietary_flags_with(&lexicon)
+        };
+        assert_eq!(flags("1/4 cup pesto"), [Vegetarian]);
+        assert_eq!(flags("1/4 cup vegan pesto"), [Vegan, Vegetarian]);
+        assert_eq!(
+            lexicon.categories("bacon and cheddar"),
+            [FoodCategory::Meat, FoodCategory::Dairy]
+        );
+    }
+}
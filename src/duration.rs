@@ -0,0 +1,151 @@
+//! Resting, proofing and cooking times in ingredient lines (e.g. "steak, rested 10 minutes")
+
+use crate::span::scan_quantities;
+use crate::temperature::joining_word_start;
+use crate::{Quantity, QuantitySpan, Span, UnitCategory};
+use std::time::Duration;
+
+impl Quantity {
+    /// The quantity as a duration, if its unit is hours, minutes or seconds,
+    /// taking the middle of a range
+    pub fn duration(&self) -> Option<Duration> {
+        if !self.is_duration() || self.amount.is_unspecified() {
+            return None;
+        }
+        let seconds = self.amount.value() * self.unit.as_ref()?.factor()?;
+        Some(Duration::from_secs_f64(seconds.max(0.)))
+    }
+
+    /// Whether the quantity is a length of time rather than an amount of the ingredient
+    pub fn is_duration(&self) -> bool {
+        self.unit
+            .as_ref()
+            .is_some_and(|unit| unit.category() == UnitCategory::Time)
+    }
+}
+
+/// Spellings of time units, to skip scanning names without one
+const TIME_WORDS: &[&str] = &[
+    "hr", "hrs", "hour", "hours", "min", "mins", "minute", "minutes", "sec", "secs", "second",
+    "seconds",
+];
+
+/// Whether a word of the name (or the letters after its digits, as in "10min") is a time unit
+fn has_time_word(name: &str) -> bool {
+    name.split(|c: char| !c.is_alphanumeric())
+        .map(|word| word.trim_start_matches(|c: char| c.is_ascii_digit()))
+        .any(|word| {
+            TIME_WORDS
+                .iter()
+                .any(|time_word| time_word.eq_ignore_ascii_case(word))
+        })
+}
+
+/// Words joining a duration to the text before it (e.g. "marinated for 2 hours")
+const JOINING_WORDS: &[&str] = &["for"];
+
+/// Durations in an ingredient name (e.g. "10 minutes" in "steak, rested 10 minutes"),
+/// with the spans of each and of the text to strip from the name, including a joining
+/// word before it
+pub(crate) fn find_durations(name: &str) -> Vec<(Quantity, QuantitySpan, Span)> {
+    if !has_time_word(name) {
+        return Vec::new();
+    }
+    scan_quantities(name)
+        .into_iter()
+        .filter(|(_, quantity)| quantity.is_duration())
+        .map(|(span, quantity)| {
+            let stripped = Span {
+                start: joining_word_start(&name[..span.quantity.start], JOINING_WORDS)
+                    .unwrap_or(span.quantity.start),
+                end: span.quantity.end,
+            };
+            (quantity, span, stripped)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::has_time_word;
+    use crate::{Ingredient, Quantity, Unit, UnitType};
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_duration() {
+        for (input, seconds) in [
+            ("10 minutes", 600),
+            ("2 hrs", 7200),
+            ("30 sec.", 30),
+            ("1 1/2 hours", 5400),
+            ("5-10 min", 450),
+        ] {
+            let quantity = Quantity::parse_str(input).unwrap();
+            assert_eq!(quantity.unit_type, Some(UnitType::Time), "{}", input);
+            assert_eq!(
+                quantity.duration(),
+                Some(Duration::from_secs(seconds)),
+                "{}",
+                input
+            );
+        }
+        assert_eq!(Quantity::parse_str("2 cups").unwrap().duration(), None);
+    }
+
+    #[test]
+    fn test_duration_in_name() {
+        let ingredient = Ingredient::parse("1 lb steak, rested 10 minutes").unwrap();
+        assert_eq!(ingredient.ingredient.as_deref(), Some("steak, rested"));
+        assert_eq!(ingredient.quantities.len(), 2);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Pound));
+        assert_eq!(
+            ingredient.quantities[1].duration(),
+            Some(Duration::from_secs(600))
+        );
+        let scaled = ingredient.scale(3.);
+        assert_eq!(
+            scaled.quantities[1].duration(),
+            ingredient.quantities[1].duration()
+        );
+        assert_eq!(
+            ingredient.to_string(),
+            "1 pound steak, rested for 10 minutes"
+        );
+
+        let ingredient = Ingredient::parse("2 lb chicken thighs, marinate 2 hours").unwrap();
+        assert_eq!(
+            ingredient.ingredient.as_deref(),
+            Some("chicken thighs, marinate")
+        );
+        assert_eq!(
+            ingredient.quantities[1].duration(),
+            Some(Duration::from_secs(7200))
+        );
+        let ingredient = Ingredient::parse("1 lb chicken marinated for 2 hours").unwrap();
+        assert_eq!(ingredient.ingredient.as_deref(), Some("chicken marinated"));
+
+        let ingredient = Ingredient::parse("dough proofed 2 hours").unwrap();
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Hour));
+        let spanned = Ingredient::parse_with_spans("dough proofed 2 hours").unwrap();
+        assert_eq!(
+            spanned.quantities[0]
+                .quantity
+                .as_str("dough proofed 2 hours"),
+            "2 hours"
+        );
+        assert!(Ingredient::parse("2 minced garlic cloves")
+            .unwrap()
+            .quantities[0]
+            .duration()
+            .is_none());
+    }
+
+    #[test]
+    fn test_has_time_word() {
+        assert!(has_time_word("steak, rested 10 minutes"));
+        assert!(has_time_word("dough, proofed 2 Hrs"));
+        assert!(has_time_word("chilled 30min"));
+        assert!(!has_time_word("garlic, minced"));
+        assert!(!has_time_word("secret sauce"));
+    }
+}
@@ -0,0 +1,203 @@
+//! Position and expectation details of grammar failures
+
+use crate::{locale, Rule};
+use pest::error::{ErrorVariant, LineColLocation};
+use serde::Serialize;
+use std::fmt;
+
+/// Kind of input the grammar expected where parsing failed
+#[derive(Debug, Eq, PartialEq, Hash, Serialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Expected {
+    /// An amount with or without a unit (e.g. "2 cups", "pinch")
+    Quantity,
+    /// A number, fraction or range (e.g. "2", "1 1/2", "two", "2-3")
+    Amount,
+    /// A unit (e.g. "cup", "g")
+    Unit,
+    /// The ingredient name
+    Ingredient,
+    /// The end of the input
+    EndOfInput,
+}
+
+impl Expected {
+    fn from_rule(rule: Rule) -> Option<Self> {
+        let expected = match locale::canonical_rule(rule) {
+            Rule::multipart_quantity
+            | Rule::quantity_fragment
+            | Rule::quantity
+            | Rule::amount_with_attached_units
+            | Rule::amount_with_multiplier
+            | Rule::amount_imprecise
+            | Rule::parenthesized_quantity => Self::Quantity,
+            Rule::amount
+            | Rule::amount_range
+            | Rule::float
+            | Rule::mixed_number
+            | Rule::fraction
+            | Rule::integer
+            | Rule::number
+            | Rule::compound_number
+            | Rule::written_number
+            | Rule::word_fraction
+            | Rule::dozens => Self::Amount,
+            Rule::unit | Rule::english_unit | Rule::metric_unit | Rule::imprecise_unit => {
+                Self::Unit
+            }
+            Rule::ingredient => Self::Ingredient,
+            Rule::EOI => Self::EndOfInput,
+            _ => return None,
+        };
+        Some(expected)
+    }
+
+    pub(crate) fn description(self) -> &'static str {
+        match self {
+            Self::Quantity => "a quantity",
+            Self::Amount => "an amount",
+            Self::Unit => "a unit",
+            Self::Ingredient => "an ingredient name",
+            Self::EndOfInput => "the end of the input",
+        }
+    }
+}
+
+/// Where and why the grammar failed to parse a line
+#[derive(Debug, PartialEq, Serialize, Clone)]
+pub struct ParseError {
+    /// Line of the failure, from 1
+    pub line: usize,
+    /// Column of the failure in characters, from 1
+    pub column: usize,
+    /// Text of the line the failure is on
+    pub text: String,
+    /// The word the failure starts at, empty at the end of the line
+    pub found: String,
+    /// What the grammar would have accepted instead
+    pub expected: Vec<Expected>,
+}
+
+impl ParseError {
+    /// Byte offset of the failure in `text`
+    pub fn offset(&self) -> usize {
+        self.text
+            .char_indices()
+            .nth(self.column - 1)
+            .map_or(self.text.len(), |(offset, _)| offset)
+    }
+}
+
+impl From<pest::error::Error<Rule>> for ParseError {
+    fn from(error: pest::error::Error<Rule>) -> Self {
+        let (line, column) = match error.line_col {
+            LineColLocation::Pos(position) | LineColLocation::Span(position, _) => position,
+        };
+        let mut expected = Vec::new();
+        if let ErrorVariant::ParsingError { positives, .. } = &error.variant {
+            for rule in positives {
+                if let Some(category) = Expected::from_rule(*rule) {
+                    if !expected.contains(&category) {
+                        expected.push(category);
+                    }
+                }
+            }
+        }
+        expected.sort_by_key(|category| *category as u8);
+        let mut error = Self {
+            line,
+            column,
+            text: error.line().to_owned(),
+            found: String::new(),
+            expected,
+        };
+        error.found = error.text[error.offset()..]
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_owned();
+        error
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.found.is_empty() {
+            write!(f, "Unexpected end of input")?;
+        } else {
+            write!(f, "Unexpected '{}'", self.found)?;
+        }
+        write!(f, " at line {}, column {}", self.line, self.column)?;
+        let expected: Vec<_> = self.expected.iter().map(|e| e.description()).collect();
+        match expected.split_last() {
+            Some((last, [])) => write!(f, ", expected {}", last),
+            Some((last, rest)) => write!(f, ", expected {} or {}", rest.join(", "), last),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for crate::IngreedyError {
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        match self {
+            Self::ParseError(error) => error.source_code(),
+            _ => None,
+        }
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        match self {
+            Self::ParseError(error) => error.labels(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for ParseError {
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        Some(&self.text)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        let expected: Vec<_> = self.expected.iter().map(|e| e.description()).collect();
+        let label = if expected.is_empty() {
+            None
+        } else {
+            Some(format!("expected {}", expected.join(" or ")))
+        };
+        Some(Box::new(std::iter::once(miette::LabeledSpan::new(
+            label,
+            self.offset(),
+            self.found.len(),
+        ))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Amount, Expected, IngreedyError};
+
+    #[test]
+    fn test_parse_error() {
+        let error = match Amount::parse("2 cups") {
+            Err(IngreedyError::ParseError(error)) => error,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!((error.line, error.column), (1, 3));
+        assert_eq!(error.found, "cups");
+        assert_eq!(error.offset(), 2);
+        assert!(error.expected.contains(&Expected::Amount));
+        assert!(!error.to_string().contains("Pest"));
+
+        let error = match Amount::parse("") {
+            Err(IngreedyError::ParseError(error)) => error,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(error.found, "");
+        assert_eq!(error.expected, vec![Expected::Amount]);
+    }
+}
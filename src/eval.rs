@@ -0,0 +1,349 @@
+//! Scoring the parser against labeled ingredient lines
+
+use crate::{Amount, Ingredient, ParserConfig, Unit};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// An ingredient line with the amount, unit and name it should parse to.
+///
+/// Reads the columns of NYT ingredient-phrase-tagger data too (`qty` for `amount`),
+/// where an amount of 0 and empty cells mean there is none.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize, Clone)]
+pub struct LabeledLine {
+    pub input: String,
+    #[serde(default, alias = "qty")]
+    pub amount: Option<f64>,
+    /// Name or any spelling of the unit (e.g. "cup", "tbsp")
+    #[serde(default)]
+    pub unit: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// A labeled field of an ingredient line
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum EvalField {
+    Amount,
+    Unit,
+    Name,
+}
+
+impl EvalField {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Amount => "amount",
+            Self::Unit => "unit",
+            Self::Name => "name",
+        }
+    }
+}
+
+impl fmt::Display for EvalField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Counts of how well the parser found one field
+#[derive(Debug, Default, Eq, PartialEq, Serialize, Deserialize, Clone, Copy)]
+pub struct FieldScore {
+    /// Lines where the parser found the labeled value, or nothing where there is none
+    pub correct: usize,
+    /// Lines where the parser found a value and it is the labeled one
+    pub true_positives: usize,
+    /// Lines where the parser found a value
+    pub predicted: usize,
+    /// Lines labeled with a value
+    pub labeled: usize,
+    pub total: usize,
+}
+
+impl FieldScore {
+    /// Share of lines where the field is right, from 0 to 1
+    pub fn accuracy(&self) -> f64 {
+        ratio(self.correct, self.total)
+    }
+
+    /// Share of the values found that are right
+    pub fn precision(&self) -> f64 {
+        ratio(self.true_positives, self.predicted)
+    }
+
+    /// Share of the labeled values that were found
+    pub fn recall(&self) -> f64 {
+        ratio(self.true_positives, self.labeled)
+    }
+
+    /// Count a line that failed to parse
+    fn add_error(&mut self, labeled: bool) {
+        self.total += 1;
+        self.labeled += labeled as usize;
+    }
+
+    fn add<T: PartialEq>(&mut self, expected: &Option<T>, found: &Option<T>) -> bool {
+        let correct = expected == found;
+        self.total += 1;
+        self.labeled += expected.is_some() as usize;
+        self.predicted += found.is_some() as usize;
+        self.correct += correct as usize;
+        self.true_positives += (correct && found.is_some()) as usize;
+        correct
+    }
+}
+
+/// `numerator / denominator`, or 1 when there is nothing to count
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        1.
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+/// A field the parser got wrong
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct Mismatch {
+    pub input: String,
+    pub field: EvalField,
+    /// Labeled value, empty if there is none
+    pub expected: String,
+    /// Value the parser found, or the error if the line failed to parse
+    pub found: String,
+}
+
+/// Scores of the parser on a labeled dataset, with the mismatches in dataset order
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize, Clone)]
+pub struct EvalReport {
+    pub lines: usize,
+    /// Lines with every field right
+    pub exact_matches: usize,
+    /// Lines that failed to parse, counted as wrong in every field
+    pub errors: usize,
+    pub amount: FieldScore,
+    pub unit: FieldScore,
+    pub name: FieldScore,
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl EvalReport {
+    /// Score of a field
+    pub fn score(&self, field: EvalField) -> &FieldScore {
+        match field {
+            EvalField::Amount => &self.amount,
+            EvalField::Unit => &self.unit,
+            EvalField::Name => &self.name,
+        }
+    }
+
+    /// Share of lines with every field right
+    pub fn exact_match(&self) -> f64 {
+        ratio(self.exact_matches, self.lines)
+    }
+}
+
+impl fmt::Display for EvalReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for mismatch in &self.mismatches {
+            writeln!(f, "{:?} {}", mismatch.input, mismatch.field)?;
+            writeln!(f, "- {}", mismatch.expected)?;
+            writeln!(f, "+ {}", mismatch.found)?;
+        }
+        writeln!(
+            f,
+            "{} lines, {} failed to parse, {:.1}% exact matches",
+            self.lines,
+            self.errors,
+            self.exact_match() * 100.
+        )?;
+        writeln!(f, "field   accuracy  precision  recall")?;
+        for field in [EvalField::Amount, EvalField::Unit, EvalField::Name] {
+            let score = self.score(field);
+            writeln!(
+                f,
+                "{:<6}  {:>7.1}%  {:>8.1}%  {:>5.1}%",
+                field.name(),
+                score.accuracy() * 100.,
+                score.precision() * 100.,
+                score.recall() * 100.
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Amount of the first quantity measuring the ingredient, compared to 6 decimal places
+fn amount_found(ingredient: &Ingredient) -> Option<Amount> {
+    let quantity = ingredient
+        .quantities
+        .iter()
+        .find(|quantity| quantity.measures_ingredient())?;
+    Some(quantity.amount).filter(|amount| !amount.is_unspecified())
+}
+
+fn rounded(amount: f64) -> i64 {
+    (amount * 1e6).round() as i64
+}
+
+fn display<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map(T::to_string).unwrap_or_default()
+}
+
+/// Parse each labeled line with the given configuration and score the amount, unit and name found.
+///
+/// Names are compared ignoring case and surrounding whitespace, and unit labels are read
+/// with `Unit::from_str` so "tbsp" is a tablespoon.
+pub fn evaluate<'a>(
+    lines: impl IntoIterator<Item = &'a LabeledLine>,
+    config: &ParserConfig,
+) -> EvalReport {
+    let mut report = EvalReport::default();
+    for line in lines {
+        report.lines += 1;
+        let expected_amount = line.amount.filter(|amount| *amount != 0.);
+        let expected_unit = line
+            .unit
+            .as_deref()
+            .filter(|unit| !unit.trim().is_empty())
+            .map(|unit| unit.parse().unwrap_or_else(|_| Unit::Other(unit.into())));
+        let expected_name = line
+            .name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty());
+        let ingredient = match Ingredient::parse_with_config(&line.input, config) {
+            Ok(ingredient) => ingredient,
+            Err(error) => {
+                report.errors += 1;
+                for (field, expected) in [
+                    (EvalField::Amount, display(&expected_amount)),
+                    (EvalField::Unit, display(&expected_unit)),
+                    (EvalField::Name, display(&expected_name)),
+                ] {
+                    report.mismatches.push(Mismatch {
+                        input: line.input.clone(),
+                        field,
+                        expected,
+                        found: format!("error: {}", error),
+                    });
+                }
+                report.amount.add_error(expected_amount.is_some());
+                report.unit.add_error(expected_unit.is_some());
+                report.name.add_error(expected_name.is_some());
+                continue;
+            }
+        };
+        let found_amount = amount_found(&ingredient);
+        let found_unit = ingredient
+            .quantities
+            .iter()
+            .find(|quantity| quantity.measures_ingredient())
+            .and_then(|quantity| quantity.unit.clone());
+        let found_name = ingredient.ingredient.as_deref().map(str::trim);
+        let fields = [
+            (
+                EvalField::Amount,
+                report.amount.add(
+                    &expected_amount.map(rounded),
+                    &found_amount.map(|amount| rounded(amount.value())),
+                ),
+                display(&expected_amount),
+                display(&found_amount.map(|amount| amount.value())),
+            ),
+            (
+                EvalField::Unit,
+                report.unit.add(&expected_unit, &found_unit),
+                display(&expected_unit),
+                display(&found_unit),
+            ),
+            (
+                EvalField::Name,
+                report.name.add(
+                    &expected_name.map(str::to_lowercase),
+                    &found_name.map(str::to_lowercase),
+                ),
+                display(&expected_name),
+                display(&found_name),
+            ),
+        ];
+        if fields.iter().all(|(_, correct, _, _)| *correct) {
+            report.exact_matches += 1;
+        }
+        for (field, correct, expected, found) in fields {
+            if !correct {
+                report.mismatches.push(Mismatch {
+                    input: line.input.clone(),
+                    field,
+                    expected,
+                    found,
+                });
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labeled(input: &str, amount: f64, unit: &str, name: &str) -> LabeledLine {
+        LabeledLine {
+            input: input.into(),
+            amount: Some(amount),
+            unit: Some(unit.into()),
+            name: Some(name.into()),
+        }
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let lines = [
+            labeled("2 cups flour", 2., "cups", "Flour"),
+            labeled("1 tbsp sugar", 1., "tsp", "sugar"),
+            labeled("3 eggs", 3., "", "eggs"),
+            labeled("salt", 0., "", "salt"),
+        ];
+        let report = evaluate(&lines, &ParserConfig::default());
+        assert_eq!(report.lines, 4);
+        assert_eq!(report.errors, 0);
+        assert_eq!(
+            report.unit,
+            FieldScore {
+                correct: 3,
+                true_positives: 1,
+                predicted: 2,
+                labeled: 2,
+                total: 4,
+            }
+        );
+        assert_eq!(report.unit.accuracy(), 0.75);
+        assert_eq!(report.unit.precision(), 0.5);
+        assert_eq!(report.amount.accuracy(), 1.);
+        assert_eq!(report.name.accuracy(), 1.);
+        assert_eq!(report.exact_match(), 0.75);
+        assert_eq!(
+            report.mismatches,
+            [Mismatch {
+                input: "1 tbsp sugar".into(),
+                field: EvalField::Unit,
+                expected: "teaspoon".into(),
+                found: "tablespoon".into(),
+            }]
+        );
+        let text = report.to_string();
+        assert!(text.contains("- teaspoon\n+ tablespoon"));
+        assert!(text.contains("unit       75.0%      50.0%   50.0%"));
+    }
+
+    #[test]
+    fn test_nyt_columns() {
+        let line: LabeledLine =
+            serde_json::from_str(r#"{"input": "1 cup milk", "qty": 1.0, "unit": "cup"}"#).unwrap();
+        assert_eq!(line.amount, Some(1.));
+        let report = evaluate(&[line], &ParserConfig::default());
+        assert_eq!(report.amount.true_positives, 1);
+        assert_eq!(report.name.predicted, 1);
+        assert_eq!(report.name.correct, 0);
+    }
+}
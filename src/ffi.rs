@@ -0,0 +1,70 @@
+//! C bindings for embedding the parser in other languages
+//!
+//! The header `include/ingreedy.h` is generated with `cbindgen --config cbindgen.toml --output include/ingreedy.h`.
+
+use crate::Ingredient;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+/// JSON of a parsed line, or `{"input": ..., "error": ...}` if it can't be parsed
+fn parse_json(input: &str) -> String {
+    match Ingredient::parse(input) {
+        Ok(ingredient) => serde_json::to_string(&ingredient),
+        Err(error) => serde_json::to_string(&serde_json::json!({
+            "input": input,
+            "error": error.to_string(),
+        })),
+    }
+    .unwrap_or_default()
+}
+
+/// Parse a NUL-terminated UTF-8 ingredient line into a JSON string.
+///
+/// Lines that can't be parsed give `{"input": ..., "error": ...}`.
+/// Returns NULL if `input` is NULL or not valid UTF-8.
+/// The result must be released with `ingreedy_free_string`.
+///
+/// # Safety
+/// `input` must be NULL or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ingreedy_parse_json(input: *const c_char) -> *mut c_char {
+    if input.is_null() {
+        return std::ptr::null_mut();
+    }
+    match CStr::from_ptr(input).to_str() {
+        Ok(input) => CString::new(parse_json(input))
+            .map(CString::into_raw)
+            .unwrap_or(std::ptr::null_mut()),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Free a string returned by `ingreedy_parse_json`. Passing NULL does nothing.
+///
+/// # Safety
+/// `string` must be NULL or a pointer returned by `ingreedy_parse_json` that wasn't freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn ingreedy_free_string(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_json() {
+        let input = CString::new("2 cups flour").unwrap();
+        unsafe {
+            let output = ingreedy_parse_json(input.as_ptr());
+            let json: serde_json::Value =
+                serde_json::from_str(CStr::from_ptr(output).to_str().unwrap()).unwrap();
+            assert_eq!(json["ingredient"], "flour");
+            ingreedy_free_string(output);
+            assert!(ingreedy_parse_json(std::ptr::null()).is_null());
+            ingreedy_free_string(std::ptr::null_mut());
+        }
+    }
+}
@@ -0,0 +1,360 @@
+//! Formatting of parsed ingredients back into human-readable text
+
+use crate::scale::{decimal, nice_fraction};
+use crate::{
+    Amount, Ingredient, ParsedLine, Quantity, Recipe, ShoppingItem, ShoppingList, Unit, Yield,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How amounts are written
+#[derive(Default, Debug, Eq, PartialEq, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum AmountStyle {
+    /// Whole numbers and ASCII fractions (e.g. "1 1/2")
+    #[default]
+    Fraction,
+    /// Whole numbers and Unicode vulgar fractions (e.g. "1½")
+    UnicodeFraction,
+    /// Decimals (e.g. "1.5")
+    Decimal,
+}
+
+/// How units are written
+#[derive(Default, Debug, Eq, PartialEq, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum UnitStyle {
+    /// Full names, pluralized with the amount (e.g. "2 tablespoons")
+    #[default]
+    Full,
+    /// Abbreviations (e.g. "2 tbsp")
+    Abbreviated,
+}
+
+/// Units system to convert quantities to before formatting
+#[derive(Default, Debug, Eq, PartialEq, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum UnitPreference {
+    /// Keep the units as parsed
+    #[default]
+    AsParsed,
+    /// Convert to grams, milliliters and kilojoules (see `Quantity::to_metric`)
+    Metric,
+    /// Convert to cups, spoons, ounces and pounds (see `Quantity::to_english`)
+    English,
+}
+
+/// Options for `Quantity::format` and `Ingredient::format`
+#[derive(Default, Debug, Eq, PartialEq, Serialize, Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct FormatOptions {
+    pub amount_style: AmountStyle,
+    pub unit_style: UnitStyle,
+    pub unit_preference: UnitPreference,
+}
+
+/// Unicode vulgar fractions for the ASCII fractions `nice_fraction` writes
+const UNICODE_FRACTIONS: &[(&str, &str)] = &[
+    ("1/2", "½"),
+    ("1/3", "⅓"),
+    ("2/3", "⅔"),
+    ("1/4", "¼"),
+    ("3/4", "¾"),
+    ("1/8", "⅛"),
+    ("3/8", "⅜"),
+    ("5/8", "⅝"),
+    ("7/8", "⅞"),
+];
+
+fn format_amount(amount: f64, style: AmountStyle) -> String {
+    match style {
+        AmountStyle::Fraction => nice_fraction(amount),
+        AmountStyle::Decimal => decimal(amount),
+        AmountStyle::UnicodeFraction => {
+            let fraction = nice_fraction(amount);
+            let (whole, last) = match fraction.rsplit_once(' ') {
+                Some((whole, last)) => (whole, last),
+                None => ("", fraction.as_str()),
+            };
+            match UNICODE_FRACTIONS.iter().find(|(ascii, _)| *ascii == last) {
+                Some((_, unicode)) => format!("{}{}", whole, unicode),
+                None => fraction,
+            }
+        }
+    }
+}
+
+/// Full unit name, pluralized for amounts other than one (e.g. "pinches")
+fn full_name(unit: &Unit, amount: f64) -> String {
+    match unit {
+        Unit::Fahrenheit => return "degrees Fahrenheit".into(),
+        Unit::Celsius => return "degrees Celsius".into(),
+        _ => {}
+    }
+    let name = unit.to_string();
+    if amount > 0. && amount <= 1. {
+        name
+    } else if ["ch", "sh", "s", "x"].iter().any(|end| name.ends_with(end)) {
+        format!("{}es", name)
+    } else {
+        format!("{}s", name)
+    }
+}
+
+impl Quantity {
+    /// The quantity converted to the preferred units, if it needs to be and can be
+    fn in_preferred_units(&self, options: &FormatOptions) -> Option<Self> {
+        match options.unit_preference {
+            UnitPreference::AsParsed => None,
+            UnitPreference::Metric => self.to_metric().ok(),
+            UnitPreference::English => self.to_english().ok(),
+        }
+    }
+
+    /// Write the quantity as text (e.g. "1 1/2 cups", or "about 1 1/2 cups" if approximate)
+    pub fn format(&self, options: &FormatOptions) -> String {
+        let converted = self.in_preferred_units(options);
+        let quantity = converted.as_ref().unwrap_or(self);
+        let mut amount = match quantity.amount {
+            Amount::Range { min, max, .. } => format!(
+                "{}-{}",
+                format_amount(min, options.amount_style),
+                format_amount(max, options.amount_style)
+            ),
+            Amount::Unspecified => String::new(),
+            amount => format_amount(amount.value(), options.amount_style),
+        };
+        if quantity.amount.is_approximate() {
+            amount.insert_str(0, "about ");
+        }
+        if let (Some(modifier), Some(_)) = (self.modifier, &quantity.unit) {
+            amount = format!("{} {}", amount, modifier.name());
+        }
+        match &quantity.unit {
+            None => amount,
+            Some(unit) => match options.unit_style {
+                UnitStyle::Full => {
+                    format!("{} {}", amount, full_name(unit, quantity.amount.value()))
+                }
+                UnitStyle::Abbreviated if quantity.is_temperature() => {
+                    format!("{}{}", amount, unit.abbreviation())
+                }
+                UnitStyle::Abbreviated => format!("{} {}", amount, unit.abbreviation()),
+            },
+        }
+    }
+}
+
+/// Write a size as it's written before a name (e.g. "9-inch" in "9-inch pie crust")
+fn format_size(size: &Quantity, options: &FormatOptions) -> String {
+    let converted = size.in_preferred_units(options);
+    let size = converted.as_ref().unwrap_or(size);
+    let amount = format_amount(size.amount.value(), options.amount_style);
+    match &size.unit {
+        None => amount,
+        Some(unit) => match options.unit_style {
+            UnitStyle::Full => format!("{}-{}", amount, unit),
+            UnitStyle::Abbreviated => format!("{} {}", amount, unit.abbreviation()),
+        },
+    }
+}
+
+impl Ingredient {
+    /// Write the ingredient as text (e.g. "2lb 4oz potatoes" as "2 pounds 4 ounces potatoes"),
+    /// with sizes written before the name ("9-inch"), durations after it ("for 10 minutes")
+    /// and temperatures in parentheses after those.
+    pub fn format(&self, options: &FormatOptions) -> String {
+        let durations = self
+            .quantities
+            .iter()
+            .filter(|quantity| quantity.is_duration())
+            .map(|quantity| format!("for {}", quantity.format(options)));
+        let temperatures = self
+            .quantities
+            .iter()
+            .filter(|quantity| quantity.is_temperature())
+            .map(|quantity| format!("({})", quantity.format(options)));
+        self.quantities
+            .iter()
+            .filter(|quantity| !quantity.is_temperature() && !quantity.is_duration())
+            .map(|quantity| {
+                if quantity.is_length() {
+                    format_size(quantity, options)
+                } else {
+                    quantity.format(options)
+                }
+            })
+            .filter(|quantity| !quantity.is_empty())
+            .chain(self.size.map(|size| size.name().to_owned()))
+            .chain(
+                self.ingredient.iter().map(|name| {
+                    format!("{}{}", name, self.footnote.as_deref().unwrap_or_default())
+                }),
+            )
+            .chain(durations)
+            .chain(self.note.as_ref().map(|note| format!("({})", note)))
+            .chain(temperatures)
+            .chain(self.usage_note.clone())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl Yield {
+    /// Write the yield as text (e.g. "Serves 4" or "Makes 12 muffins")
+    pub fn format(&self, options: &FormatOptions) -> String {
+        let amount = format_amount(self.amount, options.amount_style);
+        match &self.unit {
+            None => format!("Serves {}", amount),
+            Some(unit) => format!("Makes {} {}", amount, unit),
+        }
+    }
+}
+
+impl Recipe {
+    /// Write the recipe as text, one line per parsed line, with headers followed by a colon
+    pub fn format(&self, options: &FormatOptions) -> String {
+        self.lines
+            .iter()
+            .map(|line| match line {
+                ParsedLine::Ingredient(ingredient) => ingredient.format(options),
+                ParsedLine::Header(header) => format!("{}:", header),
+                ParsedLine::Unparsed(line) => line.clone(),
+                ParsedLine::Yield(recipe_yield) => recipe_yield.format(options),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl ShoppingItem {
+    /// Write the item as its name and quantities (e.g. "flour: 2 cups, 100 grams")
+    pub fn format(&self, options: &FormatOptions) -> String {
+        let quantities = self
+            .quantities
+            .iter()
+            .map(|quantity| quantity.format(options))
+            .filter(|quantity| !quantity.is_empty())
+            .collect::<Vec<_>>();
+        if quantities.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}: {}", self.name, quantities.join(", "))
+        }
+    }
+}
+
+impl ShoppingList {
+    /// Write the list as text, one line per item
+    pub fn format(&self, options: &FormatOptions) -> String {
+        self.items()
+            .iter()
+            .map(|item| item.format(options))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format(&FormatOptions::default()))
+    }
+}
+
+impl fmt::Display for Ingredient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format(&FormatOptions::default()))
+    }
+}
+
+impl fmt::Display for Recipe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format(&FormatOptions::default()))
+    }
+}
+
+impl fmt::Display for ShoppingList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format(&FormatOptions::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(input: &str, options: FormatOptions) -> String {
+        Ingredient::parse(input).unwrap().format(&options)
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for input in [
+            "1 1/2 cups flour",
+            "2 pounds 4 ounces potatoes",
+            "3 eggs",
+            "1 pinch salt",
+            "2 pinches salt",
+            "1/4 teaspoon pepper",
+            "2 fluid ounces milk",
+            "2-3 cups flour",
+            "salt to taste",
+            "1 cup warm water (110 degrees Fahrenheit)",
+            "1 9-inch pie crust",
+        ] {
+            let ingredient = Ingredient::parse(input).unwrap();
+            assert_eq!(ingredient.to_string(), input);
+            let reparsed = Ingredient::parse(&ingredient.to_string()).unwrap();
+            assert_eq!(reparsed.quantities[0].unit, ingredient.quantities[0].unit);
+        }
+    }
+
+    #[test]
+    fn test_format_options() {
+        let unicode = FormatOptions {
+            amount_style: AmountStyle::UnicodeFraction,
+            unit_style: UnitStyle::Abbreviated,
+            ..FormatOptions::default()
+        };
+        assert_eq!(format("1 1/2 tablespoons sugar", unicode), "1½ tbsp sugar");
+        let decimal = FormatOptions {
+            amount_style: AmountStyle::Decimal,
+            ..FormatOptions::default()
+        };
+        assert_eq!(format("1 1/2 cups flour", decimal), "1.5 cups flour");
+        let metric = FormatOptions {
+            unit_preference: UnitPreference::Metric,
+            amount_style: AmountStyle::Decimal,
+            unit_style: UnitStyle::Abbreviated,
+        };
+        assert_eq!(format("2 cups milk", metric), "473.18 ml milk");
+        assert_eq!(
+            format("1 cup warm water (110°F)", metric),
+            "236.59 ml warm water (43.33°C)"
+        );
+        let english = FormatOptions {
+            unit_preference: UnitPreference::English,
+            ..FormatOptions::default()
+        };
+        assert_eq!(format("500 g butter", english), "1.1 pounds butter");
+        assert_eq!(format("30 ml oil", english), "2.03 tablespoons oil");
+        assert_eq!(format("pinch salt", english), "1 pinch salt");
+    }
+
+    #[test]
+    fn test_format_shopping_list() {
+        let mut list = ShoppingList::new();
+        list.extend(
+            [
+                "2 cups flour",
+                "1 pinch salt",
+                "1 cup flour",
+                "100 g flour",
+                "salt to taste",
+            ]
+            .iter()
+            .map(|line| Ingredient::parse(line).unwrap()),
+        );
+        assert_eq!(list.to_string(), "flour: 3 cups, 100 grams\nsalt: 1 pinch");
+    }
+}
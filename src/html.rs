@@ -0,0 +1,162 @@
+//! Decoding entities and stripping markup from ingredient lines scraped from web pages
+
+use crate::{Ingredient, IngreedyError};
+
+/// Named character references found in recipe markup, and the text they stand for
+const NAMED_ENTITIES: &[(&str, &str)] = &[
+    ("amp", "&"),
+    ("lt", "<"),
+    ("gt", ">"),
+    ("quot", "\""),
+    ("apos", "'"),
+    ("nbsp", "\u{a0}"),
+    ("frac12", "½"),
+    ("frac13", "⅓"),
+    ("frac23", "⅔"),
+    ("frac14", "¼"),
+    ("frac34", "¾"),
+    ("frac15", "⅕"),
+    ("frac16", "⅙"),
+    ("frac18", "⅛"),
+    ("frac38", "⅜"),
+    ("frac58", "⅝"),
+    ("frac78", "⅞"),
+    ("frasl", "⁄"),
+    ("deg", "°"),
+    ("times", "×"),
+    ("ndash", "–"),
+    ("mdash", "—"),
+    ("hellip", "…"),
+    ("lsquo", "‘"),
+    ("rsquo", "’"),
+    ("ldquo", "“"),
+    ("rdquo", "”"),
+    ("eacute", "é"),
+    ("egrave", "è"),
+    ("ntilde", "ñ"),
+    ("uuml", "ü"),
+    ("micro", "µ"),
+];
+
+/// Tags that separate words when stripped ("salt<br>pepper")
+const BLOCK_TAGS: &[&str] = &["br", "p", "div", "li", "td", "tr", "hr"];
+
+/// Text of a character reference without its "&" and ";" (e.g. "frac12" or "#189"),
+/// or `None` if it isn't one
+fn decode_entity(entity: &str) -> Option<String> {
+    let code = match entity.strip_prefix('#') {
+        Some(hex) if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16).ok()?,
+        Some(decimal) => decimal.parse().ok()?,
+        None => {
+            return NAMED_ENTITIES
+                .iter()
+                .find(|(name, _)| *name == entity)
+                .map(|(_, text)| (*text).to_owned())
+        }
+    };
+    std::char::from_u32(code).map(String::from)
+}
+
+/// Decode character references, leaving anything that isn't one as it is
+fn decode_entities(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find('&') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest
+            .find(';')
+            .and_then(|end| Some((end, decode_entity(&rest[1..end])?)));
+        match decoded {
+            Some((end, text)) => {
+                output.push_str(&text);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                output.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Remove tags, replacing those of block elements with a space
+fn strip_tags(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find('<') {
+        output.push_str(&rest[..start]);
+        let end = match rest[start..].find('>') {
+            Some(end) => end,
+            None => {
+                rest = &rest[start..];
+                break;
+            }
+        };
+        let tag = &rest[start + 1..start + end];
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+        if BLOCK_TAGS
+            .iter()
+            .any(|block| block.eq_ignore_ascii_case(name))
+        {
+            output.push(' ');
+        }
+        rest = &rest[start + end + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Strip tags from an HTML fragment, decode its character references
+/// ("&frac12;", "&amp;", "&#189;") and collapse whitespace (including "&nbsp;")
+pub fn strip_html(input: &str) -> String {
+    decode_entities(&strip_tags(input))
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl Ingredient {
+    /// Parse an ingredient line scraped from a web page, first stripping its markup
+    /// and decoding its character references with `strip_html`
+    pub fn parse_html(input: &str) -> Result<Self, IngreedyError> {
+        Self::parse(&strip_html(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Unit;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_strip_html() {
+        assert_eq!(strip_html("1 &frac12; cups flour"), "1 ½ cups flour");
+        assert_eq!(strip_html("salt &amp; pepper"), "salt & pepper");
+        assert_eq!(strip_html("<b>2</b> cups <i>sugar</i>"), "2 cups sugar");
+        assert_eq!(strip_html("2&nbsp;tbsp oil"), "2 tbsp oil");
+        assert_eq!(strip_html("&#189; cup milk"), "½ cup milk");
+        assert_eq!(strip_html("&#xBD; cup milk"), "½ cup milk");
+        assert_eq!(strip_html("salt<br/>pepper"), "salt pepper");
+        assert_eq!(strip_html("&lt;b&gt; &unknown; R&D"), "<b> &unknown; R&D");
+        assert_eq!(strip_html("1 cup <unclosed"), "1 cup <unclosed");
+    }
+
+    #[test]
+    fn test_parse_html() {
+        let ingredient = Ingredient::parse_html(
+            "<li><span class=\"amount\">1&frac12;</span>&nbsp;cups <a href=\"/flour\">flour</a></li>",
+        )
+        .unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.5);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
+        assert_eq!(ingredient.ingredient, Some("flour".to_string()));
+    }
+}
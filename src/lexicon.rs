@@ -0,0 +1,61 @@
+//! Matching ingredient names against lexicons of words and phrases
+
+use crate::ingredient_key;
+use std::collections::HashMap;
+
+/// Words and phrases mapped to tags.
+///
+/// Terms are matched as whole words by their `ingredient_key`, so "Eggs" matches "egg",
+/// and the longest term wins where several start at the same word
+/// (so "peanut butter" can have different tags from "butter").
+#[derive(Debug, Clone)]
+pub(crate) struct PhraseLexicon<T> {
+    terms: HashMap<String, Vec<T>>,
+    /// Number of words in the longest term
+    longest: usize,
+}
+
+impl<T> Default for PhraseLexicon<T> {
+    fn default() -> Self {
+        Self {
+            terms: HashMap::new(),
+            longest: 0,
+        }
+    }
+}
+
+impl<T: Copy + Ord> PhraseLexicon<T> {
+    /// Set the tags of a word or phrase, replacing any it had
+    pub(crate) fn insert(&mut self, term: &str, tags: &[T]) {
+        let key = ingredient_key(term);
+        self.longest = self.longest.max(key.split(' ').count());
+        self.terms.insert(key, tags.to_vec());
+    }
+
+    /// Tags of the terms in a name, sorted and without duplicates
+    pub(crate) fn tags(&self, name: &str) -> Vec<T> {
+        let key = ingredient_key(name);
+        let words: Vec<_> = key.split_whitespace().collect();
+        let mut tags = Vec::new();
+        let mut start = 0;
+        while start < words.len() {
+            let longest_match =
+                (1..=self.longest.min(words.len() - start))
+                    .rev()
+                    .find_map(|length| {
+                        let term = words[start..start + length].join(" ");
+                        self.terms.get(&term).map(|found| (length, found))
+                    });
+            match longest_match {
+                Some((length, found)) => {
+                    tags.extend(found);
+                    start += length;
+                }
+                None => start += 1,
+            }
+        }
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+}
@@ -3,11 +3,103 @@
 #[macro_use]
 extern crate pest_derive;
 
-use lazy_static::lazy_static;
+#[cfg(feature = "allergens")]
+mod allergen;
+mod amount;
+#[cfg(feature = "arena")]
+mod arena;
+mod ast;
+mod batch;
+mod borrowed;
+mod cache;
+mod capabilities;
+mod classify;
+mod config;
+mod convert;
+#[cfg(feature = "dietary")]
+mod dietary;
+mod duration;
+mod error;
+mod eval;
+#[cfg(feature = "ffi")]
+mod ffi;
+mod format;
+#[cfg(feature = "html")]
+mod html;
+#[cfg(any(feature = "allergens", feature = "dietary"))]
+mod lexicon;
+mod locale;
+mod name;
+mod normalize;
+mod nyt;
+mod ocr;
+mod options;
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "rational")]
+mod rational;
+mod recipe;
+mod resolver;
+mod scale;
+#[cfg(feature = "schema")]
+mod schema;
+mod shopping;
+mod similarity;
+mod span;
+mod split;
+mod temperature;
+mod unit;
+mod warning;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod weight;
+
+#[cfg(feature = "allergens")]
+pub use allergen::{Allergen, AllergenLexicon};
+pub use amount::Amount;
+#[cfg(feature = "arena")]
+pub use arena::ArenaIngredient;
+pub use ast::{Ast, AstNode, NodeKind};
+#[cfg(feature = "rayon")]
+pub use batch::parse_lines_par;
+pub use batch::{parse_lines, parse_lines_iter, IngredientStream};
+pub use borrowed::{IngredientRef, QuantityRef};
+pub use cache::{CacheStats, CachedParser};
+pub use capabilities::{
+    capabilities, Capabilities, UnitCapability, GRAMMAR_VERSION, SCHEMA_VERSION,
+};
+pub use classify::{classify_line, classify_line_with_confidence, LineClassification, LineKind};
+pub use config::{ParserConfig, ParserContext, UnitRegistry};
+pub use convert::MeasurementSystem;
+#[cfg(feature = "dietary")]
+pub use dietary::{DietaryFlag, DietaryLexicon, FoodCategory};
+pub use error::{Expected, ParseError};
+pub use eval::{evaluate, EvalField, EvalReport, FieldScore, LabeledLine, Mismatch};
+pub use format::{AmountStyle, FormatOptions, UnitPreference, UnitStyle};
+#[cfg(feature = "html")]
+pub use html::strip_html;
+pub use locale::Locale;
+pub use name::{clean_ingredient_name, ingredient_key, NameNormalizer};
+pub use normalize::{DecimalSeparator, Normalization};
+pub use nyt::NytRow;
+pub use ocr::{correct_ocr, OcrCorrection};
+pub use options::ParseOptions;
+pub use recipe::{ParsedLine, Recipe, Yield};
+pub use resolver::{IngredientCatalog, IngredientId, IngredientResolver};
+pub use scale::nice_fraction;
+#[cfg(feature = "schema")]
+pub use schema::{json_schema, recipe_json_schema};
+pub use shopping::{ShoppingItem, ShoppingList};
+pub use span::{find_quantities, QuantitySpan, Span, SpannedIngredient};
+pub use temperature::{Temperature, TemperatureScale};
+pub use unit::{is_unit_token, Unit, UnitCategory};
+pub use warning::{Warning, WarningCategory};
+pub use weight::{DensityProvider, DensityTable, MissingData, PieceWeights};
+
 use pest::iterators::{Pair, Pairs};
 use pest::Parser;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::borrow::Cow;
 use std::num::ParseFloatError;
 use thiserror::Error;
 
@@ -26,12 +118,52 @@ pub enum IngreedyError {
     /// Thrown if a given string could not be parsed as float
     #[error("Couldn't parse float")]
     ParseFloatError(#[from] ParseFloatError),
-    /// Thrown if Pest fails to parse
-    #[error("Pest failed to parse")]
-    PestParseError(#[from] pest::error::Error<Rule>),
+    /// Thrown if the grammar fails to parse the input
+    #[error(transparent)]
+    ParseError(#[from] ParseError),
     /// Thrown if no inner rule found
     #[error("No inner rule found")]
     InnerRuleNoneError,
+    /// Thrown if a string is not a known unit
+    #[error("Unknown unit '{0}'")]
+    UnknownUnit(String),
+    /// Thrown if a quantity without a unit is converted
+    #[error("Quantity has no unit")]
+    MissingUnit,
+    /// Thrown if a unit has no known conversion factor
+    #[error("No conversion factor for unit '{0}'")]
+    UnconvertibleUnit(String),
+    /// Thrown if converting between units of different categories (e.g. mass and volume)
+    #[error("Can't convert {from} to {to}")]
+    IncompatibleUnits {
+        /// The unit converted from
+        from: String,
+        /// The unit converted to
+        to: String,
+    },
+    /// Thrown if resolving a quantity to grams needs reference data that isn't available
+    #[error("No {data} known for '{ingredient}'")]
+    NeedsData {
+        /// The kind of reference data missing
+        data: MissingData,
+        /// The ingredient it is missing for
+        ingredient: String,
+    },
+    /// Thrown in strict mode if a line lacks a quantity or an ingredient name
+    #[error("Line is missing {}", .0.description())]
+    Incomplete(Expected),
+    /// Thrown if scaling a recipe to a number of servings that doesn't say how much it makes
+    #[error("Recipe has no yield")]
+    MissingYield,
+    /// Thrown if reading ingredient lines from a reader fails
+    #[error("Couldn't read input: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl From<pest::error::Error<Rule>> for IngreedyError {
+    fn from(error: pest::error::Error<Rule>) -> Self {
+        Self::ParseError(error.into())
+    }
 }
 
 impl IngreedyError {
@@ -44,192 +176,804 @@ impl IngreedyError {
     }
 }
 
-lazy_static! {
-    static ref NUMBER_VALUE: HashMap<&'static str, f64> = {
-        let mut map = HashMap::new();
-        map.insert("a", 1.);
-        map.insert("an", 1.);
-        map.insert("zero", 0.);
-        map.insert("one", 1.);
-        map.insert("two", 2.);
-        map.insert("three", 3.);
-        map.insert("four", 4.);
-        map.insert("five", 5.);
-        map.insert("six", 6.);
-        map.insert("seven", 7.);
-        map.insert("eight", 8.);
-        map.insert("nine", 9.);
-        map.insert("ten", 10.);
-        map.insert("eleven", 11.);
-        map.insert("twelve", 12.);
-        map.insert("thirteen", 13.);
-        map.insert("fourteen", 14.);
-        map.insert("fifteen", 15.);
-        map.insert("sixteen", 16.);
-        map.insert("seventeen", 17.);
-        map.insert("eighteen", 18.);
-        map.insert("nineteen", 19.);
-        map.insert("twenty", 20.);
-        map.insert("thirty", 30.);
-        map.insert("forty", 40.);
-        map.insert("fifty", 50.);
-        map.insert("sixty", 60.);
-        map.insert("seventy", 70.);
-        map.insert("eighty", 80.);
-        map.insert("ninety", 90.);
-        map
-    };
-    static ref UNICODE_FRACTION_VALUE: HashMap<&'static str, f64> = {
-        let mut map = HashMap::new();
-        map.insert("¼", 1.0 / 4.);
-        map.insert("½", 1.0 / 2.);
-        map.insert("¾", 3.0 / 4.);
-        map.insert("⅐", 1.0 / 7.);
-        map.insert("⅑", 1.0 / 9.);
-        map.insert("⅒", 1.0 / 10.);
-        map.insert("⅓", 1.0 / 3.);
-        map.insert("⅔", 2.0 / 3.);
-        map.insert("⅕", 1.0 / 5.);
-        map.insert("⅖", 2.0 / 5.);
-        map.insert("⅗", 3.0 / 5.);
-        map.insert("⅘", 4.0 / 5.);
-        map.insert("⅙", 1.0 / 6.);
-        map.insert("⅚", 5.0 / 6.);
-        map.insert("⅛", 1.0 / 8.);
-        map.insert("⅜", 3.0 / 8.);
-        map.insert("⅝", 5.0 / 8.);
-        map.insert("⅞", 7.0 / 8.);
-        map
+/// A public table of values along with the function looking them up with a `match`,
+/// from a single list of entries
+macro_rules! value_table {
+    (
+        $(#[$doc:meta])*
+        $table:ident, $lookup:ident, $rule:literal, { $($key:literal => $value:expr,)* }
+    ) => {
+        $(#[$doc])*
+        pub const $table: &[(&str, f64)] = &[$(($key, $value)),*];
+
+        /// Value of an entry of the table
+        pub(crate) fn $lookup(key: &str) -> Result<f64, IngreedyError> {
+            match key {
+                $($key => Ok($value),)*
+                _ => Err(IngreedyError::WrongRule {
+                    found: key.into(),
+                    rule: $rule.into(),
+                }),
+            }
+        }
     };
 }
-#[derive(Parser)]
-#[grammar = "grammar.pest"] // relative to src
-pub struct IngredientParser;
+
+value_table! {
+    /// Values of the number words of every locale (e.g. 3 for "three", "tres" and "三"),
+    /// as the parser reads them in spelled-out amounts
+    NUMBER_WORDS, number_word_value, "written_number", {
+        "a" => 1.,
+        "an" => 1.,
+        "zero" => 0.,
+        "one" => 1.,
+        "two" => 2.,
+        "three" => 3.,
+        "four" => 4.,
+        "five" => 5.,
+        "six" => 6.,
+        "seven" => 7.,
+        "eight" => 8.,
+        "nine" => 9.,
+        "ten" => 10.,
+        "eleven" => 11.,
+        "twelve" => 12.,
+        "thirteen" => 13.,
+        "fourteen" => 14.,
+        "fifteen" => 15.,
+        "sixteen" => 16.,
+        "seventeen" => 17.,
+        "eighteen" => 18.,
+        "nineteen" => 19.,
+        "twenty" => 20.,
+        "thirty" => 30.,
+        "forty" => 40.,
+        "fifty" => 50.,
+        "sixty" => 60.,
+        "seventy" => 70.,
+        "eighty" => 80.,
+        "ninety" => 90.,
+        // Spanish
+        "una" => 1.,
+        "uno" => 1.,
+        "un" => 1.,
+        "dos" => 2.,
+        "tres" => 3.,
+        "cuatro" => 4.,
+        "cinco" => 5.,
+        "seis" => 6.,
+        "siete" => 7.,
+        "ocho" => 8.,
+        "nueve" => 9.,
+        "diez" => 10.,
+        "once" => 11.,
+        "doce" => 12.,
+        "quince" => 15.,
+        "veinte" => 20.,
+        "treinta" => 30.,
+        "cuarenta" => 40.,
+        "cincuenta" => 50.,
+        "cien" => 100.,
+        "media" => 0.5,
+        "medio" => 0.5,
+        // French
+        "une" => 1.,
+        "deux" => 2.,
+        "trois" => 3.,
+        "quatre" => 4.,
+        "cinq" => 5.,
+        "sept" => 7.,
+        "huit" => 8.,
+        "neuf" => 9.,
+        "dix" => 10.,
+        "onze" => 11.,
+        "douze" => 12.,
+        "quinze" => 15.,
+        "vingt" => 20.,
+        "trente" => 30.,
+        "quarante" => 40.,
+        "cinquante" => 50.,
+        "cent" => 100.,
+        "demie" => 0.5,
+        "demi" => 0.5,
+        // Chinese and Japanese
+        "半" => 0.5,
+        "一" => 1.,
+        "二" => 2.,
+        "两" => 2.,
+        "兩" => 2.,
+        "三" => 3.,
+        "四" => 4.,
+        "五" => 5.,
+        "六" => 6.,
+        "七" => 7.,
+        "八" => 8.,
+        "九" => 9.,
+        "十" => 10.,
+    }
+}
+
+value_table! {
+    /// Values of the Unicode vulgar fraction characters (e.g. 0.5 for "½")
+    UNICODE_FRACTIONS, unicode_fraction_value, "unicode_fraction", {
+        "¼" => 1.0 / 4.,
+        "½" => 1.0 / 2.,
+        "¾" => 3.0 / 4.,
+        "⅐" => 1.0 / 7.,
+        "⅑" => 1.0 / 9.,
+        "⅒" => 1.0 / 10.,
+        "⅓" => 1.0 / 3.,
+        "⅔" => 2.0 / 3.,
+        "⅕" => 1.0 / 5.,
+        "⅖" => 2.0 / 5.,
+        "⅗" => 3.0 / 5.,
+        "⅘" => 4.0 / 5.,
+        "⅙" => 1.0 / 6.,
+        "⅚" => 5.0 / 6.,
+        "⅛" => 1.0 / 8.,
+        "⅜" => 3.0 / 8.,
+        "⅝" => 5.0 / 8.,
+        "⅞" => 7.0 / 8.,
+    }
+}
+
+mod parser {
+    /// The pest parser of the grammars, with their `Rule`s
+    #[derive(Parser)]
+    #[grammar = "grammar.pest"] // relative to src
+    #[grammar = "grammar_es.pest"]
+    #[grammar = "grammar_fr.pest"]
+    #[grammar = "grammar_zh.pest"]
+    #[grammar = "grammar_ja.pest"]
+    pub struct IngredientParser;
+}
+
+/// Rules of the pest grammars, only public for the deprecated `IngredientParser`
+/// and `Ingredient::parse_pairs`
+#[doc(hidden)]
+pub use parser::Rule;
+
+/// The pest parser of the grammars, with their `Rule`s
+#[deprecated(
+    since = "0.3.0",
+    note = "pest's rules change with the grammar; use `Ast::parse` for the parse tree"
+)]
+pub type IngredientParser = parser::IngredientParser;
+
+/// Rule of a pair, with locale-specific rules mapped onto the English ones
+fn rule_of(pair: &Pair<Rule>) -> Rule {
+    locale::canonical_rule(pair.as_rule())
+}
 
 /// Ingredient information
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[derive(Default, Debug, Serialize, Deserialize, Clone)]
 pub struct Ingredient {
-    /// quantities for ingredient
+    /// quantities for ingredient, in the order they appear in the input.
+    ///
+    /// Each part of a multipart quantity ("2lb 4oz", "1 cup plus 2 tablespoons") is kept in source order, and a
+    /// leading bare amount is multiplied into the quantity after it ("2 1 cup" is 2 cups).
+    /// Alternatives after a "/" or "or" ("1kg / 2lb") and parenthesized conversions
+    /// ("1 cup (240 ml)") are not included, so the first quantity is always the primary one.
     pub quantities: Vec<Quantity>,
-    /// ingredient name
+    /// equivalent measurements given after a "/" or "or" (e.g. the "2lb 4oz" of "1kg / 2lb 4oz"),
+    /// in source order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternatives: Vec<Quantity>,
+    /// ingredient name, without a leading size
+    /// ("juice of 2 lemons" is named "lemon juice", with 2 as its quantity)
     pub ingredient: Option<String>,
+    /// size of the pieces, taken from the start of the name (e.g. "2 large eggs")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<Size>,
+    /// footnote marker taken out of the name (e.g. "*" in "1 cup flour*")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub footnote: Option<String>,
+    /// how the ingredient is prepared, split off the name after its first comma
+    /// with `ParseOptions::split_preparation` (e.g. "finely chopped" in "onion, finely chopped")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preparation: Option<String>,
+    /// purpose phrase taken from the end of the name (e.g. "for garnish" in "parsley, for garnish")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_note: Option<String>,
+    /// parenthesized remarks taken out of the name with `ParseOptions::split_note`
+    /// (e.g. "recommended: Goya" in "Sazon seasoning (recommended: Goya)")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// identifier of the ingredient in a database, set by an `IngredientResolver`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<IngredientId>,
+    /// non-fatal problems found while parsing, such as implausible amounts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+    /// the line as it was given to the parser
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub raw: String,
 }
 
-/// System of unit used for a quantity
-#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone, Copy)]
+/// System of unit used for a quantity, serialized in lowercase (e.g. "metric")
+#[derive(Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Clone)]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
 pub enum UnitType {
     English,
     Metric,
     Imprecise,
+    /// Containers whose contents vary (e.g. "can", "jar")
+    Container,
+    /// Degrees Fahrenheit or Celsius (e.g. "350°F")
+    Temperature,
+    /// Hours, minutes or seconds (e.g. "rested 10 minutes")
+    Time,
+    /// British imperial volumes (e.g. a 568 ml pint), with `MeasurementSystem::Imperial`
+    Imperial,
+    /// Traditional Chinese and Japanese measures (e.g. "斤", "合"), with `Locale::Zh` or `Locale::Ja`
+    Traditional,
+    /// Words counting pieces of an ingredient (e.g. "clove", "sprig"),
+    /// read as units with `ParseOptions::count_units`
+    Count,
+    /// Unit type unknown to this version, kept as serialized
+    Other(String),
 }
 
-impl UnitType {
-    fn parse(pair: &Pair<Rule>) -> Result<Self, IngreedyError> {
-        match pair.as_rule() {
-            Rule::imprecise_unit => Ok(Self::Imprecise),
-            Rule::metric_unit => Ok(Self::Metric),
-            Rule::english_unit => Ok(Self::English),
-            _ => Err(IngreedyError::wrong_rule(pair, "unit_type")),
+/// Reads names in any case, so output of versions that capitalized them ("English") still parses
+impl From<String> for UnitType {
+    fn from(unit_type: String) -> Self {
+        match unit_type.to_ascii_lowercase().as_str() {
+            "english" => Self::English,
+            "metric" => Self::Metric,
+            "imprecise" => Self::Imprecise,
+            "container" => Self::Container,
+            "temperature" => Self::Temperature,
+            "time" => Self::Time,
+            "imperial" => Self::Imperial,
+            "traditional" => Self::Traditional,
+            "count" => Self::Count,
+            _ => Self::Other(unit_type),
+        }
+    }
+}
+
+impl From<UnitType> for String {
+    fn from(unit_type: UnitType) -> Self {
+        match unit_type {
+            UnitType::English => "english".into(),
+            UnitType::Metric => "metric".into(),
+            UnitType::Imprecise => "imprecise".into(),
+            UnitType::Container => "container".into(),
+            UnitType::Temperature => "temperature".into(),
+            UnitType::Time => "time".into(),
+            UnitType::Imperial => "imperial".into(),
+            UnitType::Traditional => "traditional".into(),
+            UnitType::Count => "count".into(),
+            UnitType::Other(unit_type) => unit_type,
+        }
+    }
+}
+
+/// Grammar path a quantity was parsed from
+#[non_exhaustive]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Default, Debug, Eq, PartialEq, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum QuantitySource {
+    /// A bare amount without a unit (e.g. "3 eggs")
+    #[default]
+    Amount,
+    /// An amount with a unit (e.g. "2 cups")
+    AttachedUnit,
+    /// An amount multiplying a parenthesized quantity (e.g. "2 (28 ounce) cans"),
+    /// or one after an "x" (e.g. "2 x 400g tins")
+    Multiplier,
+    /// An amount with a unit followed by a parenthesized conversion (e.g. "1 cup (240 ml)")
+    Conversion,
+    /// An imprecise unit without an amount (e.g. "pinch")
+    Imprecise,
+    /// A bare amount followed by a unit from a `UnitRegistry` (e.g. "2 knobs")
+    RegisteredUnit,
+    /// A container unit followed by its parenthesized size (e.g. "1 can (15 oz)")
+    Container,
+}
+
+/// How a measure was filled, which changes the amount it holds
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum QuantityModifier {
+    /// Filled above the rim (e.g. "1 heaping tablespoon")
+    Heaping,
+    /// Slightly under-filled (e.g. "1 scant cup")
+    Scant,
+    /// Filled and scraped flat (e.g. "1 level teaspoon")
+    Level,
+    /// Pressed down into the measure (e.g. "1 cup packed brown sugar")
+    Packed,
+    /// Filled into a dome (e.g. "1 rounded tablespoon")
+    Rounded,
+}
+
+impl QuantityModifier {
+    fn from_rule(rule: Rule) -> Option<Self> {
+        let modifier = match rule {
+            Rule::heaping => Self::Heaping,
+            Rule::scant => Self::Scant,
+            Rule::level => Self::Level,
+            Rule::packed => Self::Packed,
+            Rule::rounded => Self::Rounded,
+            _ => return None,
+        };
+        Some(modifier)
+    }
+
+    /// Lowercase name, matching the serialized form (e.g. "heaping")
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Heaping => "heaping",
+            Self::Scant => "scant",
+            Self::Level => "level",
+            Self::Packed => "packed",
+            Self::Rounded => "rounded",
+        }
+    }
+}
+
+/// Size of the pieces of a counted ingredient (e.g. "large" in "2 large eggs")
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Size {
+    Small,
+    Medium,
+    Large,
+    ExtraLarge,
+}
+
+impl Size {
+    fn from_rule(rule: Rule) -> Option<Self> {
+        let size = match rule {
+            Rule::small => Self::Small,
+            Rule::medium => Self::Medium,
+            Rule::large => Self::Large,
+            Rule::extra_large => Self::ExtraLarge,
+            _ => return None,
+        };
+        Some(size)
+    }
+
+    /// Lowercase name, as accepted by `PieceWeights::get_sized` (e.g. "extra large")
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Small => "small",
+            Self::Medium => "medium",
+            Self::Large => "large",
+            Self::ExtraLarge => "extra large",
         }
     }
 }
 
 /// Quantity information
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[derive(Default, Debug, Serialize, Deserialize, Clone)]
 pub struct Quantity {
-    pub amount: f64,
-    pub unit: Option<String>,
+    /// exact amount, range, approximate amount, or none (e.g. "to taste")
+    #[serde(default)]
+    pub amount: Amount,
+    pub unit: Option<Unit>,
     pub unit_type: Option<UnitType>,
+    /// how the quantity was parsed
+    #[serde(default)]
+    pub source: QuantitySource,
+    /// how the measure was filled (e.g. "heaping" in "1 heaping tablespoon")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modifier: Option<QuantityModifier>,
+    /// size of each container (e.g. the "15 oz" of "1 can (15 oz)")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container_size: Option<Box<Quantity>>,
+    /// text the quantity was parsed from (e.g. "1-½ oz." or "about 2 cups"),
+    /// after Unicode and number normalization
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub raw: String,
+    /// Unit name as a string, as `unit` was before the `Unit` enum (e.g. "fluid_ounce")
+    #[cfg(feature = "unit-string")]
+    #[serde(skip)]
+    pub unit_string: Option<String>,
+    /// `amount` as an exact fraction, when it was written as one (e.g. 1/3 for "1/3 cup")
+    /// and every operation on it since has kept it exact
+    #[cfg(feature = "rational")]
+    #[serde(skip)]
+    pub exact_amount: Option<num_rational::Rational64>,
 }
 
+/// Slashes between the numerator and denominator of a fraction: ASCII, fraction slash "⁄"
+/// and division slash "∕"
+pub(crate) const FRACTION_SLASHES: [char; 3] = ['/', '⁄', '∕'];
+
 fn parse_multicharacter_fraction(fraction: &str) -> Result<f64, IngreedyError> {
     let numbers = fraction
-        .split('/')
+        .split(FRACTION_SLASHES)
         .map(str::parse::<f64>)
         .collect::<Result<Vec<_>, _>>()?;
-    Ok(numbers[0] / numbers[1])
+    match numbers[..] {
+        [numerator, denominator] => Ok(numerator / denominator),
+        _ => Err(IngreedyError::WrongRule {
+            found: fraction.into(),
+            rule: "multicharacter_fraction".into(),
+        }),
+    }
+}
+
+/// Digit value of a superscript or subscript digit (e.g. "¹" or "₂")
+fn script_digit(c: char) -> Option<char> {
+    let digit = match c {
+        '⁰' | '₀' => 0,
+        '¹' | '₁' => 1,
+        '²' | '₂' => 2,
+        '³' | '₃' => 3,
+        '⁴'..='⁹' => c as u32 - '⁴' as u32 + 4,
+        '₄'..='₉' => c as u32 - '₄' as u32 + 4,
+        _ => return None,
+    };
+    std::char::from_digit(digit, 10)
+}
+
+/// Parse a fraction written with a superscript numerator and subscript denominator (e.g. "¹⁄₂")
+fn parse_superscript_fraction(fraction: &str) -> Result<f64, IngreedyError> {
+    let digits = fraction
+        .chars()
+        .map(|c| script_digit(c).unwrap_or('/'))
+        .collect::<String>();
+    parse_multicharacter_fraction(&digits)
 }
 
 fn parse_fraction(pair: &Pair<Rule>) -> Result<f64, IngreedyError> {
     match pair.as_rule() {
         Rule::multicharacter_fraction => Ok(parse_multicharacter_fraction(pair.as_str())?),
-        Rule::unicode_fraction => Ok(UNICODE_FRACTION_VALUE[pair.as_str()]),
+        Rule::superscript_fraction => Ok(parse_superscript_fraction(pair.as_str())?),
+        Rule::unicode_fraction => unicode_fraction_value(pair.as_str()),
         _ => Err(IngreedyError::wrong_rule(pair, "fraction")),
     }
 }
 
+/// Value of a spelled-out number, adding up its words
+/// (e.g. 125 for "one hundred and twenty-five")
+pub(crate) fn number_value(number: &str) -> Result<f64, IngreedyError> {
+    number
+        .split([' ', '\t', ',', '-'])
+        .filter(|word| !word.is_empty() && *word != "and")
+        .try_fold(0., |total: f64, word| match word {
+            "hundred" => Ok(total.max(1.) * 100.),
+            word => Ok(total + number_word_value(word)?),
+        })
+}
+
+/// Numerator and denominator of a spelled-out fraction (e.g. 3 and 4 for "three quarters")
+pub(crate) fn word_fraction(pair: Pair<Rule>) -> Result<(i64, i64), IngreedyError> {
+    let mut numerator = 1;
+    for part in pair.into_inner() {
+        match part.as_rule() {
+            Rule::fraction_numerator => numerator = number_word_value(part.as_str())? as i64,
+            Rule::fraction_word => {
+                let word = get_next_inner_pair(part)?;
+                let denominator = match word.as_rule() {
+                    Rule::half => 2,
+                    Rule::third => 3,
+                    Rule::quarter => 4,
+                    Rule::eighth => 8,
+                    _ => return Err(IngreedyError::wrong_rule(&word, "fraction_word")),
+                };
+                return Ok((numerator, denominator));
+            }
+            _ => {}
+        }
+    }
+    Err(IngreedyError::InnerRuleNoneError)
+}
+
 fn parse_amount(pair: Pair<Rule>) -> Result<f64, IngreedyError> {
-    match pair.as_rule() {
+    match rule_of(&pair) {
         Rule::float | Rule::integer => Ok(pair.as_str().parse()?),
         Rule::fraction => Ok(parse_fraction(&get_next_inner_pair(pair)?)?),
-        Rule::mixed_number => Ok(pair
-            .into_inner()
-            .filter_map(|x| match x.as_rule() {
-                Rule::integer => x.as_str().parse::<f64>().ok(),
-                Rule::fraction => {
-                    if let Ok(x) = get_next_inner_pair(x) {
-                        parse_fraction(&x).ok()
-                    } else {
-                        None
-                    }
-                }
-                Rule::separator => None,
-                _ => panic!("wrong rule for mixed_number {:?}", x),
-            })
-            .sum()),
-        Rule::number => Ok(NUMBER_VALUE[get_next_inner_pair(pair)?.as_str().trim()]),
+        Rule::mixed_number => pair.into_inner().try_fold(0., |total, part| {
+            Ok(total
+                + match part.as_rule() {
+                    Rule::integer => part.as_str().parse::<f64>()?,
+                    Rule::fraction => parse_fraction(&get_next_inner_pair(part)?)?,
+                    Rule::separator => 0.,
+                    _ => return Err(IngreedyError::wrong_rule(&part, "mixed_number")),
+                })
+        }),
+        Rule::number => number_value(get_next_inner_pair(pair)?.as_str()),
+        Rule::compound_number => number_value(pair.as_str()),
+        Rule::dozens => match pair.into_inner().next() {
+            Some(count) => Ok(parse_amount(get_next_inner_pair(count)?)? * 12.),
+            None => Ok(12.),
+        },
+        Rule::word_fraction => {
+            let (numerator, denominator) = word_fraction(pair)?;
+            Ok(numerator as f64 / denominator as f64)
+        }
         _ => Err(IngreedyError::wrong_rule(&pair, "amount")),
     }
 }
 
+/// Parse a `unit` (or `imprecise_unit`) rule into its leaf unit
+fn parse_unit(pair: Pair<Rule>) -> Result<Unit, IngreedyError> {
+    let unit_system = get_next_inner_pair(pair)?;
+    let unit = get_next_inner_pair(unit_system)?;
+    Unit::from_rule(rule_of(&unit)).ok_or_else(|| IngreedyError::wrong_rule(&unit, "unit"))
+}
+
 impl Quantity {
+    /// Set the unit along with the unit type it implies
+    pub(crate) fn set_unit(&mut self, unit: Unit) {
+        self.unit_type = Some(unit.unit_type());
+        #[cfg(feature = "unit-string")]
+        {
+            self.unit_string = Some(unit.name().to_owned());
+        }
+        self.unit = Some(unit);
+    }
+
+    /// Whether the quantity is a length, giving the size of the pieces (e.g. "9-inch")
+    pub fn is_length(&self) -> bool {
+        self.unit
+            .as_ref()
+            .is_some_and(|unit| unit.category() == UnitCategory::Length)
+    }
+
+    /// Whether the quantity measures the ingredient, rather than being a temperature,
+    /// duration or size that shouldn't be scaled or added up
+    pub fn measures_ingredient(&self) -> bool {
+        !self.is_temperature() && !self.is_duration() && !self.is_length()
+    }
+
+    /// Set the amount from an `amount` or `amount_range` rule
+    fn set_amount(&mut self, pair: Pair<Rule>) -> Result<(), IngreedyError> {
+        #[cfg(feature = "rational")]
+        {
+            self.exact_amount = match rule_of(&pair) {
+                Rule::amount => rational::exact_amount(&get_next_inner_pair(pair.clone())?),
+                _ => None,
+            };
+        }
+        self.amount = Amount::from_pair(pair)?;
+        Ok(())
+    }
+
+    /// Multiply the amount by that of another quantity (e.g. the "2" of "2 (28 ounce)")
+    fn multiply_by(&mut self, other: &Self) {
+        self.amount = self.amount * other.amount;
+        #[cfg(feature = "rational")]
+        {
+            self.exact_amount = rational::mul(self.exact_amount, other.exact_amount);
+        }
+    }
+
     fn parse(pair: Pair<Rule>) -> Result<Self, IngreedyError> {
         let mut quantity = Self::default();
-        match pair.as_rule() {
-            Rule::amount_with_conversion | Rule::amount_with_attached_units => {
+        let raw = pair.as_str().trim_end();
+        match rule_of(&pair) {
+            Rule::amount_with_attached_units => {
+                quantity.source = QuantitySource::AttachedUnit;
                 for pair in pair.into_inner() {
-                    match pair.as_rule() {
-                        Rule::amount => {
-                            quantity.amount = parse_amount(get_next_inner_pair(pair)?)?;
+                    match rule_of(&pair) {
+                        Rule::parenthesized_quantity => {
+                            let is_container = quantity.unit_type == Some(UnitType::Container);
+                            if is_container {
+                                let size = pair
+                                    .into_inner()
+                                    .nth(1)
+                                    .ok_or(IngreedyError::InnerRuleNoneError)?;
+                                quantity.container_size = Some(Box::new(Self::parse(size)?));
+                                quantity.source = QuantitySource::Container;
+                            } else {
+                                quantity.source = QuantitySource::Conversion;
+                            }
                         }
+                        Rule::amount | Rule::amount_range => quantity.set_amount(pair)?,
                         Rule::unit => {
-                            let unit = get_next_inner_pair(pair)?;
-                            quantity.unit_type = Some(UnitType::parse(&unit)?);
-                            quantity.unit =
-                                Some(format!("{:?}", get_next_inner_pair(unit)?.as_rule()));
+                            quantity.set_unit(parse_unit(pair)?);
+                        }
+                        Rule::quantity_modifier => {
+                            let modifier = get_next_inner_pair(pair)?;
+                            quantity.modifier =
+                                Some(QuantityModifier::from_rule(rule_of(&modifier)).ok_or_else(
+                                    || IngreedyError::wrong_rule(&modifier, "quantity_modifier"),
+                                )?);
                         }
                         _ => {}
                     }
                 }
             }
             Rule::amount_with_multiplier => {
-                let mut multiplier = 1.;
+                let mut multiplier = Self::default();
                 for pair in pair.into_inner() {
-                    match pair.as_rule() {
-                        Rule::amount => {
-                            multiplier = parse_amount(get_next_inner_pair(pair)?)?;
-                        }
-                        Rule::parenthesized_quantity => {
-                            let mut parenthesized_quantity = pair.into_inner();
-                            parenthesized_quantity.next().unwrap();
-                            quantity = Self::parse(parenthesized_quantity.next().unwrap())?;
-                            quantity.amount *= multiplier;
+                    match rule_of(&pair) {
+                        Rule::amount | Rule::amount_range => multiplier.set_amount(pair)?,
+                        // "2 (400g)" or "2 x 400g"
+                        Rule::parenthesized_quantity | Rule::multiplied_quantity => {
+                            let inner = pair
+                                .into_inner()
+                                .nth(1)
+                                .ok_or(IngreedyError::InnerRuleNoneError)?;
+                            let inner = Self::parse(inner)?;
+                            if inner.is_length() {
+                                // a count of pieces of a size ("1 (2-inch) piece")
+                                quantity = Self {
+                                    container_size: Some(Box::new(inner)),
+                                    ..multiplier.clone()
+                                };
+                            } else {
+                                quantity = inner;
+                                quantity.multiply_by(&multiplier);
+                            }
+                            quantity.source = QuantitySource::Multiplier;
                         }
                         _ => {}
                     }
                 }
             }
             Rule::amount_imprecise => {
-                let unit = get_next_inner_pair(pair)?;
-                quantity.unit_type = Some(UnitType::parse(&unit)?);
-                quantity.unit = Some(format!("{:?}", get_next_inner_pair(unit)?.as_rule()));
-                quantity.amount = 1.;
+                quantity.set_unit(parse_unit(pair)?);
+                quantity.amount = Amount::Exact(1.);
+                #[cfg(feature = "rational")]
+                {
+                    quantity.exact_amount = Some(num_rational::Rational64::from_integer(1));
+                }
+                quantity.source = QuantitySource::Imprecise;
             }
             _ => return Err(IngreedyError::wrong_rule(&pair, "quantity")),
         }
-
+        quantity.raw = raw.to_owned();
         Ok(quantity)
     }
+
+    /// Parse a standalone quantity (e.g. "2 cups", "1 (28 ounce)" or "pinch")
+    pub fn parse_str(input: &str) -> Result<Self, IngreedyError> {
+        let input = normalize::normalize_unicode(input, Normalization::default());
+        for pair in parser::IngredientParser::parse(Rule::quantity_input, &input)? {
+            if pair.as_rule() == Rule::quantity_fragment {
+                return Self::parse_fragment(pair);
+            }
+        }
+        Err(IngreedyError::InnerRuleNoneError)
+    }
+
+    /// Parse a `quantity_fragment` rule, which is either a full quantity or a bare amount,
+    /// optionally marked as approximate
+    fn parse_fragment(pair: Pair<Rule>) -> Result<Self, IngreedyError> {
+        let raw = pair.as_str().trim_end().to_owned();
+        let mut inner = pair.into_inner();
+        let mut quantity_fragment = inner.next().ok_or(IngreedyError::InnerRuleNoneError)?;
+        let approximate = rule_of(&quantity_fragment) == Rule::approximation;
+        if approximate {
+            quantity_fragment = inner.next().ok_or(IngreedyError::InnerRuleNoneError)?;
+        }
+        let quantity = match rule_of(&quantity_fragment) {
+            Rule::amount | Rule::amount_range => {
+                let mut quantity = Self::default();
+                quantity.set_amount(quantity_fragment)?;
+                quantity
+            }
+            Rule::quantity => Self::parse(get_next_inner_pair(quantity_fragment)?)?,
+            _ => {
+                return Err(IngreedyError::wrong_rule(
+                    &quantity_fragment,
+                    "quantity_fragment",
+                ))
+            }
+        };
+        Ok(Self {
+            amount: if approximate {
+                quantity.amount.approximate()
+            } else {
+                quantity.amount
+            },
+            raw,
+            ..quantity
+        })
+    }
+}
+
+/// Walk the pairs of a parsed ingredient line, calling `on_quantity` for each quantity,
+/// `on_alternative` for each alternative quantity, `on_size` for the size of the pieces
+/// and `on_name` for the ingredient name.
+///
+/// A leading bare amount is multiplied into the quantity after it ("2 1 cup" is 2 cups),
+/// in which case `on_quantity` is told to replace the first quantity.
+fn walk_pairs<'i>(
+    pairs: Pairs<'i, Rule>,
+    mut on_quantity: impl FnMut(Quantity, QuantitySpan, bool),
+    mut on_alternative: impl FnMut(Quantity),
+    mut on_size: impl FnMut(Size),
+    mut on_name: impl FnMut(&'i str, Span),
+) -> Result<(), IngreedyError> {
+    let mut count = 0;
+    let mut unitless_first: Option<(Quantity, QuantitySpan)> = None;
+    for rule in pairs {
+        match rule_of(&rule) {
+            Rule::multipart_quantity => {
+                for pair in rule.into_inner() {
+                    if rule_of(&pair) == Rule::plus {
+                        unitless_first = None;
+                    } else if rule_of(&pair) == Rule::quantity_fragment {
+                        let input = pair.get_input();
+                        let mut span = QuantitySpan::from_pair(&pair);
+                        let fragment = pair.clone();
+                        let mut quantity = Quantity::parse_fragment(pair)?;
+                        // only look for the span of a size once there is one
+                        let size_span = match quantity.container_size.as_deref() {
+                            Some(size) if size.is_length() => fragment
+                                .into_inner()
+                                .flatten()
+                                .find(|pair| {
+                                    matches!(
+                                        rule_of(pair),
+                                        Rule::parenthesized_quantity | Rule::multiplied_quantity
+                                    )
+                                })
+                                .map(|pair| QuantitySpan::from_pair(&pair)),
+                            _ => None,
+                        };
+                        let size = size_span.and_then(|size_span| {
+                            let size = quantity.container_size.take()?;
+                            Some((*size, size_span))
+                        });
+                        if size.is_some() {
+                            span.unit = None;
+                        }
+                        // a size isn't multiplied by the count before it ("one 9-inch pie crust")
+                        let replace_first = match unitless_first.take() {
+                            Some((first, first_span)) if !quantity.is_length() => {
+                                quantity.multiply_by(&first);
+                                span = first_span.merge(span);
+                                quantity.raw = span.quantity.as_str(input).trim_end().to_owned();
+                                true
+                            }
+                            _ => false,
+                        };
+                        count = if replace_first { 1 } else { count + 1 };
+                        if count == 1 && quantity.unit.is_none() {
+                            unitless_first = Some((quantity.clone(), span));
+                        }
+                        on_quantity(quantity, span, replace_first);
+                        if let Some((size, size_span)) = size {
+                            count += 1;
+                            unitless_first = None;
+                            on_quantity(size, size_span, false);
+                        }
+                    }
+                }
+            }
+            Rule::alternative_quantity => {
+                for pair in rule.into_inner().flat_map(Pair::into_inner) {
+                    if rule_of(&pair) == Rule::quantity_fragment {
+                        on_alternative(Quantity::parse_fragment(pair)?);
+                    }
+                }
+            }
+            Rule::ingredient_size => {
+                let size = get_next_inner_pair(rule)?;
+                on_size(
+                    Size::from_rule(rule_of(&size))
+                        .ok_or_else(|| IngreedyError::wrong_rule(&size, "ingredient_size"))?,
+                );
+            }
+            Rule::ingredient | Rule::leading_ingredient => {
+                let name = normalize::strip_of(rule.as_str());
+                let end = rule.as_span().end();
+                on_name(
+                    name,
+                    Span {
+                        start: end - name.len(),
+                        end,
+                    },
+                );
+            }
+            _ => {}
+        }
+    }
+    Ok(())
 }
 
 fn get_next_inner_pair(pair: Pair<Rule>) -> Result<Pair<Rule>, IngreedyError> {
@@ -239,60 +983,207 @@ fn get_next_inner_pair(pair: Pair<Rule>) -> Result<Pair<Rule>, IngreedyError> {
 }
 
 impl Ingredient {
-    /// Parse a single line of input into `Ingredient` information
+    /// Parse a single line of input into `Ingredient` information.
+    ///
+    /// Malformed input gives an error; no input makes this panic.
     #[inline]
     pub fn parse(input: &str) -> Result<Self, IngreedyError> {
-        Self::parse_pairs(IngredientParser::parse(Rule::ingredient_addition, input)?)
+        Self::parse_with_locale(input, Locale::En)
+    }
+    /// Parse a single line of input written in the given language
+    #[inline]
+    pub fn parse_with_locale(input: &str, locale: Locale) -> Result<Self, IngreedyError> {
+        Self::parse_normalized(
+            input,
+            locale,
+            Normalization::default(),
+            &ParseOptions::default(),
+        )
+    }
+    /// Parse a single line of input, never failing.
+    ///
+    /// If the line can't be parsed, or nothing in it is recognized, the whole
+    /// trimmed line becomes the ingredient name and an `Unparsed` warning says why.
+    pub fn parse_lossy(input: &str) -> Self {
+        let unparsed = |message: String| Self {
+            ingredient: Some(input.trim().to_owned()).filter(|name| !name.is_empty()),
+            warnings: vec![Warning {
+                category: WarningCategory::Unparsed,
+                message,
+            }],
+            ..Self::default()
+        };
+        match Self::parse(input) {
+            Ok(ingredient)
+                if ingredient.quantities.is_empty() && ingredient.ingredient.is_none() =>
+            {
+                unparsed("No quantity or ingredient found".into())
+            }
+            Ok(ingredient) => ingredient,
+            Err(error) => unparsed(format!("Couldn't parse line: {}", error)),
+        }
+    }
+    /// Parse a single line of input after Unicode normalization
+    fn parse_normalized(
+        input: &str,
+        locale: Locale,
+        normalization: Normalization,
+        options: &ParseOptions,
+    ) -> Result<Self, IngreedyError> {
+        let normalized = normalize::normalize_unicode(input, normalization);
+        let mut ingredient = Self::parse_spanned(
+            parser::IngredientParser::parse(locale.ingredient_rule(), &normalized)?,
+            options,
+        )?
+        .ingredient;
+        if let Cow::Owned(_) = normalized {
+            ingredient.raw = input.to_owned();
+        }
+        Ok(ingredient)
     }
     /// Parse `Ingredient` from Pest-returned Pairs<Rule> object
+    #[deprecated(
+        since = "0.3.0",
+        note = "pest's pairs change with the grammar; use `Ingredient::parse` or `Ast::parse`"
+    )]
     #[inline]
     pub fn parse_pairs(pairs: Pairs<Rule>) -> Result<Self, IngreedyError> {
+        Ok(Self::parse_spanned(pairs, &ParseOptions::default())?.ingredient)
+    }
+    /// Parse `Ingredient` from Pest-returned Pairs<Rule> object, with the spans of its components
+    fn parse_spanned(
+        pairs: Pairs<Rule>,
+        options: &ParseOptions,
+    ) -> Result<SpannedIngredient, IngreedyError> {
+        let input = pairs.get_input();
         let mut ingredient = Self {
-            quantities: Vec::new(),
-            ingredient: None,
+            raw: input.to_owned(),
+            ..Self::default()
         };
-        for rule in pairs {
-            match rule.as_rule() {
-                Rule::multipart_quantity => {
-                    for pair in rule.into_inner() {
-                        if pair.as_rule() == Rule::quantity_fragment {
-                            let quantity_fragment = get_next_inner_pair(pair)?;
-                            let mut quantity = match quantity_fragment.as_rule() {
-                                Rule::amount => Quantity {
-                                    amount: parse_amount(get_next_inner_pair(quantity_fragment)?)?,
-                                    ..Quantity::default()
-                                },
-                                Rule::quantity => {
-                                    Quantity::parse(get_next_inner_pair(quantity_fragment)?)?
-                                }
-                                _ => {
-                                    return Err(IngreedyError::wrong_rule(
-                                        &quantity_fragment,
-                                        "quantity_fragment",
-                                    ))
-                                }
-                            };
-                            if let Some(q) = ingredient.quantities.first() {
-                                if q.unit.is_none() {
-                                    quantity.amount *= q.amount;
-                                    ingredient.quantities = Vec::new();
-                                }
-                            }
-                            ingredient.quantities.push(quantity);
-                        }
+        let (mut spans, mut name_span, mut unspecified, mut temperature) =
+            (Vec::new(), None, None, None);
+        let mut durations = Vec::new();
+        let part = pairs
+            .clone()
+            .find(|pair| pair.as_rule() == Rule::fruit_part)
+            .map(|pair| pair.as_str());
+        let (quantities, alternatives, size, name, footnote, usage_note, note, preparation) = (
+            &mut ingredient.quantities,
+            &mut ingredient.alternatives,
+            &mut ingredient.size,
+            &mut ingredient.ingredient,
+            &mut ingredient.footnote,
+            &mut ingredient.usage_note,
+            &mut ingredient.note,
+            &mut ingredient.preparation,
+        );
+        walk_pairs(
+            pairs,
+            |quantity, span, replace_first| {
+                if replace_first {
+                    quantities.clear();
+                    spans.clear();
+                }
+                quantities.push(quantity);
+                spans.push(span);
+            },
+            |alternative| alternatives.push(alternative),
+            |piece_size| *size = Some(piece_size),
+            |mut ingredient_name, mut span| {
+                if !options.strip_of && input[..span.start].ends_with("of ") {
+                    span.start -= "of ".len();
+                    ingredient_name = &input[span.start..span.end];
+                }
+                unspecified =
+                    normalize::find_unspecified_amount(ingredient_name).map(|(start, end)| Span {
+                        start: span.start + start,
+                        end: span.start + end,
+                    });
+                let mut stripped = match normalize::split_footnote(ingredient_name) {
+                    Some((stripped, marker)) => {
+                        *footnote = Some(marker.to_owned());
+                        stripped
+                    }
+                    None => ingredient_name.to_owned(),
+                };
+                let strip = |stripped: &mut String, removed: Span| {
+                    let removed = removed.as_str(ingredient_name);
+                    if let Some(index) = stripped.find(removed) {
+                        let after = &stripped[index + removed.len()..];
+                        *stripped = format!("{}{}", stripped[..index].trim_end(), after);
                     }
+                };
+                if let Some((quantity, quantity_span, removed)) =
+                    temperature::find_temperature(ingredient_name)
+                {
+                    strip(&mut stripped, removed);
+                    temperature = Some((quantity, quantity_span.shift(span.start)));
                 }
-                Rule::ingredient => {
-                    let mut ing = rule.as_str();
-                    if ing.starts_with("of ") {
-                        ing = &ing[3..];
+                for (quantity, quantity_span, removed) in duration::find_durations(ingredient_name)
+                {
+                    strip(&mut stripped, removed);
+                    durations.push((quantity, quantity_span.shift(span.start)));
+                }
+                if let Some((rest, note)) = normalize::split_usage_note(&stripped) {
+                    *usage_note = Some(note.to_owned());
+                    stripped = rest.to_owned();
+                }
+                if options.split_note {
+                    if let Some((rest, text)) = normalize::split_note(&stripped) {
+                        *note = Some(text);
+                        stripped = rest;
                     }
-                    ingredient.ingredient = Some(ing.to_owned());
                 }
-                _ => {}
-            }
+                if options.split_preparation {
+                    if let Some((rest, text)) = stripped.split_once(',') {
+                        *preparation = Some(text.trim().to_owned()).filter(|text| !text.is_empty());
+                        stripped = rest.trim_end().to_owned();
+                    }
+                }
+                if stripped.is_empty() {
+                    return;
+                }
+                if ingredient_name.starts_with(stripped.as_str()) {
+                    span.end = span.start + stripped.len();
+                }
+                if let Some(part) = part {
+                    stripped = normalize::part_name(&stripped, part);
+                }
+                *name = Some(stripped);
+                name_span = Some(span);
+            },
+        )?;
+        if let (true, Some(span)) = (ingredient.quantities.is_empty(), unspecified) {
+            ingredient.quantities.push(Quantity {
+                raw: span.as_str(&ingredient.raw).to_owned(),
+                ..Quantity::default()
+            });
+            spans.push(QuantitySpan {
+                quantity: span,
+                amount: None,
+                unit: None,
+            });
         }
-        Ok(ingredient)
+        for (quantity, span) in temperature.into_iter().chain(durations) {
+            ingredient.quantities.push(quantity);
+            spans.push(span);
+        }
+        let mut spanned = SpannedIngredient {
+            ingredient,
+            quantities: spans,
+            name: name_span,
+        };
+        spanned.ingredient.check_warnings();
+        Ok(spanned)
+    }
+
+    /// Recompute warnings from the current quantities
+    pub(crate) fn check_warnings(&mut self) {
+        self.warnings = self
+            .quantities
+            .iter()
+            .filter_map(warning::check_quantity)
+            .collect();
     }
 }
 
@@ -300,6 +1191,49 @@ impl Ingredient {
 mod tests {
     use super::*;
     use approx::assert_relative_eq;
+    use proptest::prelude::*;
+
+    /// Run every entry point on an input, which may fail but must not panic
+    fn parse_everything(input: &str) {
+        for locale in Locale::all() {
+            let _ = Ingredient::parse_with_locale(input, *locale);
+        }
+        let _ = Ingredient::parse_lossy(input).to_string();
+        let _ = Ingredient::parse_with_spans(input);
+        let _ = Amount::parse(input);
+        let _ = Quantity::parse_str(input);
+        let _ = Recipe::parse(input).scale(2.).to_string();
+    }
+
+    proptest! {
+        #[test]
+        fn test_never_panics(input in "\\PC*") {
+            parse_everything(&input);
+        }
+
+        #[test]
+        fn test_never_panics_on_quantities(
+            input in "([0-9]{1,3}|[½¼⅓¹⁄₂/.,-]|cups?|tbsp|oz\\.|g|pinch|dozen|half|a|and|plus|to|about|hundred|twenty-five|[()*] |[ ]){0,12}[a-z ,]{0,10}"
+        ) {
+            parse_everything(&input);
+        }
+    }
+
+    #[test]
+    fn test_malformed_amounts() {
+        for input in &[
+            "1/2/3 cup flour",
+            "1/0 cup flour",
+            "½½ cup",
+            "2 (3 (4 oz)) cans",
+            "Makes 2 İstanbul rolls",
+            "9223372036854775807 1/2 cup flour",
+            "9223372036854775807 and 1/2 cups flour",
+            "3 1/9223372036854775807 cup flour",
+        ] {
+            parse_everything(input);
+        }
+    }
 
     #[test]
     fn test1() {
@@ -307,8 +1241,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 1.);
-        assert_eq!(ingredient.quantities[0].unit, Some("cup".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(ingredient.ingredient, Some("flour".to_string()));
     }
@@ -318,8 +1252,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 1.5);
-        assert_eq!(ingredient.quantities[0].unit, Some("cup".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.5);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(ingredient.ingredient, Some("flour".to_string()));
     }
@@ -329,7 +1263,7 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 1.5);
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.5);
         assert_eq!(ingredient.quantities[0].unit, None);
         assert_eq!(ingredient.quantities[0].unit_type, None);
         assert_eq!(ingredient.ingredient, Some("potatoes".to_string()));
@@ -340,7 +1274,7 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 12345.);
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 12345.);
         assert_eq!(ingredient.quantities[0].unit, None);
         assert_eq!(ingredient.quantities[0].unit_type, None);
         assert_eq!(ingredient.ingredient, Some("potatoes".to_string()));
@@ -351,8 +1285,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 5. / 3.);
-        assert_eq!(ingredient.quantities[0].unit, Some("cup".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 5. / 3.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(ingredient.ingredient, Some("flour".to_string()));
     }
@@ -362,8 +1296,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 72.);
-        assert_eq!(ingredient.quantities[0].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 72.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(
             ingredient.ingredient,
@@ -376,8 +1310,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 28.);
-        assert_eq!(ingredient.quantities[0].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 28.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(
             ingredient.ingredient,
@@ -390,8 +1324,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 0.5);
-        assert_eq!(ingredient.quantities[0].unit, Some("cup".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 0.5);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(ingredient.ingredient, Some("flour".to_string()));
     }
@@ -401,8 +1335,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 12.);
-        assert_eq!(ingredient.quantities[0].unit, Some("gram".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 12.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Gram));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::Metric));
         assert_eq!(ingredient.ingredient, Some("potatoes".to_string()));
     }
@@ -412,8 +1346,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 12.);
-        assert_eq!(ingredient.quantities[0].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 12.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(ingredient.ingredient, Some("potatoes".to_string()));
     }
@@ -423,8 +1357,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 12.);
-        assert_eq!(ingredient.quantities[0].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 12.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(ingredient.ingredient, Some("tequila".to_string()));
     }
@@ -434,7 +1368,7 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 0.5);
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 0.5);
         assert_eq!(ingredient.quantities[0].unit, None);
         assert_eq!(ingredient.quantities[0].unit_type, None);
         assert_eq!(ingredient.ingredient, Some("potato".to_string()));
@@ -445,8 +1379,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 1.5);
-        assert_eq!(ingredient.quantities[0].unit, Some("cup".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.5);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(ingredient.ingredient, Some("flour".to_string()));
     }
@@ -456,7 +1390,7 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 1.5);
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.5);
         assert_eq!(ingredient.quantities[0].unit, None);
         assert_eq!(ingredient.quantities[0].unit_type, None);
         assert_eq!(ingredient.ingredient, Some("potatoes".to_string()));
@@ -467,7 +1401,7 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 1.);
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.);
         assert_eq!(ingredient.quantities[0].unit, None);
         assert_eq!(ingredient.quantities[0].unit_type, None);
         assert_eq!(
@@ -481,8 +1415,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 1.);
-        assert_eq!(ingredient.quantities[0].unit, Some("cup".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(ingredient.ingredient, Some("flour".to_string()));
     }
@@ -492,7 +1426,7 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 1.);
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.);
         assert_eq!(ingredient.quantities[0].unit, None);
         assert_eq!(ingredient.quantities[0].unit_type, None);
         assert_eq!(
@@ -506,11 +1440,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 1.);
-        assert_eq!(
-            ingredient.quantities[0].unit,
-            Some("tablespoon".to_string())
-        );
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Tablespoon));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(
             ingredient.ingredient,
@@ -523,8 +1454,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 56.);
-        assert_eq!(ingredient.quantities[0].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 56.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(
             ingredient.ingredient,
@@ -532,13 +1463,32 @@ mod tests {
         );
     }
     #[test]
+    fn test_multiplication_sign() {
+        for input in [
+            "2 x 400g tins tomatoes",
+            "2x400g tins tomatoes",
+            "2 × 400 g tins tomatoes",
+        ] {
+            let ingredient = Ingredient::parse(input).unwrap();
+            assert_relative_eq!(ingredient.quantities[0].amount.value(), 800.);
+            assert_eq!(ingredient.quantities[0].unit, Some(Unit::Gram));
+            assert_eq!(ingredient.quantities[0].source, QuantitySource::Multiplier);
+            assert_eq!(ingredient.ingredient, Some("tins tomatoes".to_string()));
+        }
+        let ingredient = Ingredient::parse("3x 28oz cans tomatoes").unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 84.);
+        assert_eq!(ingredient.quantities[0].raw, "3x 28oz");
+        let ingredient = Ingredient::parse("2 xl eggs").unwrap();
+        assert_eq!(ingredient.ingredient, Some("xl eggs".to_string()));
+    }
+    #[test]
     fn test20() {
         let input = ".25 cups flour";
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 0.25);
-        assert_eq!(ingredient.quantities[0].unit, Some("cup".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 0.25);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(ingredient.ingredient, Some("flour".to_string()));
     }
@@ -548,8 +1498,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 2.);
-        assert_eq!(ingredient.quantities[0].unit, Some("cup".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(ingredient.ingredient, Some("potatoes".to_string()));
     }
@@ -559,7 +1509,7 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 2.);
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.);
         assert_eq!(ingredient.quantities[0].unit, None);
         assert_eq!(ingredient.quantities[0].unit_type, None);
         assert_eq!(ingredient.ingredient, Some("eggs, beaten".to_string()));
@@ -570,8 +1520,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 84.);
-        assert_eq!(ingredient.quantities[0].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 84.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(
             ingredient.ingredient,
@@ -584,8 +1534,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 5.75);
-        assert_eq!(ingredient.quantities[0].unit, Some("pinch".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 5.75);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Pinch));
         assert_eq!(
             ingredient.quantities[0].unit_type,
             Some(UnitType::Imprecise)
@@ -598,7 +1548,7 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 0.5);
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 0.5);
         assert_eq!(ingredient.quantities[0].unit, None);
         assert_eq!(ingredient.quantities[0].unit_type, None);
         assert_eq!(ingredient.ingredient, Some("potatoes".to_string()));
@@ -609,8 +1559,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 1.);
-        assert_eq!(ingredient.quantities[0].unit, Some("cup".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(ingredient.ingredient, Some("flour".to_string()));
     }
@@ -620,11 +1570,14 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert!(ingredient.quantities.is_empty());
+        assert_eq!(ingredient.quantities.len(), 1);
+        assert_eq!(ingredient.quantities[0].amount, Amount::Unspecified);
+        assert_eq!(ingredient.quantities[0].unit, None);
         assert_eq!(
             ingredient.ingredient,
-            Some("ground black pepper to taste".to_string())
+            Some("ground black pepper".to_string())
         );
+        assert_eq!(ingredient.usage_note, Some("to taste".to_string()));
     }
     #[test]
     fn test28() {
@@ -632,8 +1585,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 28.);
-        assert_eq!(ingredient.quantities[0].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 28.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(
             ingredient.ingredient,
@@ -646,8 +1599,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 1.);
-        assert_eq!(ingredient.quantities[0].unit, Some("cup".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(ingredient.ingredient, Some("flour".to_string()));
     }
@@ -657,8 +1610,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 84.);
-        assert_eq!(ingredient.quantities[0].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 84.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(
             ingredient.ingredient,
@@ -671,8 +1624,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 56.);
-        assert_eq!(ingredient.quantities[0].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 56.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(
             ingredient.ingredient,
@@ -685,8 +1638,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 10.);
-        assert_eq!(ingredient.quantities[0].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 10.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(
             ingredient.ingredient,
@@ -699,8 +1652,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 1.);
-        assert_eq!(ingredient.quantities[0].unit, Some("kilogram".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Kilogram));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::Metric));
         assert_eq!(ingredient.ingredient, Some("potatoes".to_string()));
     }
@@ -710,11 +1663,11 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 2.);
-        assert_eq!(ingredient.quantities[0].unit, Some("pound".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Pound));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
-        assert_relative_eq!(ingredient.quantities[1].amount, 4.);
-        assert_eq!(ingredient.quantities[1].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[1].amount.value(), 4.);
+        assert_eq!(ingredient.quantities[1].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[1].unit_type, Some(UnitType::English));
         assert_eq!(ingredient.ingredient, Some("potatoes".to_string()));
     }
@@ -724,11 +1677,11 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 2.);
-        assert_eq!(ingredient.quantities[0].unit, Some("pound".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Pound));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
-        assert_relative_eq!(ingredient.quantities[1].amount, 4.);
-        assert_eq!(ingredient.quantities[1].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[1].amount.value(), 4.);
+        assert_eq!(ingredient.quantities[1].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[1].unit_type, Some(UnitType::English));
         assert_eq!(ingredient.ingredient, Some("potatoes".to_string()));
     }
@@ -738,8 +1691,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 1.5);
-        assert_eq!(ingredient.quantities[0].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.5);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(ingredient.ingredient, Some("vanilla ice cream".to_string()));
     }
@@ -749,8 +1702,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 1.5);
-        assert_eq!(ingredient.quantities[0].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.5);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(ingredient.ingredient, Some("vanilla ice cream".to_string()));
     }
@@ -769,8 +1722,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 1.5);
-        assert_eq!(ingredient.quantities[0].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.5);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(ingredient.ingredient, Some("vanilla ice cream".to_string()));
     }
@@ -780,8 +1733,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 3.625);
-        assert_eq!(ingredient.quantities[0].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 3.625);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(
             ingredient.ingredient,
@@ -794,8 +1747,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 3.625);
-        assert_eq!(ingredient.quantities[0].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 3.625);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(
             ingredient.ingredient,
@@ -808,8 +1761,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 16.);
-        assert_eq!(ingredient.quantities[0].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 16.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(
             ingredient.ingredient,
@@ -822,11 +1775,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 750.);
-        assert_eq!(
-            ingredient.quantities[0].unit,
-            Some("milliliter".to_string())
-        );
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 750.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Milliliter));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::Metric));
         assert_eq!(
             ingredient.ingredient,
@@ -839,8 +1789,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 1.);
-        assert_eq!(ingredient.quantities[0].unit, Some("pinch".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Pinch));
         assert_eq!(
             ingredient.quantities[0].unit_type,
             Some(UnitType::Imprecise)
@@ -853,8 +1803,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 64.);
-        assert_eq!(ingredient.quantities[0].unit, Some("ounce".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 64.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Ounce));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert_eq!(
             ingredient.ingredient,
@@ -867,8 +1817,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 5.);
-        assert_eq!(ingredient.quantities[0].unit, Some("gram".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 5.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Gram));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::Metric));
         assert!(ingredient.ingredient.is_none());
     }
@@ -878,8 +1828,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 30.);
-        assert_eq!(ingredient.quantities[0].unit, Some("calorie".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 30.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Calorie));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert!(ingredient.ingredient.is_none());
     }
@@ -889,8 +1839,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 2.5);
-        assert_eq!(ingredient.quantities[0].unit, Some("calorie".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.5);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Calorie));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert!(ingredient.ingredient.is_none());
     }
@@ -900,8 +1850,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 50.);
-        assert_eq!(ingredient.quantities[0].unit, Some("joule".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 50.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Joule));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::Metric));
         assert!(ingredient.ingredient.is_none());
     }
@@ -911,8 +1861,8 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 1.);
-        assert_eq!(ingredient.quantities[0].unit, Some("kilojoule".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Kilojoule));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::Metric));
         assert!(ingredient.ingredient.is_none());
     }
@@ -922,9 +1872,698 @@ mod tests {
         let ingredient = Ingredient::parse(input);
         assert!(ingredient.is_ok());
         let ingredient = ingredient.unwrap();
-        assert_relative_eq!(ingredient.quantities[0].amount, 20.);
-        assert_eq!(ingredient.quantities[0].unit, Some("gallon".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 20.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Gallon));
         assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
         assert!(ingredient.ingredient.is_none());
     }
+    #[test]
+    fn test_amount_parse() {
+        assert_relative_eq!(Amount::parse("1 1/2").unwrap().value(), 1.5);
+        assert_relative_eq!(Amount::parse("¾").unwrap().value(), 0.75);
+        assert_relative_eq!(Amount::parse(" .25 ").unwrap().value(), 0.25);
+        assert_relative_eq!(Amount::parse("two").unwrap().value(), 2.);
+        assert!(Amount::parse("2 cups").is_err());
+        assert!(Amount::parse("flour").is_err());
+    }
+    #[test]
+    fn test_quantity_parse_str() {
+        let quantity = Quantity::parse_str("2 cups").unwrap();
+        assert_relative_eq!(quantity.amount.value(), 2.);
+        assert_eq!(quantity.unit, Some(Unit::Cup));
+        assert_eq!(quantity.unit_type, Some(UnitType::English));
+        let quantity = Quantity::parse_str("2 (28 ounce)").unwrap();
+        assert_relative_eq!(quantity.amount.value(), 56.);
+        assert_eq!(quantity.unit, Some(Unit::Ounce));
+        let quantity = Quantity::parse_str("3").unwrap();
+        assert_relative_eq!(quantity.amount.value(), 3.);
+        assert_eq!(quantity.unit, None);
+        assert!(Quantity::parse_str("2 cups flour").is_err());
+    }
+    #[test]
+    fn test_parse_lossy() {
+        let ingredient = Ingredient::parse_lossy("2 cups flour");
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
+        assert!(ingredient.warnings.is_empty());
+        let ingredient = Ingredient::parse_lossy(" (optional) ");
+        assert!(ingredient.quantities.is_empty());
+        assert_eq!(ingredient.ingredient, Some("(optional)".to_string()));
+        assert_eq!(ingredient.warnings[0].category, WarningCategory::Unparsed);
+        let ingredient = Ingredient::parse_lossy("");
+        assert_eq!(ingredient.ingredient, None);
+        assert_eq!(ingredient.warnings[0].category, WarningCategory::Unparsed);
+    }
+    #[test]
+    fn test_quantity_order() {
+        let units = |input| {
+            Ingredient::parse(input)
+                .unwrap()
+                .quantities
+                .into_iter()
+                .map(|quantity| quantity.unit)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            units("1 cup 2 tbsp 1 tsp milk"),
+            vec![
+                Some(Unit::Cup),
+                Some(Unit::Tablespoon),
+                Some(Unit::Teaspoon)
+            ]
+        );
+        assert_eq!(
+            units("4oz 2lb potatoes"),
+            vec![Some(Unit::Ounce), Some(Unit::Pound)]
+        );
+        assert_eq!(units("1kg / 2lb 4oz potatoes"), vec![Some(Unit::Kilogram)]);
+        assert_eq!(units("1 cup (240 ml) milk"), vec![Some(Unit::Cup)]);
+    }
+    #[test]
+    fn test_quantity_source() {
+        let source = |input| Ingredient::parse(input).unwrap().quantities[0].source;
+        assert_eq!(source("3 eggs"), QuantitySource::Amount);
+        assert_eq!(source("2 cups flour"), QuantitySource::AttachedUnit);
+        assert_eq!(
+            source("2 (28 ounce) cans tomatoes"),
+            QuantitySource::Multiplier
+        );
+        assert_eq!(source("1 cup (240 ml) milk"), QuantitySource::Conversion);
+        assert_eq!(source("pinch salt"), QuantitySource::Imprecise);
+    }
+    #[test]
+    fn test_alternatives() {
+        let ingredient = Ingredient::parse("750ml/1 pint 7fl oz hot vegetable stock").unwrap();
+        assert_eq!(ingredient.quantities.len(), 1);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Milliliter));
+        let units: Vec<_> = ingredient
+            .alternatives
+            .iter()
+            .map(|q| q.unit.clone())
+            .collect();
+        assert_eq!(units, vec![Some(Unit::Pint), Some(Unit::FluidOunce)]);
+        assert_relative_eq!(ingredient.alternatives[1].amount.value(), 7.);
+        assert_eq!(
+            ingredient.ingredient,
+            Some("hot vegetable stock".to_string())
+        );
+
+        let ingredient = Ingredient::parse("1 cup or 240 ml water").unwrap();
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
+        assert_relative_eq!(ingredient.alternatives[0].amount.value(), 240.);
+        assert_eq!(ingredient.alternatives[0].unit, Some(Unit::Milliliter));
+        assert_eq!(ingredient.ingredient, Some("water".to_string()));
+
+        let ingredient = Ingredient::parse("2 cups or so flour").unwrap();
+        assert!(ingredient.alternatives.is_empty());
+        assert_eq!(ingredient.ingredient, Some("or so flour".to_string()));
+        let ingredient =
+            Ingredient::parse_with_locale("250 g o 2 tazas de harina", Locale::Es).unwrap();
+        assert_eq!(ingredient.alternatives[0].unit, Some(Unit::Cup));
+        assert_eq!(ingredient.ingredient, Some("harina".to_string()));
+    }
+    #[test]
+    fn test_quantity_modifier() {
+        let ingredient = Ingredient::parse("1 heaping tablespoon cocoa powder").unwrap();
+        assert_eq!(
+            ingredient.quantities[0].modifier,
+            Some(QuantityModifier::Heaping)
+        );
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Tablespoon));
+        assert_eq!(ingredient.ingredient, Some("cocoa powder".to_string()));
+
+        let ingredient = Ingredient::parse("1 cup packed brown sugar").unwrap();
+        assert_eq!(
+            ingredient.quantities[0].modifier,
+            Some(QuantityModifier::Packed)
+        );
+        assert_eq!(ingredient.ingredient, Some("brown sugar".to_string()));
+        let ingredient = Ingredient::parse("1 cup firmly packed brown sugar").unwrap();
+        assert_eq!(
+            ingredient.quantities[0].modifier,
+            Some(QuantityModifier::Packed)
+        );
+        assert_eq!(ingredient.ingredient, Some("brown sugar".to_string()));
+
+        let modifier = |input| Ingredient::parse(input).unwrap().quantities[0].modifier;
+        assert_eq!(modifier("1 scant cup milk"), Some(QuantityModifier::Scant));
+        assert_eq!(modifier("1 level tsp salt"), Some(QuantityModifier::Level));
+        assert_eq!(
+            modifier("2 rounded tbsp flour"),
+            Some(QuantityModifier::Rounded)
+        );
+        assert_eq!(modifier("2 cups flour"), None);
+
+        let ingredient = Ingredient::parse("1 packed lunch").unwrap();
+        assert_eq!(ingredient.quantities[0].modifier, None);
+        assert_eq!(ingredient.ingredient, Some("packed lunch".to_string()));
+        assert_eq!(
+            Ingredient::parse("1 cup packed brown sugar")
+                .unwrap()
+                .to_string(),
+            "1 packed cup brown sugar"
+        );
+    }
+    #[test]
+    fn test_length_sizes() {
+        let input = "1 (2-inch) piece fresh ginger";
+        let spanned = Ingredient::parse_with_spans(input).unwrap();
+        let ingredient = &spanned.ingredient;
+        assert_eq!(ingredient.quantities.len(), 2);
+        assert_eq!(ingredient.quantities[0].amount, Amount::Exact(1.));
+        assert_eq!(ingredient.quantities[0].unit, None);
+        assert!(ingredient.quantities[0].container_size.is_none());
+        assert_eq!(ingredient.quantities[1].amount, Amount::Exact(2.));
+        assert_eq!(ingredient.quantities[1].unit, Some(Unit::Inch));
+        assert_eq!(spanned.quantities[1].unit.unwrap().as_str(input), "inch");
+        assert_eq!(
+            ingredient.ingredient,
+            Some("piece fresh ginger".to_string())
+        );
+
+        let ingredient = Ingredient::parse("one 9-inch pie crust").unwrap();
+        assert_eq!(ingredient.quantities[0].amount, Amount::Exact(1.));
+        assert_eq!(ingredient.quantities[1].amount, Amount::Exact(9.));
+        assert_eq!(ingredient.quantities[1].unit, Some(Unit::Inch));
+        assert_eq!(ingredient.quantities[1].unit_type, Some(UnitType::English));
+        assert_eq!(ingredient.ingredient, Some("pie crust".to_string()));
+        let doubled = ingredient.scale(2.);
+        assert_eq!(doubled.quantities[0].amount, Amount::Exact(2.));
+        assert_eq!(doubled.quantities[1].amount, Amount::Exact(9.));
+
+        let ingredient = Ingredient::parse("2 (5 cm) pieces cinnamon stick").unwrap();
+        assert_eq!(ingredient.quantities[0].amount, Amount::Exact(2.));
+        assert_eq!(ingredient.quantities[1].unit, Some(Unit::Centimeter));
+        assert_eq!(ingredient.quantities[1].unit_type, Some(UnitType::Metric));
+        let quantity = Quantity::parse_str("3 inches")
+            .unwrap()
+            .to_metric()
+            .unwrap();
+        assert_eq!(quantity.unit, Some(Unit::Centimeter));
+        assert!((quantity.amount.value() - 7.62).abs() < 1e-9);
+    }
+    #[test]
+    fn test_container_units() {
+        let ingredient = Ingredient::parse("1 can (15 oz) black beans").unwrap();
+        let quantity = &ingredient.quantities[0];
+        assert_eq!(quantity.unit, Some(Unit::Can));
+        assert_eq!(quantity.unit_type, Some(UnitType::Container));
+        assert_eq!(quantity.source, QuantitySource::Container);
+        let size = quantity.container_size.as_ref().unwrap();
+        assert_relative_eq!(size.amount.value(), 15.);
+        assert_eq!(size.unit, Some(Unit::Ounce));
+        assert_eq!(ingredient.ingredient, Some("black beans".to_string()));
+
+        let ingredient = Ingredient::parse("2 sticks butter").unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Stick));
+        assert!(ingredient.quantities[0].container_size.is_none());
+        assert_eq!(ingredient.ingredient, Some("butter".to_string()));
+
+        let ingredient = Ingredient::parse("1 canola oil").unwrap();
+        assert_eq!(ingredient.quantities[0].unit, None);
+        assert_eq!(ingredient.ingredient, Some("canola oil".to_string()));
+    }
+    #[test]
+    fn test_size() {
+        let ingredient = Ingredient::parse("2 large eggs").unwrap();
+        assert_eq!(ingredient.size, Some(Size::Large));
+        assert_eq!(ingredient.ingredient, Some("eggs".to_string()));
+        let ingredient = Ingredient::parse("1 Medium onion, diced").unwrap();
+        assert_eq!(ingredient.size, Some(Size::Medium));
+        assert_eq!(ingredient.ingredient, Some("onion, diced".to_string()));
+        let ingredient = Ingredient::parse("3 extra-large eggs").unwrap();
+        assert_eq!(ingredient.size, Some(Size::ExtraLarge));
+        assert_eq!(ingredient.ingredient, Some("eggs".to_string()));
+        assert_eq!(ingredient.to_string(), "3 extra large eggs");
+
+        let ingredient = Ingredient::parse("2 smallish potatoes").unwrap();
+        assert_eq!(ingredient.size, None);
+        assert_eq!(ingredient.ingredient, Some("smallish potatoes".to_string()));
+        let ingredient = Ingredient::parse("1 large").unwrap();
+        assert_eq!(ingredient.size, None);
+        assert_eq!(ingredient.ingredient, Some("large".to_string()));
+    }
+    #[test]
+    fn test_servings() {
+        let ingredient = Ingredient::parse("2 servings cooked rice").unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Serving));
+        assert_eq!(
+            ingredient.quantities[0].unit_type,
+            Some(UnitType::Imprecise)
+        );
+        assert_eq!(ingredient.ingredient, Some("cooked rice".to_string()));
+
+        let ingredient = Ingredient::parse("approximately 2 servings' worth of pasta").unwrap();
+        assert!(ingredient.quantities[0].amount.is_approximate());
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Serving));
+        assert_eq!(ingredient.ingredient, Some("pasta".to_string()));
+
+        let ingredient = Ingredient::parse("serving spoon").unwrap();
+        assert!(ingredient.quantities.is_empty());
+        assert_eq!(ingredient.ingredient, Some("serving spoon".to_string()));
+    }
+    #[test]
+    fn test_footnote() {
+        let ingredient = Ingredient::parse("1 cup flour*").unwrap();
+        assert_eq!(ingredient.ingredient, Some("flour".to_string()));
+        assert_eq!(ingredient.footnote, Some("*".to_string()));
+        assert_eq!(ingredient.to_string(), "1 cup flour*");
+        let ingredient = Ingredient::parse("2 tbsp butter†").unwrap();
+        assert_eq!(ingredient.ingredient, Some("butter".to_string()));
+        assert_eq!(ingredient.footnote, Some("†".to_string()));
+        let ingredient = Ingredient::parse("2 cups flour").unwrap();
+        assert_eq!(ingredient.footnote, None);
+
+        let input = "1 cup flour**";
+        let spanned = Ingredient::parse_with_spans(input).unwrap();
+        assert_eq!(spanned.name.unwrap().as_str(input), "flour");
+    }
+    #[test]
+    fn test_product_units() {
+        let unit = |input| Ingredient::parse(input).unwrap().quantities[0].unit.clone();
+        assert_eq!(unit("1 scoop whey"), Some(Unit::Scoop));
+        assert_eq!(unit("2 squares dark chocolate"), Some(Unit::Square));
+        assert_eq!(unit("1 tub mascarpone"), Some(Unit::Tub));
+        assert_eq!(unit("1 bar chocolate"), Some(Unit::Bar));
+        assert_eq!(unit("scoop vanilla ice cream"), Some(Unit::Scoop));
+        let ingredient = Ingredient::parse("2 squares dark chocolate").unwrap();
+        assert_eq!(
+            ingredient.quantities[0].unit_type,
+            Some(UnitType::Imprecise)
+        );
+        assert_eq!(ingredient.ingredient, Some("dark chocolate".to_string()));
+        let ingredient = Ingredient::parse("1 tub (250 g) mascarpone").unwrap();
+        assert_eq!(
+            ingredient.quantities[0].unit_type,
+            Some(UnitType::Container)
+        );
+        assert!(ingredient.quantities[0].container_size.is_some());
+
+        let ingredient = Ingredient::parse("2 barley rolls").unwrap();
+        assert_eq!(ingredient.quantities[0].unit, None);
+        let ingredient = Ingredient::parse("square baking pan").unwrap();
+        assert!(ingredient.quantities.is_empty());
+    }
+    #[test]
+    fn test_unit_case_and_periods() {
+        let unit = |input| Ingredient::parse(input).unwrap().quantities[0].unit.clone();
+        for (input, expected) in [
+            ("2 Tbsp. sugar", Unit::Tablespoon),
+            ("2 TBSP sugar", Unit::Tablespoon),
+            ("1 T sugar", Unit::Tablespoon),
+            ("1 Tsp. salt", Unit::Teaspoon),
+            ("1 t salt", Unit::Teaspoon),
+            ("2 Lbs. beef", Unit::Pound),
+            ("3 ozs cheese", Unit::Ounce),
+            ("1 Pkg. yeast", Unit::Package),
+            ("1 Qt. milk", Unit::Quart),
+            ("1 Gal. water", Unit::Gallon),
+            ("1 Fl Oz milk", Unit::FluidOunce),
+            ("2 Cups flour", Unit::Cup),
+            ("1 L water", Unit::Liter),
+        ] {
+            assert_eq!(unit(input), Some(expected), "{}", input);
+        }
+        let ingredient = Ingredient::parse("1 Tsp. salt").unwrap();
+        assert_eq!(ingredient.ingredient.as_deref(), Some("salt"));
+        let ingredient = Ingredient::parse("Drop biscuits").unwrap();
+        assert!(ingredient.quantities.is_empty());
+    }
+    #[test]
+    fn test_metric_sub_units() {
+        let unit = |input| Ingredient::parse(input).unwrap().quantities[0].unit.clone();
+        assert_eq!(unit("400 mcg folic acid"), Some(Unit::Microgram));
+        assert_eq!(unit("25 µg vitamin D"), Some(Unit::Microgram));
+        assert_eq!(unit("4 cl gin"), Some(Unit::Centiliter));
+        assert_eq!(unit("1.5 centilitres lime juice"), Some(Unit::Centiliter));
+        assert_eq!(unit("2 dL cream"), Some(Unit::Deciliter));
+        assert_eq!(unit("1 c sugar"), Some(Unit::Cup));
+        assert_eq!(unit("200cc water"), Some(Unit::Milliliter));
+        assert_eq!(unit("50 cm3 cream"), Some(Unit::Milliliter));
+        assert_eq!(unit("50 cm³ cream"), Some(Unit::Milliliter));
+        assert_eq!(unit("2 cm ginger"), Some(Unit::Centimeter));
+        let ingredient = Ingredient::parse("25 mcg vitamin D").unwrap();
+        assert!(ingredient.warnings.is_empty());
+        let ingredient = Ingredient::parse("3 dl milk").unwrap();
+        let milliliters = ingredient.quantities[0]
+            .convert_to(Unit::Milliliter)
+            .unwrap();
+        assert_relative_eq!(milliliters.amount.value(), 300.);
+    }
+    #[test]
+    fn test_approximate() {
+        for input in &[
+            "about 2 cups chicken stock",
+            "approx. 2 cups chicken stock",
+            "~2 cups chicken stock",
+            "roughly 2 cups chicken stock",
+        ] {
+            let ingredient = Ingredient::parse(input).unwrap();
+            assert!(
+                ingredient.quantities[0].amount.is_approximate(),
+                "{}",
+                input
+            );
+            assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.);
+            assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
+            assert_eq!(ingredient.ingredient, Some("chicken stock".to_string()));
+        }
+        let ingredient = Ingredient::parse("about 3 eggs").unwrap();
+        assert!(ingredient.quantities[0].amount.is_approximate());
+        assert_eq!(ingredient.ingredient, Some("eggs".to_string()));
+        assert!(!Ingredient::parse("2 cups flour").unwrap().quantities[0]
+            .amount
+            .is_approximate());
+        let ingredient = Ingredient::parse("roughly chopped onion").unwrap();
+        assert!(ingredient.quantities.is_empty());
+        assert_eq!(
+            ingredient.ingredient,
+            Some("roughly chopped onion".to_string())
+        );
+        let ingredient =
+            Ingredient::parse_with_locale("aproximadamente 200 g de harina", Locale::Es).unwrap();
+        assert!(ingredient.quantities[0].amount.is_approximate());
+        let ingredient =
+            Ingredient::parse_with_locale("environ 200 g de farine", Locale::Fr).unwrap();
+        assert!(ingredient.quantities[0].amount.is_approximate());
+        assert_eq!(ingredient.ingredient, Some("farine".to_string()));
+        assert!(Quantity::parse_str("~200 g")
+            .unwrap()
+            .amount
+            .is_approximate());
+        assert_eq!(
+            Ingredient::parse("about 2 cups stock").unwrap().to_string(),
+            "about 2 cups stock"
+        );
+        let ingredient = Ingredient::parse("about 2-3 cups flour").unwrap();
+        assert!(ingredient.quantities[0].amount.is_approximate());
+        assert_eq!(ingredient.quantities[0].amount.bounds(), (2., 3.));
+        assert_eq!(ingredient.to_string(), "about 2-3 cups flour");
+        assert!(ingredient.scale(2.).quantities[0].amount.is_approximate());
+        assert!(!Ingredient::parse("2-3 cups flour").unwrap().quantities[0]
+            .amount
+            .is_approximate());
+    }
+    #[test]
+    fn test_number_tables() {
+        for (fraction, value) in UNICODE_FRACTIONS {
+            assert_relative_eq!(Amount::parse(fraction).unwrap().value(), *value);
+        }
+        for (fraction, value) in UNICODE_FRACTIONS {
+            assert_eq!(unicode_fraction_value(fraction).unwrap(), *value);
+        }
+        for (word, value) in NUMBER_WORDS {
+            assert_eq!(number_word_value(word).unwrap(), *value, "{}", word);
+        }
+        for (word, value) in NUMBER_WORDS.iter().take(30) {
+            assert_relative_eq!(number_value(word).unwrap(), *value);
+        }
+        assert!(number_word_value("eleventy").is_err());
+    }
+    #[test]
+    fn test_word_fractions() {
+        for (input, amount, unit) in &[
+            ("half a cup milk", 0.5, Some(Unit::Cup)),
+            ("a quarter of a teaspoon salt", 0.25, Some(Unit::Teaspoon)),
+            ("three quarters cup sugar", 0.75, Some(Unit::Cup)),
+            ("three-quarters of a cup sugar", 0.75, Some(Unit::Cup)),
+            ("two thirds cup sugar", 2. / 3., Some(Unit::Cup)),
+            ("an eighth teaspoon salt", 0.125, Some(Unit::Teaspoon)),
+            ("half an onion", 0.5, None),
+        ] {
+            let ingredient = Ingredient::parse(input).unwrap();
+            assert_relative_eq!(ingredient.quantities[0].amount.value(), *amount);
+            assert_eq!(&ingredient.quantities[0].unit, unit, "{}", input);
+        }
+        let ingredient = Ingredient::parse("half and half").unwrap();
+        assert!(ingredient.quantities.is_empty());
+        assert_eq!(ingredient.ingredient, Some("half and half".to_string()));
+        assert_relative_eq!(Amount::parse("three quarters").unwrap().value(), 0.75);
+    }
+    #[test]
+    fn test_compound_numbers() {
+        for (input, amount) in &[
+            ("twenty-five almonds", 25.),
+            ("twenty five almonds", 25.),
+            ("sixteen cherries", 16.),
+            ("one hundred g flour", 100.),
+            ("one hundred and twenty g flour", 120.),
+            ("a dozen eggs", 12.),
+            ("two dozen eggs", 24.),
+            ("half a dozen eggs", 6.),
+            ("2 dozen eggs", 24.),
+        ] {
+            let ingredient = Ingredient::parse(input).unwrap();
+            assert_relative_eq!(ingredient.quantities[0].amount.value(), *amount);
+            assert!(ingredient.ingredient.is_some(), "{}", input);
+        }
+        assert_eq!(
+            Ingredient::parse("two dozen eggs").unwrap().ingredient,
+            Some("eggs".to_string())
+        );
+    }
+    #[test]
+    fn test_names_starting_like_numbers() {
+        for input in &[
+            "anise seeds",
+            "tenderloin",
+            "halibut fillet",
+            "onions",
+            "quartered figs",
+        ] {
+            let ingredient = Ingredient::parse(input).unwrap();
+            assert!(ingredient.quantities.is_empty(), "{}", input);
+            assert_eq!(ingredient.ingredient.as_deref(), Some(*input));
+        }
+        let ingredient = Ingredient::parse("one-half cup anise seeds").unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 0.5);
+    }
+    #[test]
+    fn test_ranges() {
+        let range = Amount::Range {
+            min: 2.,
+            max: 3.,
+            approximate: false,
+        };
+        for input in &[
+            "2-3 cups flour",
+            "2 - 3 cups flour",
+            "2 to 3 cups flour",
+            "2–3 cups flour",
+        ] {
+            let ingredient = Ingredient::parse(input).unwrap();
+            assert_eq!(ingredient.quantities.len(), 1, "{}", input);
+            assert_eq!(ingredient.quantities[0].amount, range);
+            assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
+            assert_eq!(ingredient.ingredient, Some("flour".to_string()));
+        }
+        let ingredient = Ingredient::parse("two to three eggs").unwrap();
+        assert_eq!(ingredient.quantities[0].amount, range);
+        let ingredient = Ingredient::parse("1-1/2 cups flour").unwrap();
+        assert_eq!(ingredient.quantities[0].amount, Amount::Exact(1.5));
+        let ingredient = Ingredient::parse("about 2 cups flour").unwrap();
+        assert_eq!(ingredient.quantities[0].amount, Amount::Approximate(2.));
+        let ingredient = Ingredient::parse("2-3 (15 oz) cans beans").unwrap();
+        assert_eq!(
+            ingredient.quantities[0].amount,
+            Amount::Range {
+                min: 30.,
+                max: 45.,
+                approximate: false
+            }
+        );
+        let ingredient = Ingredient::parse("2-3 cups flour").unwrap().scale(2.);
+        assert_eq!(
+            ingredient.quantities[0].amount,
+            Amount::Range {
+                min: 4.,
+                max: 6.,
+                approximate: false
+            }
+        );
+    }
+    #[test]
+    fn test_raw() {
+        let ingredient = Ingredient::parse("1-½ oz. butter").unwrap();
+        assert_eq!(ingredient.raw, "1-½ oz. butter");
+        assert_eq!(ingredient.quantities[0].raw, "1-½ oz.");
+        let ingredient = Ingredient::parse("about 2 cups / 500 ml milk").unwrap();
+        assert_eq!(ingredient.quantities[0].raw, "about 2 cups");
+        assert_eq!(ingredient.alternatives[0].raw, "500 ml");
+        let ingredient = Ingredient::parse("2 1 cup flour").unwrap();
+        assert_eq!(ingredient.quantities[0].raw, "2 1 cup");
+        let ingredient = Ingredient::parse("1 can (15 oz) beans").unwrap();
+        assert_eq!(ingredient.quantities[0].raw, "1 can (15 oz)");
+        assert_eq!(
+            ingredient.quantities[0]
+                .container_size
+                .as_ref()
+                .unwrap()
+                .raw,
+            "15 oz"
+        );
+        let ingredient = Ingredient::parse("salt, to taste").unwrap();
+        assert_eq!(ingredient.quantities[0].raw, "to taste");
+    }
+    #[test]
+    fn test_usage_note() {
+        for (input, name, note) in &[
+            ("salt and pepper, to taste", "salt and pepper", "to taste"),
+            ("2 tbsp flour, for dusting", "flour", "for dusting"),
+            ("parsley (for garnish)", "parsley", "for garnish"),
+            ("1 cup oil, as needed", "oil", "as needed"),
+            ("1 lemon, to serve", "lemon", "to serve"),
+            ("50 g walnuts (optional)", "walnuts", "optional"),
+        ] {
+            let ingredient = Ingredient::parse(input).unwrap();
+            assert_eq!(ingredient.ingredient.as_deref(), Some(*name));
+            assert_eq!(ingredient.usage_note.as_deref(), Some(*note), "{}", input);
+        }
+        let ingredient = Ingredient::parse("2 cups flour").unwrap();
+        assert_eq!(ingredient.usage_note, None);
+        let ingredient = Ingredient::parse("1 tsp taste-maker").unwrap();
+        assert_eq!(ingredient.usage_note, None);
+        let spanned = Ingredient::parse_with_spans("salt, to taste").unwrap();
+        assert_eq!(spanned.name, Some(Span { start: 0, end: 4 }));
+        assert_eq!(spanned.quantities[0].quantity, Span { start: 6, end: 14 });
+    }
+    #[test]
+    fn test_plus_quantities() {
+        for input in &[
+            "1 cup plus 2 tablespoons butter",
+            "1 cup and 2 tablespoons butter",
+        ] {
+            let ingredient = Ingredient::parse(input).unwrap();
+            assert_eq!(ingredient.quantities.len(), 2);
+            assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
+            assert_relative_eq!(ingredient.quantities[1].amount.value(), 2.);
+            assert_eq!(ingredient.quantities[1].unit, Some(Unit::Tablespoon));
+            assert_eq!(ingredient.ingredient, Some("butter".to_string()));
+        }
+        let ingredient = Ingredient::parse("2 and 1/2 cups flour").unwrap();
+        assert_eq!(ingredient.quantities.len(), 1);
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.5);
+        let ingredient = Ingredient::parse("1 cup flour and sugar").unwrap();
+        assert_eq!(ingredient.quantities.len(), 1);
+        assert_eq!(ingredient.ingredient, Some("flour and sugar".to_string()));
+    }
+    #[test]
+    fn test_list_markers() {
+        for input in &[
+            "- 1 cup flour",
+            "* 1 cup flour",
+            "• 1 cup flour",
+            "3. 1 cup flour",
+            "12) 1 cup flour",
+        ] {
+            let ingredient = Ingredient::parse(input).unwrap();
+            assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.);
+            assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup), "{}", input);
+            assert_eq!(ingredient.ingredient, Some("flour".to_string()));
+        }
+        let spanned = Ingredient::parse_with_spans("- 2 eggs").unwrap();
+        assert_eq!(spanned.name.map(|span| span.start), Some(4));
+        let ingredient = Ingredient::parse("1.5 cups flour").unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.5);
+        let ingredient = Ingredient::parse_with_locale("- 250 g de harina", Locale::Es).unwrap();
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Gram));
+    }
+    #[test]
+    fn test_trailing_quantity() {
+        let ingredient = Ingredient::parse("Flour, 2 cups").unwrap();
+        assert_eq!(ingredient.ingredient, Some("Flour".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
+        let ingredient = Ingredient::parse("Olive oil: 2 tbsp plus 1 tsp").unwrap();
+        assert_eq!(ingredient.ingredient, Some("Olive oil".to_string()));
+        assert_eq!(ingredient.quantities.len(), 2);
+        let ingredient = Ingredient::parse("Milk - 1 cup or 240 ml").unwrap();
+        assert_eq!(ingredient.ingredient, Some("Milk".to_string()));
+        assert_eq!(ingredient.alternatives[0].unit, Some(Unit::Milliliter));
+        let spanned = Ingredient::parse_with_spans("Sugar, 100g").unwrap();
+        assert_eq!(
+            spanned.name.map(|span| span.as_str("Sugar, 100g")),
+            Some("Sugar")
+        );
+        assert_eq!(spanned.quantities[0].quantity.as_str("Sugar, 100g"), "100g");
+        // a name with a number in it isn't split
+        let ingredient = Ingredient::parse("Onion, 1 diced").unwrap();
+        assert_eq!(ingredient.ingredient, Some("Onion, 1 diced".to_string()));
+        assert!(ingredient.quantities.is_empty());
+    }
+    #[test]
+    fn test_part_of_fruit() {
+        let ingredient = Ingredient::parse("juice of 2 lemons").unwrap();
+        assert_eq!(ingredient.ingredient, Some("lemon juice".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.);
+        assert_eq!(ingredient.quantities[0].unit, None);
+        let ingredient = Ingredient::parse("Zest of 1 orange").unwrap();
+        assert_eq!(ingredient.ingredient, Some("orange zest".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.);
+        let ingredient = Ingredient::parse("juice and zest of 1/2 lime, to serve").unwrap();
+        assert_eq!(
+            ingredient.ingredient,
+            Some("lime juice and zest".to_string())
+        );
+        assert_eq!(ingredient.usage_note, Some("to serve".to_string()));
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 0.5);
+        let ingredient = Ingredient::parse("the juice of a lemon").unwrap();
+        assert_eq!(ingredient.ingredient, Some("lemon juice".to_string()));
+    }
+    #[test]
+    fn test_unicode_input() {
+        let ingredient = Ingredient::parse("1⁄2 cup sugar").unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 0.5);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
+        assert_eq!(ingredient.ingredient, Some("sugar".to_string()));
+        let ingredient = Ingredient::parse("2 1⁄2 cups flour").unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.5);
+        let ingredient = Ingredient::parse("¹⁄₂ cup milk").unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 0.5);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
+        let ingredient = Ingredient::parse("3∕4 cup milk").unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 0.75);
+        assert_eq!(ingredient.ingredient, Some("milk".to_string()));
+        let ingredient = Ingredient::parse("1¹∕₃ cups milk").unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 4. / 3.);
+        let ingredient = Ingredient::parse("1¹/₄ cups milk").unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.25);
+        let ingredient = Ingredient::parse("2 e\u{301}clairs").unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.);
+        assert_eq!(ingredient.ingredient, Some("éclairs".to_string()));
+        let ingredient = Ingredient::parse("२ cups rice").unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.);
+        assert_eq!(ingredient.ingredient, Some("rice".to_string()));
+        assert_eq!(ingredient.raw, "२ cups rice");
+        let ingredient = Ingredient::parse("１２０\u{a0}g　flour").unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 120.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Gram));
+        assert_eq!(ingredient.ingredient, Some("flour".to_string()));
+        let ingredient = Ingredient::parse("５００ｇ　flour").unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 500.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Gram));
+        assert_eq!(ingredient.ingredient, Some("flour".to_string()));
+        let ingredient = Ingredient::parse("１ ＴＢＳＰ sugar").unwrap();
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Tablespoon));
+        let ingredient = Ingredient::parse("١٫٥ kg flour").unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.5);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Kilogram));
+        let config = ParserConfig {
+            normalization: Normalization::Nfkc,
+            ..ParserConfig::default()
+        };
+        let ingredient = Ingredient::parse_with_config("1½ cups \u{fb02}our", &config).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.5);
+        assert_eq!(ingredient.ingredient, Some("flour".to_string()));
+    }
+    #[cfg(feature = "unit-string")]
+    #[test]
+    fn test_unit_string() {
+        let ingredient = Ingredient::parse("2 fl oz milk").unwrap();
+        assert_eq!(
+            ingredient.quantities[0].unit_string,
+            Some("fluid_ounce".to_string())
+        );
+    }
 }
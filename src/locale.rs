@@ -0,0 +1,225 @@
+//! Languages the grammar can parse
+
+use crate::Rule;
+use serde::{Deserialize, Serialize};
+
+/// Language of the ingredient lines to parse
+#[derive(Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    /// English (e.g. "2 cups of flour")
+    #[default]
+    En,
+    /// Spanish (e.g. "250 g de harina")
+    Es,
+    /// French (e.g. "2 cuillères à soupe de sucre")
+    Fr,
+    /// Chinese, with the jin and liang (e.g. "半斤猪肉")
+    Zh,
+    /// Japanese, with the gō (e.g. "2合 米")
+    Ja,
+}
+
+/// Every language with a grammar
+pub(crate) const LOCALES: &[Locale] = &[Locale::En, Locale::Es, Locale::Fr, Locale::Zh, Locale::Ja];
+
+impl Locale {
+    /// Every language with a grammar
+    pub fn all() -> &'static [Locale] {
+        LOCALES
+    }
+
+    /// Grammar rule parsing a full ingredient line in this language
+    pub(crate) fn ingredient_rule(&self) -> Rule {
+        match self {
+            Self::En => Rule::ingredient_addition,
+            Self::Es => Rule::es_ingredient_addition,
+            Self::Fr => Rule::fr_ingredient_addition,
+            Self::Zh => Rule::zh_ingredient_addition,
+            Self::Ja => Rule::ja_ingredient_addition,
+        }
+    }
+}
+
+/// Map the locale-specific rules of grammar_es.pest, grammar_fr.pest, grammar_zh.pest
+/// and grammar_ja.pest onto the English rules they mirror, so the same code walks all parse trees
+pub(crate) fn canonical_rule(rule: Rule) -> Rule {
+    match rule {
+        Rule::zh_multipart_quantity | Rule::ja_multipart_quantity => Rule::multipart_quantity,
+        Rule::zh_quantity_fragment | Rule::ja_quantity_fragment => Rule::quantity_fragment,
+        Rule::zh_approximation | Rule::ja_approximation => Rule::approximation,
+        Rule::zh_quantity | Rule::ja_quantity => Rule::quantity,
+        Rule::zh_amount_with_attached_units | Rule::ja_amount_with_attached_units => {
+            Rule::amount_with_attached_units
+        }
+        Rule::zh_amount | Rule::ja_amount => Rule::amount,
+        Rule::zh_ingredient | Rule::ja_ingredient => Rule::ingredient,
+        Rule::zh_unit | Rule::ja_unit | Rule::ja_spoon_unit => Rule::unit,
+        Rule::zh_english_unit | Rule::ja_english_unit => Rule::english_unit,
+        Rule::zh_metric_unit | Rule::ja_metric_unit => Rule::metric_unit,
+        Rule::zh_cup => Rule::cup,
+        Rule::zh_tablespoon | Rule::ja_tablespoon => Rule::tablespoon,
+        Rule::zh_teaspoon | Rule::ja_teaspoon => Rule::teaspoon,
+        Rule::zh_gram | Rule::ja_gram => Rule::gram,
+        Rule::zh_kilogram | Rule::ja_kilogram => Rule::kilogram,
+        Rule::zh_liter | Rule::ja_liter => Rule::liter,
+        Rule::zh_milliliter | Rule::ja_milliliter => Rule::milliliter,
+        Rule::zh_number | Rule::ja_number => Rule::number,
+        Rule::zh_written_number => Rule::written_number,
+        Rule::es_multipart_quantity | Rule::fr_multipart_quantity => Rule::multipart_quantity,
+        Rule::es_quantity_fragment | Rule::fr_quantity_fragment => Rule::quantity_fragment,
+        Rule::es_approximation | Rule::fr_approximation => Rule::approximation,
+        Rule::es_alternative_quantity | Rule::fr_alternative_quantity => Rule::alternative_quantity,
+        Rule::es_quantity | Rule::fr_quantity => Rule::quantity,
+        Rule::es_amount_with_attached_units | Rule::fr_amount_with_attached_units => {
+            Rule::amount_with_attached_units
+        }
+        Rule::es_amount_with_multiplier | Rule::fr_amount_with_multiplier => {
+            Rule::amount_with_multiplier
+        }
+        Rule::es_amount_imprecise | Rule::fr_amount_imprecise => Rule::amount_imprecise,
+        Rule::es_parenthesized_quantity | Rule::fr_parenthesized_quantity => {
+            Rule::parenthesized_quantity
+        }
+        Rule::es_amount | Rule::fr_amount => Rule::amount,
+        Rule::es_ingredient | Rule::fr_ingredient => Rule::ingredient,
+        Rule::es_unit | Rule::fr_unit => Rule::unit,
+        Rule::es_english_unit | Rule::fr_english_unit => Rule::english_unit,
+        Rule::es_metric_unit | Rule::fr_metric_unit => Rule::metric_unit,
+        Rule::es_imprecise_unit | Rule::fr_imprecise_unit => Rule::imprecise_unit,
+        Rule::es_cup | Rule::fr_cup => Rule::cup,
+        Rule::es_ounce | Rule::fr_ounce => Rule::ounce,
+        Rule::es_pound | Rule::fr_pound => Rule::pound,
+        Rule::es_tablespoon | Rule::fr_tablespoon => Rule::tablespoon,
+        Rule::es_teaspoon | Rule::fr_teaspoon => Rule::teaspoon,
+        Rule::es_gram | Rule::fr_gram => Rule::gram,
+        Rule::es_kilogram | Rule::fr_kilogram => Rule::kilogram,
+        Rule::es_milligram | Rule::fr_milligram => Rule::milligram,
+        Rule::es_liter | Rule::fr_liter => Rule::liter,
+        Rule::es_milliliter | Rule::fr_milliliter => Rule::milliliter,
+        Rule::es_dash | Rule::fr_dash => Rule::dash,
+        Rule::es_handful | Rule::fr_handful => Rule::handful,
+        Rule::es_pinch | Rule::fr_pinch => Rule::pinch,
+        Rule::es_number | Rule::fr_number => Rule::number,
+        Rule::es_written_number | Rule::fr_written_number => Rule::written_number,
+        rule => rule,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Ingredient, Locale, Unit, UnitType};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_spanish() {
+        let ingredient = Ingredient::parse_with_locale("250 g de harina", Locale::Es).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 250.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Gram));
+        assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::Metric));
+        assert_eq!(ingredient.ingredient, Some("harina".to_string()));
+
+        let ingredient =
+            Ingredient::parse_with_locale("dos cucharadas de azúcar", Locale::Es).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Tablespoon));
+        assert_eq!(ingredient.ingredient, Some("azúcar".to_string()));
+
+        let ingredient = Ingredient::parse_with_locale("una pizca de sal", Locale::Es).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Pinch));
+        assert_eq!(ingredient.ingredient, Some("sal".to_string()));
+    }
+
+    #[test]
+    fn test_french() {
+        let ingredient =
+            Ingredient::parse_with_locale("2 cuillères à soupe de sucre", Locale::Fr).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Tablespoon));
+        assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
+        assert_eq!(ingredient.ingredient, Some("sucre".to_string()));
+
+        let ingredient =
+            Ingredient::parse_with_locale("1 1/2 kg de pommes de terre", Locale::Fr).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.5);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Kilogram));
+        assert_eq!(ingredient.ingredient, Some("pommes de terre".to_string()));
+
+        let ingredient = Ingredient::parse_with_locale("10 cl d'huile", Locale::Fr).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 10.);
+        assert_eq!(ingredient.ingredient, Some("cl d'huile".to_string()));
+
+        let ingredient = Ingredient::parse_with_locale("1 l de lait", Locale::Fr).unwrap();
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Liter));
+        assert_eq!(ingredient.ingredient, Some("lait".to_string()));
+
+        let ingredient = Ingredient::parse_with_locale("3 œufs", Locale::Fr).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 3.);
+        assert_eq!(ingredient.ingredient, Some("œufs".to_string()));
+
+        let ingredient = Ingredient::parse_with_locale("une pincée de sel", Locale::Fr).unwrap();
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Pinch));
+        assert_eq!(ingredient.ingredient, Some("sel".to_string()));
+    }
+
+    #[test]
+    fn test_chinese() {
+        let ingredient = Ingredient::parse_with_locale("半斤猪肉", Locale::Zh).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 0.5);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Jin));
+        assert_eq!(
+            ingredient.quantities[0].unit_type,
+            Some(UnitType::Traditional)
+        );
+        assert_eq!(ingredient.ingredient, Some("猪肉".to_string()));
+        let grams = ingredient.quantities[0].to_metric().unwrap();
+        assert_relative_eq!(grams.amount.value(), 250.);
+
+        let ingredient = Ingredient::parse_with_locale("半斤 pork", Locale::Zh).unwrap();
+        assert_eq!(ingredient.ingredient, Some("pork".to_string()));
+
+        let ingredient = Ingredient::parse_with_locale("二两白酒", Locale::Zh).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Liang));
+        assert_eq!(ingredient.ingredient, Some("白酒".to_string()));
+
+        let ingredient = Ingredient::parse_with_locale("2 catties pork belly", Locale::Zh).unwrap();
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Jin));
+
+        let ingredient = Ingredient::parse_with_locale("200克面粉", Locale::Zh).unwrap();
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Gram));
+        assert_eq!(ingredient.ingredient, Some("面粉".to_string()));
+
+        let ingredient = Ingredient::parse_with_locale("两个鸡蛋", Locale::Zh).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.);
+        assert_eq!(ingredient.quantities[0].unit, None);
+        assert_eq!(ingredient.ingredient, Some("鸡蛋".to_string()));
+    }
+
+    #[test]
+    fn test_japanese() {
+        let ingredient = Ingredient::parse_with_locale("2合 rice", Locale::Ja).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Go));
+        assert_eq!(ingredient.ingredient, Some("rice".to_string()));
+        let milliliters = ingredient.quantities[0].to_metric().unwrap();
+        assert_relative_eq!(milliliters.amount.value(), 360.78);
+
+        let ingredient = Ingredient::parse_with_locale("大さじ2 醤油", Locale::Ja).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 2.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Tablespoon));
+        assert_eq!(ingredient.ingredient, Some("醤油".to_string()));
+
+        let ingredient = Ingredient::parse_with_locale("200cc 水", Locale::Ja).unwrap();
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Milliliter));
+    }
+
+    #[test]
+    fn test_english_default() {
+        let ingredient =
+            Ingredient::parse_with_locale("2 cups of flour", Locale::default()).unwrap();
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
+        assert_eq!(ingredient.ingredient, Some("flour".to_string()));
+    }
+}
@@ -1,21 +1,531 @@
-#[cfg(not(feature = "cli"))]
-fn main() {}
+use clap::{ArgEnum, Clap};
+use ingreedy_rs::{
+    Amount, AmountStyle, DensityTable, Expected, FormatOptions, Ingredient, IngredientStream,
+    IngreedyError, LabeledLine, Locale, NytRow, ParserConfig, PieceWeights, Quantity, Recipe,
+    ShoppingList, Size, Unit, UnitCategory, UnitType,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
 
-#[cfg(feature = "cli")]
-use clap::Clap;
-use ingreedy_rs::Ingredient;
-
-#[cfg(feature = "cli")]
 #[derive(Clap, Debug)]
 #[clap(name = "ingreedy")]
 struct Ingreedy {
+    /// Ingredient line to parse, or "-" to read newline-delimited lines from stdin
+    input: Option<String>,
+    /// File of newline-delimited ingredient lines to parse
+    #[clap(short, long, parse(from_os_str), conflicts_with = "input")]
+    file: Option<PathBuf>,
+    /// Leave lines that fail to parse out of the output
+    #[clap(long, conflicts_with = "fail-fast")]
+    skip_errors: bool,
+    /// Stop at the first line that fails to parse
+    #[clap(long)]
+    fail_fast: bool,
+    /// Read plain lines one at a time and write each result as soon as its line is parsed
+    /// rather than in buffered blocks, for piping a growing file or a slow producer through
+    /// the parser
+    #[clap(long)]
+    stream: bool,
+    /// TOML file with custom units, stopwords, default locale and formatting preferences
+    #[clap(short, long, parse(from_os_str))]
+    config: Option<PathBuf>,
+    /// Output format: JSON (NDJSON for batches), YAML, CSV or TSV with the first quantity
+    /// in columns, plain text formatted with the config file's [format] table, or CSV in the
+    /// columns of the NYT ingredient-phrase-tagger (input, name, qty, range_end, unit, comment)
+    #[clap(long, arg_enum, default_value = "json")]
+    format: OutputFormat,
+    /// Same as --format plain
+    #[clap(long, conflicts_with = "format")]
+    text: bool,
+    /// Format of batch input: plain lines, or NDJSON / CSV records with an "input"
+    /// and an optional "locale" overriding the default locale for that line
+    #[clap(long, arg_enum, default_value = "lines")]
+    input_format: InputFormat,
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Clap, Debug)]
+enum Command {
+    /// Print the JSON Schema of the ingredients printed as JSON
+    Schema {
+        /// Print the schema of a parsed recipe block instead
+        #[clap(long)]
+        recipe: bool,
+    },
+    /// Convert the quantity of an ingredient to another unit, going through the ingredient's
+    /// density or piece weight between volumes, weights and counts (e.g. "1 cup sugar" to grams)
+    Convert {
+        /// Ingredient line to convert
+        input: String,
+        /// Unit to convert to (e.g. "grams", "ml", "oz")
+        #[clap(long)]
+        to: Unit,
+    },
+    /// Multiply the amounts of a recipe's ingredients and yield by a factor, printing its lines
+    /// formatted with the config file's [format] table
+    Scale {
+        /// Factor to multiply amounts by (e.g. 2.5, or 0.5 to halve the recipe)
+        factor: f64,
+        /// File of newline-delimited recipe lines, read from stdin if not given
+        #[clap(short, long, parse(from_os_str))]
+        file: Option<PathBuf>,
+    },
+    /// Merge the ingredients of several recipes into a shopping list,
+    /// adding up quantities of the same ingredient
+    Aggregate {
+        /// Files of newline-delimited recipe lines
+        #[clap(parse(from_os_str), required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Score the parser against a CSV of labeled lines, with "input", "amount" (or "qty"),
+    /// "unit" and "name" columns, printing the mismatches and the accuracy of each field
+    Eval {
+        /// CSV file of labeled lines
+        #[clap(parse(from_os_str))]
+        file: PathBuf,
+    },
+}
+
+#[derive(ArgEnum, Debug, Clone, Copy)]
+enum InputFormat {
+    Lines,
+    Ndjson,
+    Csv,
+}
+
+#[derive(ArgEnum, Debug, Clone, Copy, PartialEq)]
+enum OutputFormat {
+    Json,
+    Yaml,
+    Csv,
+    Tsv,
+    Plain,
+    Nyt,
+}
+
+/// A line of batch input, with an optional language tag
+#[derive(Deserialize, Debug)]
+struct Record {
     input: String,
+    #[serde(default)]
+    locale: Option<Locale>,
+}
+
+/// Read batch input as records in the given format
+fn read_records<'a>(
+    reader: impl BufRead + 'a,
+    input_format: InputFormat,
+) -> Box<dyn Iterator<Item = color_eyre::Result<Record>> + 'a> {
+    match input_format {
+        InputFormat::Lines => Box::new(reader.lines().map(|line| {
+            Ok(Record {
+                input: line?,
+                locale: None,
+            })
+        })),
+        InputFormat::Ndjson => Box::new(
+            reader
+                .lines()
+                .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()))
+                .map(|line| Ok(serde_json::from_str(&line?)?)),
+        ),
+        InputFormat::Csv => Box::new(
+            csv::Reader::from_reader(reader)
+                .into_deserialize()
+                .map(|record| Ok(record?)),
+        ),
+    }
+}
+
+/// Contents of a `--config` file: `ParserConfig` fields at the top level, plus a [format] table
+#[derive(Default, Deserialize, Debug)]
+#[serde(default)]
+struct Config {
+    #[serde(flatten)]
+    parser: ParserConfig,
+    format: FormatOptions,
+}
+
+impl Config {
+    fn load(path: &std::path::Path) -> color_eyre::Result<Self> {
+        Ok(toml::from_str(&std::fs::read_to_string(path)?)?)
+    }
+}
+
+/// A CSV or TSV row: an ingredient with its first quantity, or the error parsing it
+#[derive(Serialize, Default)]
+struct Row<'a> {
+    input: &'a str,
+    amount: Option<f64>,
+    unit: Option<String>,
+    unit_type: Option<String>,
+    ingredient: Option<&'a str>,
+    error: Option<String>,
+}
+
+/// Writes parsed ingredients, and errors for lines that failed, in the output format
+enum Printer<W: Write> {
+    Json { output: W, pretty: bool },
+    Yaml(W),
+    Table(Box<csv::Writer<W>>),
+    Nyt(Box<csv::Writer<W>>),
+    Plain(W, FormatOptions),
+}
+
+impl<W: Write> Printer<W> {
+    /// Printer for one ingredient (`batch` false) or one per input line
+    fn new(output: W, format: OutputFormat, batch: bool, config: &Config) -> Self {
+        match format {
+            OutputFormat::Json => Self::Json {
+                output,
+                pretty: !batch,
+            },
+            OutputFormat::Yaml => Self::Yaml(output),
+            OutputFormat::Csv | OutputFormat::Tsv => Self::Table(Box::new(
+                csv::WriterBuilder::new()
+                    .delimiter(if format == OutputFormat::Tsv {
+                        b'\t'
+                    } else {
+                        b','
+                    })
+                    .from_writer(output),
+            )),
+            OutputFormat::Plain => Self::Plain(output, config.format),
+            OutputFormat::Nyt => Self::Nyt(Box::new(csv::Writer::from_writer(output))),
+        }
+    }
+
+    fn ingredient(&mut self, input: &str, ingredient: &Ingredient) -> color_eyre::Result<()> {
+        match self {
+            Self::Json { output, pretty } => {
+                if *pretty {
+                    serde_json::to_writer_pretty(&mut *output, ingredient)?;
+                } else {
+                    serde_json::to_writer(&mut *output, ingredient)?;
+                }
+                writeln!(output)?;
+            }
+            // Through a JSON value so enums are maps like in JSON, not YAML tags
+            Self::Yaml(output) => write!(
+                output,
+                "---\n{}",
+                serde_yaml::to_string(&serde_json::to_value(ingredient)?)?
+            )?,
+            Self::Table(writer) => {
+                let quantity = ingredient.quantities.first();
+                writer.serialize(Row {
+                    input,
+                    amount: quantity.map(|quantity| quantity.amount.value()),
+                    unit: quantity
+                        .and_then(|q| q.unit.as_ref())
+                        .map(|unit| unit.to_string()),
+                    unit_type: quantity.and_then(|q| q.unit_type.clone()).map(String::from),
+                    ingredient: ingredient.ingredient.as_deref(),
+                    ..Row::default()
+                })?
+            }
+            Self::Nyt(writer) => writer.serialize(NytRow {
+                input: input.to_owned(),
+                ..ingredient.to_nyt_row()
+            })?,
+            Self::Plain(output, format) => writeln!(output, "{}", ingredient.format(format))?,
+        }
+        Ok(())
+    }
+
+    fn error(&mut self, input: &str, error: &ingreedy_rs::IngreedyError) -> color_eyre::Result<()> {
+        let error = error.to_string();
+        match self {
+            Self::Json { output, .. } => {
+                serde_json::to_writer(
+                    &mut *output,
+                    &serde_json::json!({ "input": input, "error": error }),
+                )?;
+                writeln!(output)?;
+            }
+            Self::Yaml(output) => write!(
+                output,
+                "---\n{}",
+                serde_yaml::to_string(&serde_json::json!({ "input": input, "error": error }))?
+            )?,
+            Self::Table(writer) => writer.serialize(Row {
+                input,
+                error: Some(error),
+                ..Row::default()
+            })?,
+            // the tagger's data has no error column, so a failed line is left unlabeled
+            Self::Nyt(writer) => writer.serialize(NytRow {
+                input: input.to_owned(),
+                ..NytRow::default()
+            })?,
+            Self::Plain(output, _) => writeln!(output, "(error: {}) {}", error, input)?,
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Json { output, .. } | Self::Yaml(output) | Self::Plain(output, _) => {
+                output.flush()
+            }
+            Self::Table(writer) | Self::Nyt(writer) => writer.flush(),
+        }
+    }
+}
+
+/// Print the result of parsing a line of batch input, returning an error to stop at
+fn print_result<W: Write>(
+    printer: &mut Printer<W>,
+    line: &str,
+    result: Result<Ingredient, IngreedyError>,
+    ingreedy: &Ingreedy,
+) -> color_eyre::Result<()> {
+    match result {
+        Ok(ingredient) => printer.ingredient(line, &ingredient),
+        Err(IngreedyError::Io(error)) => Err(error.into()),
+        Err(error) if ingreedy.fail_fast => {
+            printer.flush()?;
+            Err(color_eyre::eyre::eyre!(
+                "Failed to parse '{}': {}",
+                line,
+                error
+            ))
+        }
+        Err(_) if ingreedy.skip_errors => Ok(()),
+        Err(error) => printer.error(line, &error),
+    }
+}
+
+/// Parse newline-delimited ingredients, printing one result per line
+fn parse_batch(
+    reader: impl BufRead,
+    ingreedy: &Ingreedy,
+    config: &Config,
+) -> color_eyre::Result<()> {
+    let stdout = io::stdout();
+    let output = io::BufWriter::new(stdout.lock());
+    let mut printer = Printer::new(output, ingreedy.output_format(), true, config);
+    if ingreedy.stream {
+        return stream_batch(reader, ingreedy, config, printer);
+    }
+    let mut locale_configs = HashMap::new();
+    for record in read_records(reader, ingreedy.input_format) {
+        let Record {
+            input: line,
+            locale,
+        } = record?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let parser_config = match locale {
+            Some(locale) if locale != config.parser.locale => locale_configs
+                .entry(locale)
+                .or_insert_with(|| ParserConfig {
+                    locale,
+                    ..config.parser.clone()
+                }),
+            _ => &config.parser,
+        };
+        let result = Ingredient::parse_with_config(&line, parser_config);
+        print_result(&mut printer, &line, result, ingreedy)?;
+    }
+    printer.flush()?;
+    Ok(())
+}
+
+/// Parse plain ingredient lines one at a time through `IngredientStream`,
+/// printing each result as soon as its line is parsed
+fn stream_batch<W: Write>(
+    reader: impl BufRead,
+    ingreedy: &Ingreedy,
+    config: &Config,
+    mut printer: Printer<W>,
+) -> color_eyre::Result<()> {
+    if !matches!(ingreedy.input_format, InputFormat::Lines) {
+        return Err(color_eyre::eyre::eyre!("--stream reads plain lines only"));
+    }
+    let mut stream = IngredientStream::with_config(reader, config.parser.clone());
+    while let Some((_, result)) = stream.next() {
+        print_result(&mut printer, stream.line(), result, ingreedy)?;
+        printer.flush()?;
+    }
+    Ok(())
+}
+
+/// A quantity of a unit
+fn quantity_of(amount: Amount, unit: Unit) -> Quantity {
+    Quantity {
+        amount,
+        unit_type: Some(unit.unit_type()),
+        unit: Some(unit),
+        ..Quantity::default()
+    }
 }
-#[cfg(feature = "cli")]
+
+/// Convert a quantity of an ingredient to a unit, through its density between volumes and
+/// masses, and through grams from counts and containers
+fn convert_quantity(
+    quantity: &Quantity,
+    name: &str,
+    size: Option<Size>,
+    unit: &Unit,
+    densities: &DensityTable,
+    piece_weights: &PieceWeights,
+) -> Result<Quantity, IngreedyError> {
+    if quantity.amount.is_unspecified() {
+        return Err(IngreedyError::Incomplete(Expected::Amount));
+    }
+    match quantity.unit.as_ref().map(Unit::category) {
+        Some(UnitCategory::Mass | UnitCategory::Volume) if quantity.container_size.is_none() => {
+            quantity.convert_with_density(unit.clone(), name, densities)
+        }
+        _ => {
+            let grams = quantity.to_grams_sized(name, size, densities, piece_weights)?;
+            // scale the amount rather than replace it, keeping ranges and approximations
+            let value = quantity.amount.value();
+            let grams = if value == 0. {
+                Amount::Exact(grams)
+            } else {
+                quantity.amount * (grams / value)
+            };
+            quantity_of(grams, Unit::Gram).convert_with_density(unit.clone(), name, densities)
+        }
+    }
+}
+
+/// Convert the quantities measuring an ingredient to a unit, adding up multipart quantities
+/// ("1 lb 8 oz") and leaving out temperatures and durations
+fn convert(ingredient: &Ingredient, unit: &Unit) -> Result<Ingredient, IngreedyError> {
+    let name = ingredient.ingredient.as_deref().unwrap_or_default();
+    let (densities, piece_weights) = (DensityTable::builtin(), PieceWeights::builtin());
+    let amount = ingredient
+        .quantities
+        .iter()
+        .filter(|quantity| quantity.measures_ingredient())
+        .map(|quantity| {
+            convert_quantity(
+                quantity,
+                name,
+                ingredient.size,
+                unit,
+                &densities,
+                &piece_weights,
+            )
+        })
+        .try_fold(Amount::Unspecified, |amount, quantity| {
+            Ok::<_, IngreedyError>(amount + quantity?.amount)
+        })?;
+    if amount.is_unspecified() {
+        return Err(IngreedyError::Incomplete(Expected::Quantity));
+    }
+    Ok(Ingredient {
+        quantities: vec![quantity_of(amount, unit.clone())],
+        ..ingredient.clone()
+    })
+}
+
+impl Ingreedy {
+    fn output_format(&self) -> OutputFormat {
+        if self.text {
+            OutputFormat::Plain
+        } else {
+            self.format
+        }
+    }
+}
+
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
     let ingreedy = Ingreedy::parse();
-    let ingredient = Ingredient::parse(&ingreedy.input)?;
-    println!("{}", serde_json::to_string_pretty(&ingredient)?);
+    if let Some(Command::Schema { recipe }) = ingreedy.command {
+        let schema = if recipe {
+            ingreedy_rs::recipe_json_schema()
+        } else {
+            ingreedy_rs::json_schema()
+        };
+        println!("{}", serde_json::to_string_pretty(&schema)?);
+        return Ok(());
+    }
+    let config = match &ingreedy.config {
+        Some(path) => Config::load(path)?,
+        None => Config::default(),
+    };
+    if let Some(Command::Convert { input, to }) = &ingreedy.command {
+        let ingredient = convert(&Ingredient::parse_with_config(input, &config.parser)?, to)?;
+        let mut format = config.format;
+        if to.unit_type() == UnitType::Metric {
+            format.amount_style = AmountStyle::Decimal;
+        }
+        println!("{}", ingredient.format(&format));
+        return Ok(());
+    }
+    if let Some(Command::Scale { factor, file }) = &ingreedy.command {
+        let text = match file {
+            Some(file) => std::fs::read_to_string(file)?,
+            None => io::read_to_string(io::stdin())?,
+        };
+        let recipe = Recipe::parse_with_config(&text, &config.parser).scale(*factor);
+        println!("{}", recipe.format(&config.format));
+        return Ok(());
+    }
+    if let Some(Command::Aggregate { files }) = &ingreedy.command {
+        let mut list = ShoppingList::new();
+        for file in files {
+            let recipe = Recipe::parse_with_config(&std::fs::read_to_string(file)?, &config.parser);
+            list.extend(recipe.ingredients().cloned());
+        }
+        println!("{}", list.format(&config.format));
+        return Ok(());
+    }
+    if let Some(Command::Eval { file }) = &ingreedy.command {
+        let lines = csv::Reader::from_path(file)?
+            .deserialize()
+            .collect::<Result<Vec<LabeledLine>, _>>()?;
+        print!("{}", ingreedy_rs::evaluate(&lines, &config.parser));
+        return Ok(());
+    }
+    match (&ingreedy.file, ingreedy.input.as_deref()) {
+        (Some(file), _) => {
+            let reader = BufReader::new(std::fs::File::open(file)?);
+            parse_batch(reader, &ingreedy, &config)?;
+        }
+        (None, Some("-")) => parse_batch(io::stdin().lock(), &ingreedy, &config)?,
+        (None, Some(input)) => {
+            let ingredient = Ingredient::parse_with_config(input, &config.parser)?;
+            let mut printer = Printer::new(io::stdout(), ingreedy.output_format(), false, &config);
+            printer.ingredient(input, &ingredient)?;
+            printer.flush()?;
+        }
+        (None, None) => {
+            return Err(color_eyre::eyre::eyre!(
+                "Expected an ingredient, \"-\" for stdin, or --file"
+            ))
+        }
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert() {
+        let ingredient = Ingredient::parse("1 cup warm water (110°F)").unwrap();
+        let converted = convert(&ingredient, &Unit::Milliliter).unwrap();
+        assert_eq!(converted.quantities.len(), 1);
+        assert_eq!(converted.quantities[0].unit, Some(Unit::Milliliter));
+        assert!((converted.quantities[0].amount.value() - 236.6).abs() < 1.);
+
+        let ingredient = Ingredient::parse("1 lb 8 oz flour").unwrap();
+        let converted = convert(&ingredient, &Unit::Gram).unwrap();
+        assert!((converted.quantities[0].amount.value() - 680.4).abs() < 1.);
+        let converted = convert(&Ingredient::parse("2 small eggs").unwrap(), &Unit::Gram).unwrap();
+        assert_eq!(converted.quantities[0].amount.value(), 76.);
+        let converted = convert(&Ingredient::parse("0 eggs").unwrap(), &Unit::Gram).unwrap();
+        assert_eq!(converted.quantities[0].amount.value(), 0.);
+    }
+}
@@ -0,0 +1,201 @@
+//! Canonical ingredient names, for deduplication, lookups and aggregation
+
+use crate::normalize::{match_form, strip_of};
+use crate::Ingredient;
+use serde::Deserialize;
+
+/// Descriptor and preparation words dropped from ingredient names
+const DESCRIPTORS: &[&str] = &[
+    "boneless", "chopped", "coarsely", "crushed", "cubed", "diced", "finely", "fresh", "freshly",
+    "grated", "minced", "peeled", "roughly", "shredded", "skinless", "sliced", "thinly",
+];
+
+/// Strip a leading "of" and preparation text after the first comma,
+/// and drop the words `is_descriptor` matches
+fn clean_name(name: &str, is_descriptor: impl Fn(&str) -> bool) -> String {
+    let name = strip_of(name.trim());
+    let name = name.split(',').next().unwrap_or_default();
+    name.split_whitespace()
+        .filter(|word| !is_descriptor(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Lowercase a cleaned name and make each word singular, or stem it with the `stemmer` feature
+fn key(clean_name: &str) -> String {
+    clean_name
+        .to_lowercase()
+        .split_whitespace()
+        .map(|word| match_form(word).into_owned())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn is_builtin_descriptor(word: &str) -> bool {
+    DESCRIPTORS
+        .iter()
+        .any(|descriptor| descriptor.eq_ignore_ascii_case(word))
+}
+
+/// Key that names of the same ingredient share, for matching and deduplication
+/// (e.g. "Diced Tomatoes" and "tomato, diced" are both "tomato").
+///
+/// The name is cleaned with `clean_ingredient_name`, lowercased, and each word made
+/// singular, or stemmed with the `stemmer` feature.
+pub fn ingredient_key(name: &str) -> String {
+    key(&clean_ingredient_name(name))
+}
+
+/// Normalize an ingredient name without a quantity present.
+///
+/// Strips a leading "of", drops preparation text after the first comma,
+/// removes descriptor words like "fresh" or "chopped" and collapses whitespace,
+/// e.g. "of freshly chopped parsley, to garnish" becomes "parsley".
+pub fn clean_ingredient_name(name: &str) -> String {
+    clean_name(name, is_builtin_descriptor)
+}
+
+/// Turns ingredient names into canonical keys like `ingredient_key`,
+/// with its own list of descriptor words to drop.
+///
+/// Deserializes from a list of descriptors (e.g. `["fresh", "organic"]`).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "Vec<String>")]
+pub struct NameNormalizer {
+    descriptors: Vec<String>,
+}
+
+impl Default for NameNormalizer {
+    /// Normalizer dropping the built-in descriptors, giving the same keys as `ingredient_key`
+    fn default() -> Self {
+        Self::with_descriptors(DESCRIPTORS.iter().copied())
+    }
+}
+
+impl From<Vec<String>> for NameNormalizer {
+    fn from(descriptors: Vec<String>) -> Self {
+        Self { descriptors }
+    }
+}
+
+impl NameNormalizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Normalizer dropping only the given descriptors
+    pub fn with_descriptors<S: Into<String>>(descriptors: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            descriptors: descriptors.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Also drop a descriptor word (matched ignoring ASCII case)
+    pub fn add_descriptor(&mut self, descriptor: &str) {
+        self.descriptors.push(descriptor.to_owned());
+    }
+
+    pub fn descriptors(&self) -> &[String] {
+        &self.descriptors
+    }
+
+    /// Canonical key of a name (e.g. "Fresh Boneless Skinless Chicken Breasts" as "chicken breast")
+    pub fn normalize(&self, name: &str) -> String {
+        key(&clean_name(name, |word| {
+            self.descriptors
+                .iter()
+                .any(|descriptor| descriptor.eq_ignore_ascii_case(word))
+        }))
+    }
+}
+
+impl Ingredient {
+    /// Canonical key of the ingredient name (see `ingredient_key`), or `None` without a name
+    pub fn normalized_name(&self) -> Option<String> {
+        self.ingredient.as_deref().map(ingredient_key)
+    }
+
+    /// Canonical key of the ingredient name, dropping the normalizer's descriptors
+    pub fn normalized_name_with(&self, normalizer: &NameNormalizer) -> Option<String> {
+        self.ingredient
+            .as_deref()
+            .map(|name| normalizer.normalize(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ingredient_key() {
+        assert_eq!(
+            ingredient_key("Diced Tomatoes"),
+            ingredient_key("tomato, diced")
+        );
+        assert_eq!(ingredient_key("Chopped Onions"), ingredient_key("onion"));
+        assert_ne!(ingredient_key("onion"), ingredient_key("garlic"));
+        #[cfg(not(feature = "stemmer"))]
+        assert_eq!(ingredient_key("Diced Tomatoes"), "tomato");
+        #[cfg(feature = "stemmer")]
+        assert_eq!(ingredient_key("peaches"), ingredient_key("peach"));
+    }
+
+    #[test]
+    fn test_clean_ingredient_name() {
+        assert_eq!(
+            clean_ingredient_name("of freshly chopped parsley, to garnish"),
+            "parsley"
+        );
+        assert_eq!(
+            clean_ingredient_name("boneless skinless chicken breasts"),
+            "chicken breasts"
+        );
+        assert_eq!(
+            clean_ingredient_name("  clove  garlic, minced "),
+            "clove garlic"
+        );
+        assert_eq!(clean_ingredient_name("Fresh Basil"), "Basil");
+        assert_eq!(clean_ingredient_name("offal"), "offal");
+        assert_eq!(clean_ingredient_name(""), "");
+    }
+
+    #[test]
+    fn test_normalized_name() {
+        let chicken = Ingredient::parse("2 Fresh Boneless Skinless Chicken Breasts").unwrap();
+        assert_eq!(
+            chicken.normalized_name(),
+            Some(ingredient_key("chicken breast"))
+        );
+        assert_eq!(
+            Ingredient::parse("3 potatoes").unwrap().normalized_name(),
+            Ingredient::parse("1 potato, peeled")
+                .unwrap()
+                .normalized_name()
+        );
+        assert_eq!(Ingredient::parse("2 cups").unwrap().normalized_name(), None);
+    }
+
+    #[test]
+    fn test_name_normalizer() {
+        let eggs = Ingredient::parse("2 organic fresh eggs").unwrap();
+        let mut normalizer = NameNormalizer::with_descriptors(["fresh"]);
+        assert_eq!(
+            eggs.normalized_name_with(&normalizer),
+            Some(ingredient_key("organic eggs"))
+        );
+        normalizer.add_descriptor("Organic");
+        assert_eq!(
+            eggs.normalized_name_with(&normalizer),
+            Some(ingredient_key("eggs"))
+        );
+        assert_eq!(eggs.normalized_name(), Some(ingredient_key("organic eggs")));
+        let normalizer: NameNormalizer = serde_json::from_str(r#"["organic"]"#).unwrap();
+        assert_eq!(normalizer.normalize("Organic Eggs"), ingredient_key("eggs"));
+        #[cfg(not(feature = "stemmer"))]
+        assert_eq!(
+            NameNormalizer::new().normalize("Fresh Boneless Skinless Chicken Breasts"),
+            "chicken breast"
+        );
+    }
+}
@@ -0,0 +1,425 @@
+//! Unicode and number normalization of input, and helpers for ingredient names
+
+#[cfg(feature = "stemmer")]
+use lazy_static::lazy_static;
+#[cfg(feature = "stemmer")]
+use rust_stemmers::{Algorithm, Stemmer};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+/// Unicode normalization applied to input before parsing.
+///
+/// Except for `None`, digits of other decimal numeral systems (e.g. Devanagari "२",
+/// Arabic-Indic "٢" or full-width "２") are also read as ASCII digits, full-width letters
+/// and punctuation (e.g. "ｇ") as ASCII, and Unicode spaces (e.g. no-break or ideographic)
+/// as ASCII spaces.
+#[derive(Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Normalization {
+    /// Leave the input as is
+    None,
+    /// Compose combining characters (e.g. "e" followed by U+0301 into "é")
+    #[default]
+    Nfc,
+    /// Also replace compatibility characters (e.g. the "ﬂ" ligature with "fl")
+    Nfkc,
+}
+
+/// Character between the whole and fractional part of amounts
+#[derive(Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum DecimalSeparator {
+    /// "1.5 kg", with commas between thousands ("1,000 g")
+    #[default]
+    Period,
+    /// "1,5 kg", with periods or spaces between thousands ("1.000 g" or "1 000 g")
+    Comma,
+}
+
+/// Rewrite amounts to the period decimals the grammar parses, dropping thousands separators
+/// (e.g. "1,000 g" as "1000 g", or "1,5 kg" as "1.5 kg" with `DecimalSeparator::Comma`)
+pub(crate) fn normalize_numbers(input: &str, separator: DecimalSeparator) -> Cow<'_, str> {
+    let bytes = input.as_bytes();
+    let is_digit = |index: usize| bytes.get(index).is_some_and(u8::is_ascii_digit);
+    let mut output: Option<String> = None;
+    let mut start = 0;
+    for (index, &byte) in bytes.iter().enumerate() {
+        if index == 0 || !is_digit(index - 1) || !is_digit(index + 1) {
+            continue;
+        }
+        let is_thousands = match separator {
+            DecimalSeparator::Period => byte == b',',
+            DecimalSeparator::Comma => byte == b'.' || byte == b' ',
+        } && (1..=3).all(|offset| is_digit(index + offset))
+            && !is_digit(index + 4)
+            && (0..index).rev().take_while(|&i| is_digit(i)).count() <= 3;
+        let replacement = if is_thousands {
+            ""
+        } else if separator == DecimalSeparator::Comma && byte == b',' {
+            "."
+        } else {
+            continue;
+        };
+        let output = output.get_or_insert_with(|| String::with_capacity(input.len()));
+        output.push_str(&input[start..index]);
+        output.push_str(replacement);
+        start = index + 1;
+    }
+    match output {
+        Some(mut output) => {
+            output.push_str(&input[start..]);
+            Cow::Owned(output)
+        }
+        None => Cow::Borrowed(input),
+    }
+}
+
+/// Vulgar fractions the grammar parses directly, kept intact under NFKC
+/// so that "1½" doesn't decompose into "11⁄2"
+fn is_vulgar_fraction(c: char) -> bool {
+    ('¼'..='¾').contains(&c) || ('⅐'..='⅞').contains(&c)
+}
+
+/// Zero digits of the decimal numeral systems read as ASCII digits: Arabic-Indic,
+/// Persian, Devanagari, Bengali, Gurmukhi, Gujarati, Oriya, Tamil, Telugu, Kannada,
+/// Malayalam and Thai
+const ZERO_DIGITS: &[char] = &[
+    '\u{660}', '\u{6f0}', '\u{966}', '\u{9e6}', '\u{a66}', '\u{ae6}', '\u{b66}', '\u{be6}',
+    '\u{c66}', '\u{ce6}', '\u{d66}', '\u{e50}',
+];
+
+/// ASCII character read in place of a digit of another numeral system, the Arabic
+/// decimal separator "٫", a full-width character (e.g. "５", "ｇ" or "／"), or a Unicode space
+/// (e.g. no-break or ideographic)
+fn ascii_equivalent(c: char) -> Option<char> {
+    match c {
+        '\u{66b}' => Some('.'),
+        '\u{ff01}'..='\u{ff5e}' => char::from_u32(c as u32 - 0xfee0),
+        '\u{a0}' | '\u{2000}'..='\u{200a}' | '\u{202f}' | '\u{205f}' | '\u{3000}' => Some(' '),
+        c => ZERO_DIGITS.iter().find_map(|&zero| {
+            let value = (c as u32).checked_sub(zero as u32)?;
+            char::from_digit(value, 10)
+        }),
+    }
+}
+
+/// Replace digits, separators and spaces that have an ASCII equivalent with it
+fn fold_to_ascii(input: Cow<'_, str>) -> Cow<'_, str> {
+    if !input.chars().any(|c| ascii_equivalent(c).is_some()) {
+        return input;
+    }
+    input
+        .chars()
+        .map(|c| ascii_equivalent(c).unwrap_or(c))
+        .collect()
+}
+
+/// Normalize input text, borrowing it if it's already normalized
+pub(crate) fn normalize_unicode(input: &str, normalization: Normalization) -> Cow<'_, str> {
+    match normalization {
+        Normalization::None => Cow::Borrowed(input),
+        Normalization::Nfc => fold_to_ascii(match is_nfc_quick(input.chars()) {
+            IsNormalized::Yes => Cow::Borrowed(input),
+            _ => Cow::Owned(input.nfc().collect()),
+        }),
+        Normalization::Nfkc => {
+            let mut output = String::with_capacity(input.len());
+            let mut start = 0;
+            for (index, c) in input.char_indices() {
+                if is_vulgar_fraction(c) {
+                    output.extend(input[start..index].nfkc());
+                    output.push(c);
+                    start = index + c.len_utf8();
+                }
+            }
+            output.extend(input[start..].nfkc());
+            fold_to_ascii(Cow::Owned(output))
+        }
+    }
+}
+
+/// Strip a leading "of " left over after a quantity (e.g. "2 cups of flour")
+pub(crate) fn strip_of(name: &str) -> &str {
+    name.strip_prefix("of ").unwrap_or(name)
+}
+
+/// Singular form of a lowercase English plural ("potatoes" -> "potato", "berries" -> "berry")
+pub(crate) fn singular(word: &str) -> Cow<'_, str> {
+    if let Some(stem) = word.strip_suffix("ies") {
+        Cow::Owned(format!("{}y", stem))
+    } else if let Some(stem) = word.strip_suffix("oes") {
+        Cow::Owned(format!("{}o", stem))
+    } else if word.ends_with("ss") {
+        Cow::Borrowed(word)
+    } else {
+        Cow::Borrowed(word.strip_suffix('s').unwrap_or(word))
+    }
+}
+
+/// Name of the part of a fruit used ("lemon juice" for the "juice" of "lemons")
+pub(crate) fn part_name(fruit: &str, part: &str) -> String {
+    let (rest, last) = match fruit.rsplit_once(' ') {
+        Some((rest, last)) => (format!("{} ", rest), last),
+        None => (String::new(), fruit),
+    };
+    format!("{}{} {}", rest, singular(last), part.to_lowercase())
+}
+
+#[cfg(feature = "stemmer")]
+lazy_static! {
+    static ref STEMMER: Stemmer = Stemmer::create(Algorithm::English);
+}
+
+/// Form of a lowercase word that its variants share: its Porter stem with the
+/// `stemmer` feature, otherwise its singular
+pub(crate) fn match_form(word: &str) -> Cow<'_, str> {
+    #[cfg(feature = "stemmer")]
+    {
+        STEMMER.stem(word)
+    }
+    #[cfg(not(feature = "stemmer"))]
+    {
+        singular(word)
+    }
+}
+
+/// Phrases standing in for an amount (e.g. "salt to taste")
+const UNSPECIFIED_AMOUNT_PHRASES: &[&str] = &["to taste", "as needed", "as required", "as desired"];
+
+/// Byte range of a phrase in a name that stands in for an amount, matched as whole words
+pub(crate) fn find_unspecified_amount(name: &str) -> Option<(usize, usize)> {
+    let lowercase = name.to_ascii_lowercase();
+    UNSPECIFIED_AMOUNT_PHRASES.iter().find_map(|phrase| {
+        let start = lowercase.find(phrase)?;
+        let end = start + phrase.len();
+        let is_boundary = |c: Option<char>| c.is_none_or(|c| !c.is_alphanumeric());
+        (is_boundary(lowercase[..start].chars().next_back())
+            && is_boundary(lowercase[end..].chars().next()))
+        .then_some((start, end))
+    })
+}
+
+/// Purpose phrases taken off the end of ingredient names
+const USAGE_NOTES: &[&str] = &[
+    "to taste",
+    "as needed",
+    "as required",
+    "as desired",
+    "for serving",
+    "to serve",
+    "for garnish",
+    "to garnish",
+    "for garnishing",
+    "for decoration",
+    "for dusting",
+    "for greasing",
+    "for frying",
+    "for brushing",
+    "optional",
+];
+
+/// Split a trailing purpose phrase off a name (e.g. "parsley, for garnish" into
+/// "parsley" and "for garnish"), along with the comma or parenthesis before it
+pub(crate) fn split_usage_note(name: &str) -> Option<(&str, &str)> {
+    let trimmed = name.trim_end_matches(|c: char| c == ')' || c == '.' || c.is_whitespace());
+    let lowercase = trimmed.to_ascii_lowercase();
+    let note = USAGE_NOTES.iter().find(|note| lowercase.ends_with(*note))?;
+    let start = trimmed.len() - note.len();
+    let before = &trimmed[..start];
+    if before
+        .chars()
+        .next_back()
+        .is_some_and(char::is_alphanumeric)
+    {
+        return None;
+    }
+    let rest = before.trim_end_matches(|c: char| c == ',' || c == '(' || c.is_whitespace());
+    Some((rest, &trimmed[start..]))
+}
+
+/// Take the parenthesized remarks out of a name (e.g. "Sazon seasoning (recommended: Goya)"
+/// into "Sazon seasoning" and "recommended: Goya"), joining several with "; "
+pub(crate) fn split_note(name: &str) -> Option<(String, String)> {
+    let mut stripped = String::with_capacity(name.len());
+    let mut notes = Vec::new();
+    let mut rest = name;
+    while let Some(open) = rest.find('(') {
+        let close = match rest[open..].find(')') {
+            Some(close) => open + close,
+            None => break,
+        };
+        stripped.push_str(&rest[..open]);
+        let note = rest[open + 1..close].trim();
+        if !note.is_empty() {
+            notes.push(note);
+        }
+        rest = &rest[close + 1..];
+    }
+    if notes.is_empty() {
+        return None;
+    }
+    stripped.push_str(rest);
+    let stripped = stripped
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace(" ,", ",");
+    Some((stripped, notes.join("; ")))
+}
+
+/// Symbols marking a footnote (e.g. "flour*" or "butter†")
+const FOOTNOTE_MARKERS: &[char] = &['*', '†', '‡', '§', '¶'];
+
+fn is_superscript_digit(c: char) -> bool {
+    matches!(c, '⁰' | '¹' | '²' | '³' | '⁴'..='⁹')
+}
+
+/// Split the first footnote marker off a name, returning the name without it and the marker.
+///
+/// Markers are runs of symbols like "*" or "†", or superscript digits right after
+/// a letter (e.g. "flour²"), so that "flour*, sifted" becomes "flour, sifted" and "*".
+pub(crate) fn split_footnote(name: &str) -> Option<(String, &str)> {
+    let mut previous = None;
+    let start = name.char_indices().find_map(|(index, c)| {
+        let is_marker = FOOTNOTE_MARKERS.contains(&c)
+            || (is_superscript_digit(c) && previous.is_some_and(char::is_alphabetic));
+        previous = Some(c);
+        if is_marker {
+            Some(index)
+        } else {
+            None
+        }
+    })?;
+    let end = name[start..]
+        .char_indices()
+        .find(|(_, c)| !(FOOTNOTE_MARKERS.contains(c) || is_superscript_digit(*c)))
+        .map_or(name.len(), |(index, _)| start + index);
+    let rest = &name[end..];
+    let stripped = if rest.trim().is_empty() {
+        name[..start].trim_end().to_owned()
+    } else {
+        format!("{}{}", &name[..start], rest)
+    };
+    Some((stripped, &name[start..end]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_singular() {
+        assert_eq!(singular("potatoes"), "potato");
+        assert_eq!(part_name("Meyer lemons", "Juice"), "Meyer lemon juice");
+        assert_eq!(singular("olives"), "olive");
+        assert_eq!(singular("berries"), "berry");
+        assert_eq!(singular("glass"), "glass");
+        assert_eq!(singular("flour"), "flour");
+    }
+
+    #[test]
+    fn test_normalize_unicode() {
+        assert_eq!(
+            normalize_unicode("2 e\u{301}clairs", Normalization::Nfc),
+            "2 éclairs"
+        );
+        assert_eq!(
+            normalize_unicode("2 e\u{301}clairs", Normalization::None),
+            "2 e\u{301}clairs"
+        );
+        assert_eq!(
+            normalize_unicode("1½ cups \u{fb02}our", Normalization::Nfkc),
+            "1½ cups flour"
+        );
+        assert_eq!(normalize_unicode("२ cups", Normalization::Nfc), "2 cups");
+        assert_eq!(normalize_unicode("١٫٥ كوب", Normalization::Nfc), "1.5 كوب");
+        assert_eq!(
+            normalize_unicode("２\u{3000}cups", Normalization::Nfc),
+            "2 cups"
+        );
+        assert_eq!(
+            normalize_unicode("１／２\u{a0}cup", Normalization::Nfc),
+            "1/2 cup"
+        );
+        assert_eq!(
+            normalize_unicode("５００ｇ\u{3000}flour", Normalization::Nfc),
+            "500g flour"
+        );
+        assert_eq!(
+            normalize_unicode("２\u{3000}cups", Normalization::Nfkc),
+            "2 cups"
+        );
+        assert_eq!(normalize_unicode("२ cups", Normalization::None), "२ cups");
+    }
+
+    #[test]
+    fn test_normalize_numbers() {
+        let period = |input| normalize_numbers(input, DecimalSeparator::Period);
+        assert_eq!(period("1,000 g flour"), "1000 g flour");
+        assert_eq!(period("1,250,000 mg"), "1250000 mg");
+        assert_eq!(period("1,5 kg"), "1,5 kg");
+        assert_eq!(period("2, chopped"), "2, chopped");
+        assert_eq!(period("1.5 kg"), "1.5 kg");
+        let comma = |input| normalize_numbers(input, DecimalSeparator::Comma);
+        assert_eq!(comma("1,5 kg Mehl"), "1.5 kg Mehl");
+        assert_eq!(comma("1.000 g"), "1000 g");
+        assert_eq!(comma("1 000,5 g"), "1000.5 g");
+        assert_eq!(comma("0,25 l"), "0.25 l");
+    }
+
+    #[test]
+    fn test_find_unspecified_amount() {
+        assert_eq!(find_unspecified_amount("salt, to taste"), Some((6, 14)));
+        assert_eq!(find_unspecified_amount("Oil as needed"), Some((4, 13)));
+        assert_eq!(find_unspecified_amount("potatoes"), None);
+        assert_eq!(find_unspecified_amount("ask as neededly"), None);
+    }
+
+    #[test]
+    fn test_split_usage_note() {
+        assert_eq!(
+            split_usage_note("parsley, for garnish"),
+            Some(("parsley", "for garnish"))
+        );
+        assert_eq!(
+            split_usage_note("walnuts (Optional)."),
+            Some(("walnuts", "Optional"))
+        );
+        assert_eq!(split_usage_note("to taste"), Some(("", "to taste")));
+        assert_eq!(
+            split_usage_note("pepper to taste"),
+            Some(("pepper", "to taste"))
+        );
+        assert_eq!(split_usage_note("nonoptional"), None);
+    }
+
+    #[test]
+    fn test_split_note() {
+        assert_eq!(
+            split_note("Sazon seasoning blend (recommended: Goya) with rice"),
+            Some((
+                "Sazon seasoning blend with rice".into(),
+                "recommended: Goya".into()
+            ))
+        );
+        assert_eq!(
+            split_note("tomatoes (canned) (or fresh), diced"),
+            Some(("tomatoes, diced".into(), "canned; or fresh".into()))
+        );
+        assert_eq!(split_note("flour"), None);
+        assert_eq!(split_note("flour (sifted"), None);
+    }
+
+    #[test]
+    fn test_split_footnote() {
+        assert_eq!(split_footnote("flour*"), Some(("flour".into(), "*")));
+        assert_eq!(split_footnote("butter ††"), Some(("butter".into(), "††")));
+        assert_eq!(
+            split_footnote("flour*, sifted"),
+            Some(("flour, sifted".into(), "*"))
+        );
+        assert_eq!(split_footnote("sugar²"), Some(("sugar".into(), "²")));
+        assert_eq!(split_footnote("flour"), None);
+    }
+}
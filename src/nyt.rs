@@ -0,0 +1,85 @@
+//! Output in the columns of the NYT ingredient-phrase-tagger's labeled data
+
+use crate::Ingredient;
+use serde::{Deserialize, Serialize};
+
+/// An ingredient as a row of the NYT ingredient-phrase-tagger's training data
+/// (input, name, qty, range_end, unit, comment), for labeling pipelines and evaluation scripts
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize, Clone)]
+pub struct NytRow {
+    pub input: String,
+    pub name: String,
+    /// Amount of the first quantity, the low end of a range, or 0 without one
+    pub qty: f64,
+    /// High end of a range amount, 0 otherwise
+    pub range_end: f64,
+    /// Unit of the first quantity, spelled out (e.g. "fluid ounce")
+    pub unit: String,
+    /// Preparation, parenthesized remarks and usage note, separated by commas
+    pub comment: String,
+}
+
+impl Ingredient {
+    /// The ingredient as a row of NYT ingredient-phrase-tagger data.
+    ///
+    /// Preparation and remarks only fill `comment` when parsed with
+    /// `ParseOptions::split_preparation` and `ParseOptions::split_note`.
+    pub fn to_nyt_row(&self) -> NytRow {
+        let quantity = self
+            .quantities
+            .iter()
+            .find(|quantity| quantity.measures_ingredient());
+        let (qty, range_end) = match quantity.map(|quantity| quantity.amount.bounds()) {
+            Some((min, max)) if min < max => (min, max),
+            Some((value, _)) => (value, 0.),
+            None => (0., 0.),
+        };
+        NytRow {
+            input: self.raw.clone(),
+            name: self.ingredient.clone().unwrap_or_default(),
+            qty,
+            range_end,
+            unit: quantity
+                .and_then(|quantity| quantity.unit.as_ref())
+                .map(|unit| unit.to_string())
+                .unwrap_or_default(),
+            comment: [&self.preparation, &self.note, &self.usage_note]
+                .iter()
+                .filter_map(|text| text.as_deref())
+                .collect::<Vec<_>>()
+                .join(", "),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseOptions;
+
+    #[test]
+    fn test_nyt_row() {
+        let options = ParseOptions::new().split_preparation(true).split_note(true);
+        let input = "2-3 cups onions (about 2 large), finely chopped";
+        let row = Ingredient::parse_with(input, &options)
+            .unwrap()
+            .to_nyt_row();
+        assert_eq!(
+            row,
+            NytRow {
+                input: input.into(),
+                name: "onions".into(),
+                qty: 2.,
+                range_end: 3.,
+                unit: "cup".into(),
+                comment: "finely chopped, about 2 large".into(),
+            }
+        );
+        let row = Ingredient::parse("1 fl oz rum").unwrap().to_nyt_row();
+        assert_eq!((row.qty, row.range_end), (1., 0.));
+        assert_eq!(row.unit, "fluid ounce");
+        let row = Ingredient::parse("salt, to taste").unwrap().to_nyt_row();
+        assert_eq!((row.qty, row.unit.as_str()), (0., ""));
+        assert_eq!(row.comment, "to taste");
+    }
+}
@@ -0,0 +1,176 @@
+//! Correction of common OCR misreadings in scanned ingredient lines
+
+use crate::{Ingredient, IngreedyError};
+use serde::{Deserialize, Serialize};
+
+/// Correction applied to the input before parsing
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone)]
+pub struct OcrCorrection {
+    /// Byte offset of the corrected text in the original input
+    pub position: usize,
+    /// Text as scanned
+    pub original: String,
+    /// Text it was corrected to
+    pub corrected: String,
+}
+
+/// Digit a character is commonly misread from ("l" for 1, "O" for 0)
+fn confused_digit(c: char) -> Option<char> {
+    match c {
+        'l' | 'I' | '|' => Some('1'),
+        'O' | 'o' => Some('0'),
+        _ => None,
+    }
+}
+
+/// Characters that can sit next to a digit inside a number
+fn is_numeric(c: char) -> bool {
+    c.is_ascii_digit() || c == '/' || c == '.'
+}
+
+/// Correct a single whitespace-delimited token starting at byte `offset`
+fn correct_token(
+    token: &str,
+    offset: usize,
+    is_first: bool,
+    corrections: &mut Vec<OcrCorrection>,
+) -> String {
+    // "l cup" at the start of a line: a lone "l" can't be a liter without an amount before it
+    if is_first && matches!(token, "l" | "I" | "|") {
+        corrections.push(OcrCorrection {
+            position: offset,
+            original: token.into(),
+            corrected: "1".into(),
+        });
+        return "1".into();
+    }
+    let has_digit = token.chars().any(|c| c.is_ascii_digit());
+    let chars: Vec<(usize, char)> = token.char_indices().collect();
+    let mut corrected = String::with_capacity(token.len());
+    for (i, &(index, c)) in chars.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|i| chars[i].1);
+        let next = chars.get(i + 1).map(|&(_, c)| c);
+        let digit = confused_digit(c).filter(|_| {
+            // a misread digit sits inside a number, and not in a word like "1lb" or "10oz"
+            let in_number = previous.is_some_and(is_numeric) || next.is_some_and(is_numeric);
+            has_digit
+                && in_number
+                && previous.is_none_or(|c| is_numeric(c) || confused_digit(c).is_some())
+                && next.is_none_or(|c| is_numeric(c) || confused_digit(c).is_some())
+        });
+        match digit {
+            Some(digit) => {
+                corrections.push(OcrCorrection {
+                    position: offset + index,
+                    original: c.to_string(),
+                    corrected: digit.to_string(),
+                });
+                corrected.push(digit);
+            }
+            None => corrected.push(c),
+        }
+    }
+    // "½" read as "1/2." - a fraction can't end in a period
+    let is_fraction = |s: &str| {
+        s.split_once('/').is_some_and(|(numerator, denominator)| {
+            !numerator.is_empty()
+                && !denominator.is_empty()
+                && numerator.chars().all(|c| c.is_ascii_digit())
+                && denominator.chars().all(|c| c.is_ascii_digit())
+        })
+    };
+    if let Some(fraction) = corrected.strip_suffix('.').filter(|s| is_fraction(s)) {
+        corrections.push(OcrCorrection {
+            position: offset + token.len() - 1,
+            original: ".".into(),
+            corrected: String::new(),
+        });
+        corrected = fraction.to_owned();
+    }
+    corrected
+}
+
+/// Apply common OCR confusion corrections to a line,
+/// returning the corrected line and the corrections applied
+pub fn correct_ocr(input: &str) -> (String, Vec<OcrCorrection>) {
+    let mut corrections = Vec::new();
+    let mut output = String::with_capacity(input.len());
+    let mut token_start = None;
+    let mut is_first = true;
+    for (index, c) in input
+        .char_indices()
+        .chain(std::iter::once((input.len(), ' ')))
+    {
+        match (c.is_whitespace(), token_start) {
+            (true, Some(start)) => {
+                output.push_str(&correct_token(
+                    &input[start..index],
+                    start,
+                    is_first,
+                    &mut corrections,
+                ));
+                token_start = None;
+                is_first = false;
+            }
+            (false, None) => token_start = Some(index),
+            _ => {}
+        }
+        if c.is_whitespace() && index < input.len() {
+            output.push(c);
+        }
+    }
+    (output, corrections)
+}
+
+impl Ingredient {
+    /// Parse a line from a scanned source, first correcting common OCR misreadings
+    /// ("l cup" for "1 cup", "1O0 g" for "100 g", "1/2." for "½").
+    ///
+    /// Returns the corrections applied alongside the ingredient.
+    pub fn parse_ocr_tolerant(input: &str) -> Result<(Self, Vec<OcrCorrection>), IngreedyError> {
+        let (corrected, corrections) = correct_ocr(input);
+        Ok((Self::parse(&corrected)?, corrections))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Unit;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_correct_ocr() {
+        assert_eq!(correct_ocr("l cup flour").0, "1 cup flour");
+        assert_eq!(correct_ocr("1O0 g sugar").0, "100 g sugar");
+        assert_eq!(correct_ocr("l/2. cup milk").0, "1/2 cup milk");
+        assert_eq!(correct_ocr("2 I/2 cups  oil").0, "2 1/2 cups  oil");
+        assert_eq!(correct_ocr("1lb 10oz potatoes").0, "1lb 10oz potatoes");
+        assert_eq!(correct_ocr("1 l olive oil").0, "1 l olive oil");
+        assert_eq!(correct_ocr("1 cup flour").1, vec![]);
+    }
+
+    #[test]
+    fn test_correction_positions() {
+        let (_, corrections) = correct_ocr("1O0 g sugar");
+        assert_eq!(
+            corrections,
+            vec![OcrCorrection {
+                position: 1,
+                original: "O".into(),
+                corrected: "0".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_ocr_tolerant() {
+        let (ingredient, corrections) = Ingredient::parse_ocr_tolerant("l cup flour").unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
+        assert_eq!(ingredient.ingredient, Some("flour".to_string()));
+        assert_eq!(corrections.len(), 1);
+        let (ingredient, _) = Ingredient::parse_ocr_tolerant("1/2. cup milk").unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 0.5);
+    }
+}
@@ -0,0 +1,259 @@
+//! Toggles for how lines are parsed
+
+use crate::{
+    DecimalSeparator, Ingredient, IngreedyError, MeasurementSystem, ParserConfig, Unit,
+    UnitRegistry, UnitType,
+};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+lazy_static! {
+    /// Spellings of the count units, read as units with `ParseOptions::count_units`
+    pub(crate) static ref COUNT_UNITS: UnitRegistry = {
+        let mut registry = UnitRegistry::new();
+        for unit in Unit::all()
+            .iter()
+            .filter(|unit| unit.unit_type() == UnitType::Count)
+        {
+            for alias in unit.aliases() {
+                registry.insert(alias, unit.clone(), UnitType::Count);
+            }
+        }
+        registry
+    };
+}
+
+/// Parser behavior toggles, built with chained setters
+/// (e.g. `ParseOptions::new().count_units(true).strict(true)`).
+///
+/// Deserializes with every field optional.
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct ParseOptions {
+    /// Read count words after an amount as units ("2 cloves garlic" is 2 of `Unit::Clove`)
+    pub count_units: bool,
+    /// Drop the "of" between a quantity and the name ("2 cups of flour" is named "flour")
+    pub strip_of: bool,
+    /// Move text after the first comma of the name into `Ingredient::preparation`
+    /// ("onion, finely chopped" is named "onion")
+    pub split_preparation: bool,
+    /// Move parenthesized remarks in the name into `Ingredient::note`
+    /// ("Sazon seasoning (recommended: Goya)" is named "Sazon seasoning")
+    pub split_note: bool,
+    /// Decimal separator of amounts, and with it which characters separate thousands
+    pub decimal_separator: DecimalSeparator,
+    /// Which pints, quarts, gallons, fluid ounces and cups the lines mean
+    /// ("1 pint milk" is 568 ml with `MeasurementSystem::Imperial`)
+    pub measurement_system: MeasurementSystem,
+    /// Fail on lines without a quantity or an ingredient name instead of returning what was found
+    pub strict: bool,
+    /// Split names joined by "or" as well as "and" in `Ingredient::parse_many`
+    /// ("oil or butter" is two ingredients)
+    pub split_or: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            count_units: false,
+            strip_of: true,
+            split_preparation: false,
+            split_note: false,
+            decimal_separator: DecimalSeparator::default(),
+            measurement_system: MeasurementSystem::default(),
+            strict: false,
+            split_or: true,
+        }
+    }
+}
+
+impl ParseOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn count_units(self, count_units: bool) -> Self {
+        Self {
+            count_units,
+            ..self
+        }
+    }
+
+    pub fn strip_of(self, strip_of: bool) -> Self {
+        Self { strip_of, ..self }
+    }
+
+    pub fn split_preparation(self, split_preparation: bool) -> Self {
+        Self {
+            split_preparation,
+            ..self
+        }
+    }
+
+    pub fn split_note(self, split_note: bool) -> Self {
+        Self { split_note, ..self }
+    }
+
+    pub fn decimal_separator(self, decimal_separator: DecimalSeparator) -> Self {
+        Self {
+            decimal_separator,
+            ..self
+        }
+    }
+
+    pub fn measurement_system(self, measurement_system: MeasurementSystem) -> Self {
+        Self {
+            measurement_system,
+            ..self
+        }
+    }
+
+    pub fn strict(self, strict: bool) -> Self {
+        Self { strict, ..self }
+    }
+
+    pub fn split_or(self, split_or: bool) -> Self {
+        Self { split_or, ..self }
+    }
+}
+
+impl Ingredient {
+    /// Parse a single line of input with the given options
+    pub fn parse_with(input: &str, options: &ParseOptions) -> Result<Self, IngreedyError> {
+        Self::parse_with_config(
+            input,
+            &ParserConfig {
+                options: *options,
+                ..ParserConfig::default()
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        DecimalSeparator, Expected, Ingredient, IngreedyError, MeasurementSystem, ParseOptions,
+        Unit, UnitType,
+    };
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_count_units() {
+        let options = ParseOptions::new().count_units(true);
+        let ingredient = Ingredient::parse_with("2 cloves garlic", &options).unwrap();
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Clove));
+        assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::Count));
+        assert_eq!(ingredient.ingredient.as_deref(), Some("garlic"));
+        assert_eq!(ingredient.quantities[0].raw, "2 cloves");
+        let ingredient = Ingredient::parse_with("3 bunches of parsley", &options).unwrap();
+        assert_eq!(ingredient.ingredient.as_deref(), Some("parsley"));
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Bunch));
+        let ingredient = Ingredient::parse_with("4 pcs. chicken thighs", &options).unwrap();
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Piece));
+        let ingredient = Ingredient::parse("2 cloves garlic").unwrap();
+        assert_eq!(ingredient.quantities[0].unit, None);
+    }
+
+    #[test]
+    fn test_strip_of() {
+        let ingredient = Ingredient::parse("2 cups of flour").unwrap();
+        assert_eq!(ingredient.ingredient.as_deref(), Some("flour"));
+        let options = ParseOptions::new().strip_of(false);
+        let ingredient = Ingredient::parse_with("2 cups of flour", &options).unwrap();
+        assert_eq!(ingredient.ingredient.as_deref(), Some("of flour"));
+    }
+
+    #[test]
+    fn test_split_preparation() {
+        let input = "1 onion, finely chopped";
+        let ingredient = Ingredient::parse(input).unwrap();
+        assert_eq!(
+            ingredient.ingredient.as_deref(),
+            Some("onion, finely chopped")
+        );
+        let options = ParseOptions::new().split_preparation(true);
+        let ingredient = Ingredient::parse_with(input, &options).unwrap();
+        assert_eq!(ingredient.ingredient.as_deref(), Some("onion"));
+        assert_eq!(ingredient.preparation.as_deref(), Some("finely chopped"));
+        let ingredient = Ingredient::parse_with("parsley, chopped, to serve", &options).unwrap();
+        assert_eq!(ingredient.preparation.as_deref(), Some("chopped"));
+        assert_eq!(ingredient.usage_note.as_deref(), Some("to serve"));
+    }
+
+    #[test]
+    fn test_split_note() {
+        let input = "1 tablespoon Sazon seasoning blend (recommended: Goya)";
+        let ingredient = Ingredient::parse(input).unwrap();
+        assert_eq!(ingredient.note, None);
+        let options = ParseOptions::new().split_note(true).split_preparation(true);
+        let ingredient = Ingredient::parse_with(input, &options).unwrap();
+        assert_eq!(
+            ingredient.ingredient.as_deref(),
+            Some("Sazon seasoning blend")
+        );
+        assert_eq!(ingredient.note.as_deref(), Some("recommended: Goya"));
+        let ingredient =
+            Ingredient::parse_with("2 onions (about 1 lb), chopped", &options).unwrap();
+        assert_eq!(ingredient.ingredient.as_deref(), Some("onions"));
+        assert_eq!(ingredient.note.as_deref(), Some("about 1 lb"));
+        assert_eq!(ingredient.preparation.as_deref(), Some("chopped"));
+        let ingredient = Ingredient::parse_with("walnuts (optional)", &options).unwrap();
+        assert_eq!(ingredient.note, None);
+    }
+
+    #[test]
+    fn test_strict() {
+        let options = ParseOptions::new().strict(true);
+        assert!(Ingredient::parse_with("2 cups flour", &options).is_ok());
+        assert!(Ingredient::parse_with("salt, to taste", &options).is_ok());
+        assert!(matches!(
+            Ingredient::parse_with("flour", &options),
+            Err(IngreedyError::Incomplete(Expected::Quantity))
+        ));
+        assert!(matches!(
+            Ingredient::parse_with("2 cups", &options),
+            Err(IngreedyError::Incomplete(Expected::Ingredient))
+        ));
+        assert!(Ingredient::parse("flour").is_ok());
+    }
+
+    #[test]
+    fn test_decimal_comma() {
+        let options = ParseOptions::new().decimal_separator(DecimalSeparator::Comma);
+        let ingredient = Ingredient::parse_with("1,5 kg Mehl", &options).unwrap();
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.5);
+    }
+
+    #[test]
+    fn test_measurement_system() {
+        let options = ParseOptions::new().measurement_system(MeasurementSystem::Imperial);
+        let ingredient = Ingredient::parse_with("1 pint milk", &options).unwrap();
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Pint));
+        assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::Imperial));
+        let milliliters = ingredient.quantities[0].to_metric().unwrap();
+        assert_relative_eq!(milliliters.amount.value(), 568.26125);
+        let ingredient = Ingredient::parse_with("2 tbsp sugar", &options).unwrap();
+        assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
+
+        let ingredient = Ingredient::parse("1 pint milk").unwrap();
+        assert_eq!(ingredient.quantities[0].unit_type, Some(UnitType::English));
+        let milliliters = ingredient.quantities[0].to_metric().unwrap();
+        assert_relative_eq!(milliliters.amount.value(), 473.176473);
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let options: ParseOptions =
+            serde_json::from_str(r#"{"strict": true, "decimal_separator": "comma"}"#).unwrap();
+        assert_eq!(
+            options,
+            ParseOptions::new()
+                .strict(true)
+                .decimal_separator(DecimalSeparator::Comma)
+        );
+        let options: ParseOptions =
+            serde_json::from_str(r#"{"measurement_system": "imperial"}"#).unwrap();
+        assert_eq!(options.measurement_system, MeasurementSystem::Imperial);
+    }
+}
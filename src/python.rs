@@ -0,0 +1,23 @@
+//! Python bindings through PyO3
+
+use crate::Ingredient;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Parse an ingredient line into a dict with the same fields as the JSON output,
+/// raising `ValueError` if it can't be parsed
+#[pyfunction]
+fn parse(py: Python<'_>, input: &str) -> PyResult<PyObject> {
+    let ingredient =
+        Ingredient::parse(input).map_err(|error| PyValueError::new_err(error.to_string()))?;
+    let json = serde_json::to_string(&ingredient)
+        .map_err(|error| PyValueError::new_err(error.to_string()))?;
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+#[pymodule]
+#[pyo3(name = "ingreedy")]
+fn ingreedy(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(parse, module)?)?;
+    Ok(())
+}
@@ -0,0 +1,163 @@
+//! Exact rational amounts, kept alongside the `f64` amount with the `rational` feature
+
+use crate::{
+    number_value, rule_of, script_digit, unicode_fraction_value, word_fraction, Rule,
+    FRACTION_SLASHES,
+};
+use num_rational::Rational64;
+use num_traits::{CheckedAdd, CheckedMul};
+use pest::iterators::Pair;
+
+/// Largest denominator tried when recovering a fraction from a float
+const MAX_DENOMINATOR: i64 = 1000;
+
+/// Fraction with a small denominator equal to a float (e.g. 0.333… as 1/3), if there is one
+pub(crate) fn from_f64(value: f64) -> Option<Rational64> {
+    (1..=MAX_DENOMINATOR).find_map(|denominator| {
+        let numerator = (value * denominator as f64).round();
+        if (numerator / denominator as f64 - value).abs() <= value.abs().max(1.) * 1e-12 {
+            Some(Rational64::new(numerator as i64, denominator))
+        } else {
+            None
+        }
+    })
+}
+
+/// Nearest float to a fraction
+pub(crate) fn to_f64(exact: Rational64) -> f64 {
+    *exact.numer() as f64 / *exact.denom() as f64
+}
+
+/// Product of two exact amounts, `None` if either is missing or it overflows
+pub(crate) fn mul(a: Option<Rational64>, b: Option<Rational64>) -> Option<Rational64> {
+    a?.checked_mul(&b?)
+}
+
+/// Sum of two exact amounts, `None` if either is missing or it overflows
+pub(crate) fn add(a: Option<Rational64>, b: Option<Rational64>) -> Option<Rational64> {
+    a?.checked_add(&b?)
+}
+
+/// Exact amount multiplied by a float factor, if the factor is itself a simple fraction
+pub(crate) fn scale(exact: Option<Rational64>, factor: f64) -> Option<Rational64> {
+    mul(exact, from_f64(factor))
+}
+
+/// Parse a "numerator/denominator" fraction
+fn parse_fraction_str(fraction: &str) -> Option<Rational64> {
+    let mut parts = fraction.split(FRACTION_SLASHES).map(str::parse::<i64>);
+    let (numerator, denominator) = (parts.next()?.ok()?, parts.next()?.ok()?);
+    if denominator == 0 {
+        return None;
+    }
+    Some(Rational64::new(numerator, denominator))
+}
+
+fn exact_fraction(pair: &Pair<Rule>) -> Option<Rational64> {
+    match pair.as_rule() {
+        Rule::multicharacter_fraction => parse_fraction_str(pair.as_str()),
+        Rule::superscript_fraction => {
+            let digits = pair
+                .as_str()
+                .chars()
+                .map(|c| script_digit(c).unwrap_or('/'))
+                .collect::<String>();
+            parse_fraction_str(&digits)
+        }
+        Rule::unicode_fraction => from_f64(unicode_fraction_value(pair.as_str()).ok()?),
+        _ => None,
+    }
+}
+
+/// Exact value of the inner rule of an `amount` (e.g. 5/4 for "1 1/4" or "1.25")
+pub(crate) fn exact_amount(pair: &Pair<Rule>) -> Option<Rational64> {
+    match rule_of(pair) {
+        Rule::integer => Some(Rational64::from_integer(pair.as_str().parse().ok()?)),
+        Rule::float => {
+            let (whole, decimals) = pair.as_str().split_once('.')?;
+            let denominator = 10_i64.checked_pow(decimals.len() as u32)?;
+            let whole: i64 = if whole.is_empty() {
+                0
+            } else {
+                whole.parse().ok()?
+            };
+            let decimals: i64 = decimals.parse().ok()?;
+            Some(Rational64::new(
+                whole.checked_mul(denominator)?.checked_add(decimals)?,
+                denominator,
+            ))
+        }
+        Rule::fraction => exact_fraction(&pair.clone().into_inner().next()?),
+        Rule::mixed_number => pair
+            .clone()
+            .into_inner()
+            .filter(|pair| pair.as_rule() != Rule::separator)
+            .map(|pair| exact_amount(&pair))
+            .try_fold(Rational64::from_integer(0), |sum, part| {
+                add(Some(sum), part)
+            }),
+        Rule::number => from_f64(number_value(pair.clone().into_inner().next()?.as_str()).ok()?),
+        Rule::compound_number => from_f64(number_value(pair.as_str()).ok()?),
+        Rule::dozens => match pair.clone().into_inner().next() {
+            Some(count) => mul(
+                exact_amount(&count.into_inner().next()?),
+                Some(Rational64::from(12)),
+            ),
+            None => Some(Rational64::from(12)),
+        },
+        Rule::word_fraction => {
+            let (numerator, denominator) = word_fraction(pair.clone()).ok()?;
+            Some(Rational64::new(numerator, denominator))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Amount, Ingredient, Quantity, ShoppingList, Unit};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_exact_amount() {
+        let exact = |input| Quantity::parse_str(input).unwrap().exact_amount;
+        assert_eq!(exact("1/3 cup"), Some(Rational64::new(1, 3)));
+        assert_eq!(exact("1 1/4 cups"), Some(Rational64::new(5, 4)));
+        assert_eq!(exact("1.25 cups"), Some(Rational64::new(5, 4)));
+        assert_eq!(exact("two thirds cup"), Some(Rational64::new(2, 3)));
+        assert_eq!(exact("half a dozen"), Some(Rational64::from(6)));
+        assert_eq!(exact("⅔ cup"), Some(Rational64::new(2, 3)));
+        assert_eq!(exact("1∕3 cup"), Some(Rational64::new(1, 3)));
+        assert_eq!(exact("¹⁄₆ cup"), Some(Rational64::new(1, 6)));
+        assert_eq!(exact("2 (1/3 cup)"), Some(Rational64::new(2, 3)));
+        assert_eq!(exact("pinch"), Some(Rational64::from_integer(1)));
+    }
+
+    #[test]
+    fn test_exact_scale_and_convert() {
+        let ingredient = Ingredient::parse("1/3 cup sugar").unwrap().scale(3.);
+        assert_eq!(
+            ingredient.quantities[0].exact_amount,
+            Some(Rational64::from_integer(1))
+        );
+        assert_eq!(ingredient.quantities[0].amount, Amount::Exact(1.));
+
+        let quantity = Quantity::parse_str("1/3 cup").unwrap();
+        let tablespoons = quantity.convert_to(Unit::Tablespoon).unwrap();
+        assert_eq!(tablespoons.exact_amount, Some(Rational64::new(16, 3)));
+        let milliliters = quantity.convert_to(Unit::Milliliter).unwrap();
+        assert_eq!(milliliters.exact_amount, None);
+        assert_relative_eq!(milliliters.amount.value(), 78.86, epsilon = 0.01);
+
+        let mut list = ShoppingList::new();
+        list.extend(
+            ["1/3 cup sugar", "1/3 cup sugar", "1/3 cup sugar"]
+                .iter()
+                .map(|line| Ingredient::parse(line).unwrap()),
+        );
+        let sugar = &list.items()[0].quantities[0];
+        assert_eq!(sugar.exact_amount, Some(Rational64::from_integer(1)));
+        assert_eq!(sugar.amount, Amount::Exact(1.));
+    }
+}
@@ -0,0 +1,181 @@
+//! Parsing of multi-line ingredient blocks
+
+use crate::{Amount, Ingredient, IngreedyError, ParserConfig};
+use serde::{Deserialize, Serialize};
+
+/// A non-blank line of an ingredient block
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+// most lines are ingredients, so boxing them would only add allocations
+#[allow(clippy::large_enum_variant)]
+pub enum ParsedLine {
+    /// An ingredient line (e.g. "2 cups flour")
+    Ingredient(Ingredient),
+    /// A section header, without its trailing colon (e.g. "For the sauce")
+    Header(String),
+    /// A line that isn't an ingredient (e.g. "----")
+    Unparsed(String),
+    /// How much the recipe makes (e.g. "Serves 4" or "Makes 12 muffins")
+    Yield(Yield),
+}
+
+/// How much a recipe makes
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct Yield {
+    /// Number of servings or pieces (the lower end of a range like "4-6")
+    pub amount: f64,
+    /// What is made, if not servings (e.g. "muffins" in "Makes 12 muffins")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+}
+
+/// Ingredient block of a recipe
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+pub struct Recipe {
+    /// Non-blank lines in input order
+    pub lines: Vec<ParsedLine>,
+}
+
+/// Words a yield line starts with, before an optional colon
+const YIELD_PREFIXES: &[&str] = &["servings", "serves", "yields", "yield", "makes"];
+
+/// Words after a yield amount that just mean servings
+const SERVING_WORDS: &[&str] = &["servings", "serving", "people", "persons", "portions"];
+
+/// Yield of a line, if it is one ("Serves 4", "Yield: 6 servings", "Makes 12 muffins")
+pub(crate) fn parse_yield(line: &str) -> Option<Yield> {
+    let lower = line.to_ascii_lowercase();
+    let rest = YIELD_PREFIXES
+        .iter()
+        .find_map(|prefix| lower.strip_prefix(prefix))?
+        .trim_start_matches(|c: char| c == ':' || c.is_whitespace());
+    let (amount, unit) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let amount = amount.split(['-', '–']).next().unwrap_or_default();
+    let amount = Amount::parse(amount).ok()?.value();
+    let unit = unit.trim();
+    let unit = if unit.is_empty() || SERVING_WORDS.contains(&unit) {
+        None
+    } else {
+        Some(line[line.len() - unit.len()..].to_owned())
+    };
+    Some(Yield { amount, unit })
+}
+
+/// Section header text of a line, if it is one ("For the sauce:" or "SAUCE")
+pub(crate) fn header(line: &str) -> Option<&str> {
+    if let Some(header) = line.strip_suffix(':') {
+        return Some(header.trim_end());
+    }
+    let mut letters = line.chars().filter(|c| c.is_alphabetic()).peekable();
+    let is_shouted = letters.peek().is_some()
+        && letters.all(char::is_uppercase)
+        && !line.chars().any(|c| c.is_ascii_digit());
+    if is_shouted {
+        Some(line)
+    } else {
+        None
+    }
+}
+
+impl Recipe {
+    /// Parse a pasted block of ingredient lines, skipping blank lines
+    pub fn parse(text: &str) -> Self {
+        Self::parse_with_config(text, &ParserConfig::default())
+    }
+
+    /// Parse a pasted block of ingredient lines with the given configuration
+    pub fn parse_with_config(text: &str, config: &ParserConfig) -> Self {
+        let lines = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                if let Some(recipe_yield) = parse_yield(line) {
+                    return ParsedLine::Yield(recipe_yield);
+                }
+                if let Some(header) = header(line) {
+                    return ParsedLine::Header(header.to_owned());
+                }
+                match Ingredient::parse_with_config(line, config) {
+                    Ok(ingredient) if ingredient.ingredient.is_some() => {
+                        ParsedLine::Ingredient(ingredient)
+                    }
+                    _ => ParsedLine::Unparsed(line.to_owned()),
+                }
+            })
+            .collect();
+        Self { lines }
+    }
+
+    /// Ingredients of all sections, in input order
+    pub fn ingredients(&self) -> impl Iterator<Item = &Ingredient> {
+        self.lines.iter().filter_map(|line| match line {
+            ParsedLine::Ingredient(ingredient) => Some(ingredient),
+            _ => None,
+        })
+    }
+
+    /// Yield of the recipe, from its first yield line
+    pub fn recipe_yield(&self) -> Option<&Yield> {
+        self.lines.iter().find_map(|line| match line {
+            ParsedLine::Yield(recipe_yield) => Some(recipe_yield),
+            _ => None,
+        })
+    }
+
+    /// Scale the ingredients and yield so that the recipe makes the given number
+    /// of servings (or pieces, for a yield like "Makes 12 muffins")
+    pub fn scale_to_servings(&self, servings: f64) -> Result<Self, IngreedyError> {
+        match self.recipe_yield() {
+            Some(recipe_yield) if recipe_yield.amount > 0. => {
+                Ok(self.scale(servings / recipe_yield.amount))
+            }
+            _ => Err(IngreedyError::MissingYield),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Unit;
+
+    #[test]
+    fn test_recipe_parse() {
+        let recipe = Recipe::parse(
+            "2 cups flour\n\n  1 tsp salt  \nFor the sauce:\n1 cup milk\n----\nTOPPING\n50 g butter\n",
+        );
+        assert_eq!(recipe.lines.len(), 7);
+        assert!(
+            matches!(&recipe.lines[2], ParsedLine::Header(header) if header == "For the sauce")
+        );
+        assert!(matches!(&recipe.lines[4], ParsedLine::Unparsed(line) if line == "----"));
+        assert!(matches!(&recipe.lines[5], ParsedLine::Header(header) if header == "TOPPING"));
+        let ingredients: Vec<_> = recipe.ingredients().collect();
+        assert_eq!(ingredients.len(), 4);
+        assert_eq!(ingredients[1].quantities[0].unit, Some(Unit::Teaspoon));
+        assert_eq!(ingredients[1].ingredient, Some("salt".to_string()));
+        assert_eq!(ingredients[3].ingredient, Some("butter".to_string()));
+    }
+
+    #[test]
+    fn test_parse_yield() {
+        let serves = |amount| Some(Yield { amount, unit: None });
+        assert_eq!(parse_yield("Serves 4"), serves(4.));
+        assert_eq!(parse_yield("Servings: 4-6"), serves(4.));
+        assert_eq!(parse_yield("Yield: 6 servings"), serves(6.));
+        assert_eq!(parse_yield("serves two"), serves(2.));
+        assert_eq!(
+            parse_yield("Makes 12 Muffins"),
+            Some(Yield {
+                amount: 12.,
+                unit: Some("Muffins".into())
+            })
+        );
+        assert_eq!(parse_yield("2 servings cooked rice"), None);
+        assert_eq!(parse_yield("Makes enough for a crowd"), None);
+    }
+}
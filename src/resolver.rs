@@ -0,0 +1,165 @@
+//! Linking parsed ingredient names to entries of an ingredient database
+
+use crate::{ingredient_key, Ingredient};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Identifier of an ingredient in a database (e.g. a USDA FoodData Central FDC ID)
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Clone)]
+#[serde(transparent)]
+pub struct IngredientId(pub String);
+
+impl fmt::Display for IngredientId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<&str> for IngredientId {
+    fn from(id: &str) -> Self {
+        Self(id.to_owned())
+    }
+}
+
+impl From<String> for IngredientId {
+    fn from(id: String) -> Self {
+        Self(id)
+    }
+}
+
+impl From<u64> for IngredientId {
+    fn from(id: u64) -> Self {
+        Self(id.to_string())
+    }
+}
+
+/// Looks up the database entry of an ingredient name
+pub trait IngredientResolver {
+    /// Identifier of the ingredient with this name as parsed, if the database has it
+    fn resolve(&self, name: &str) -> Option<IngredientId>;
+}
+
+impl<F: Fn(&str) -> Option<IngredientId>> IngredientResolver for F {
+    fn resolve(&self, name: &str) -> Option<IngredientId> {
+        self(name)
+    }
+}
+
+/// In-memory ingredient database matching names exactly (ignoring case),
+/// then by their `ingredient_key` so "Diced Tomatoes" finds "tomato", and through aliases
+#[derive(Default, Debug, Clone)]
+pub struct IngredientCatalog {
+    names: HashMap<String, IngredientId>,
+    keys: HashMap<String, IngredientId>,
+}
+
+impl IngredientCatalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an ingredient under its name
+    pub fn insert(&mut self, name: &str, id: impl Into<IngredientId>) {
+        let id = id.into();
+        self.keys.insert(ingredient_key(name), id.clone());
+        self.names.insert(name.trim().to_lowercase(), id);
+    }
+
+    /// Add another name for an ingredient added earlier (e.g. "cilantro" for "coriander leaves"),
+    /// returning `false` if there is no ingredient of that name
+    pub fn insert_alias(&mut self, alias: &str, name: &str) -> bool {
+        match self.resolve(name) {
+            Some(id) => {
+                self.insert(alias, id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+impl IngredientResolver for IngredientCatalog {
+    fn resolve(&self, name: &str) -> Option<IngredientId> {
+        self.names
+            .get(&name.trim().to_lowercase())
+            .or_else(|| self.keys.get(&ingredient_key(name)))
+            .cloned()
+    }
+}
+
+impl Ingredient {
+    /// Set `Ingredient::id` to the resolver's identifier for the ingredient name
+    pub fn resolve(&mut self, resolver: &(impl IngredientResolver + ?Sized)) {
+        self.id = self
+            .ingredient
+            .as_deref()
+            .and_then(|name| resolver.resolve(name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParserContext;
+
+    fn catalog() -> IngredientCatalog {
+        let mut catalog = IngredientCatalog::new();
+        catalog.insert("coriander leaves", 169998);
+        catalog.insert("tomato", "tomato-raw");
+        assert!(catalog.insert_alias("cilantro", "Coriander Leaves"));
+        assert!(!catalog.insert_alias("garbanzo beans", "chickpeas"));
+        catalog
+    }
+
+    #[test]
+    fn test_catalog() {
+        let catalog = catalog();
+        assert_eq!(catalog.len(), 3);
+        assert_eq!(catalog.resolve("Cilantro"), Some(169998.into()));
+        assert_eq!(
+            catalog.resolve("tomatoes, diced"),
+            Some("tomato-raw".into())
+        );
+        assert_eq!(catalog.resolve("basil"), None);
+
+        let mut ingredient = Ingredient::parse("1 cup chopped cilantro").unwrap();
+        ingredient.resolve(&catalog);
+        assert_eq!(ingredient.id, Some(IngredientId("169998".into())));
+        let json = serde_json::to_value(&ingredient).unwrap();
+        assert_eq!(json["id"], "169998");
+    }
+
+    #[test]
+    fn test_context_resolver() {
+        let context = ParserContext::default().with_resolver(catalog());
+        assert_eq!(
+            context.parse("2 tomatoes").unwrap().id,
+            Some("tomato-raw".into())
+        );
+        let recipe = context.parse_recipe("For the salsa:\n2 tomatoes\n1 onion");
+        let ids: Vec<_> = recipe
+            .ingredients()
+            .map(|ingredient| &ingredient.id)
+            .collect();
+        assert_eq!(ids, [&Some("tomato-raw".into()), &None]);
+        let closure = |name: &str| (name == "onion").then(|| IngredientId::from(11282));
+        assert_eq!(
+            ParserContext::default()
+                .with_resolver(closure)
+                .parse("1 onion")
+                .unwrap()
+                .id,
+            Some("11282".into())
+        );
+    }
+}
@@ -0,0 +1,241 @@
+//! Scaling of ingredients and recipes (e.g. doubling or halving)
+
+use crate::{Amount, Ingredient, ParsedLine, Quantity, Recipe, Unit, UnitType, Yield};
+
+/// Denominators of the fractions used by `nice_fraction`, in order of preference
+const DENOMINATORS: &[u32] = &[2, 3, 4, 8];
+
+/// How far an amount may be from a fraction to be written as it
+const FRACTION_TOLERANCE: f64 = 0.01;
+
+/// Write an amount as a whole number and common fraction (e.g. 1.5 as "1 1/2"),
+/// falling back to at most two decimals if no half, third, quarter or eighth is close
+pub fn nice_fraction(amount: f64) -> String {
+    let whole = amount.floor();
+    let remainder = amount - whole;
+    for &denominator in DENOMINATORS {
+        let numerator = (remainder * f64::from(denominator)).round();
+        if (remainder - numerator / f64::from(denominator)).abs() >= FRACTION_TOLERANCE {
+            continue;
+        }
+        return match (whole as i64, numerator as u32) {
+            (whole, 0) => whole.to_string(),
+            (whole, numerator) if numerator == denominator => (whole + 1).to_string(),
+            (0, numerator) => format!("{}/{}", numerator, denominator),
+            (whole, numerator) => format!("{} {}/{}", whole, numerator, denominator),
+        };
+    }
+    decimal(amount)
+}
+
+/// Write an amount with at most two decimals (e.g. 1.5 as "1.5")
+pub(crate) fn decimal(amount: f64) -> String {
+    let decimal = format!("{:.2}", amount);
+    decimal
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_owned()
+}
+
+/// Spread a total amount in base units over the units of a multipart quantity
+/// (e.g. 48 oz over pounds and ounces as 3 lb), largest unit first
+fn redistribute(total: f64, units: &[(Unit, f64)]) -> Vec<Quantity> {
+    let mut remaining = total;
+    let mut quantities = Vec::new();
+    for (index, (unit, factor)) in units.iter().enumerate() {
+        let amount = if index + 1 == units.len() {
+            remaining / factor
+        } else {
+            (remaining / factor + FRACTION_TOLERANCE).floor()
+        };
+        remaining -= amount * factor;
+        if amount > FRACTION_TOLERANCE || (quantities.is_empty() && index + 1 == units.len()) {
+            let mut quantity = Quantity {
+                amount: Amount::Exact(amount),
+                ..Quantity::default()
+            };
+            quantity.set_unit(unit.clone());
+            quantities.push(quantity);
+        }
+    }
+    quantities
+}
+
+impl Quantity {
+    /// Multiply the amount by a factor; temperatures and durations are kept as they are
+    pub fn scale(&self, factor: f64) -> Self {
+        if !self.measures_ingredient() {
+            return self.clone();
+        }
+        #[cfg(feature = "rational")]
+        if let Some(exact) = crate::rational::scale(self.exact_amount, factor) {
+            return Self {
+                amount: self.amount.map(|_| crate::rational::to_f64(exact)),
+                exact_amount: Some(exact),
+                ..self.clone()
+            };
+        }
+        Self {
+            amount: self.amount * factor,
+            #[cfg(feature = "rational")]
+            exact_amount: None,
+            ..self.clone()
+        }
+    }
+
+    /// Amount as a whole number and common fraction (e.g. "1 1/2")
+    pub fn nice_amount(&self) -> String {
+        nice_fraction(self.amount.value())
+    }
+}
+
+impl Ingredient {
+    /// Multiply all quantity amounts by a factor (e.g. 2 to double a recipe).
+    ///
+    /// Multipart quantities of exact amounts in units of the same kind are carried over
+    /// between their parts, so "1lb 8oz" doubled is "3lb" rather than "2lb 16oz".
+    pub fn scale(&self, factor: f64) -> Self {
+        let units = self
+            .quantities
+            .iter()
+            .map(|quantity| Some((quantity.unit.clone()?, quantity.factor()?)))
+            .collect::<Option<Vec<_>>>()
+            .filter(|units| {
+                units.len() > 1
+                    && self.quantities.iter().all(|quantity| {
+                        matches!(quantity.amount, Amount::Exact(_))
+                            && quantity.measures_ingredient()
+                    })
+                    && units
+                        .windows(2)
+                        .all(|pair| pair[0].0.category() == pair[1].0.category())
+                    && units.windows(2).all(|pair| pair[0].1 > pair[1].1)
+            });
+        let quantities = match units {
+            Some(units) => {
+                let total: f64 = self
+                    .quantities
+                    .iter()
+                    .zip(&units)
+                    .map(|(quantity, (_, unit_factor))| quantity.amount.value() * unit_factor)
+                    .sum();
+                redistribute(total * factor, &units)
+                    .into_iter()
+                    .map(|mut quantity| {
+                        quantity.source = self.quantities[0].source;
+                        if self.quantities[0].unit_type == Some(UnitType::Imperial) {
+                            quantity.use_imperial();
+                        }
+                        quantity
+                    })
+                    .collect()
+            }
+            None => self
+                .quantities
+                .iter()
+                .map(|quantity| quantity.scale(factor))
+                .collect(),
+        };
+        let mut ingredient = Self {
+            quantities,
+            alternatives: self
+                .alternatives
+                .iter()
+                .map(|quantity| quantity.scale(factor))
+                .collect(),
+            ..self.clone()
+        };
+        ingredient.check_warnings();
+        ingredient
+    }
+}
+
+impl Recipe {
+    /// Multiply the quantities of all ingredients, and the yield, by a factor
+    pub fn scale(&self, factor: f64) -> Self {
+        let lines = self
+            .lines
+            .iter()
+            .map(|line| match line {
+                ParsedLine::Ingredient(ingredient) => {
+                    ParsedLine::Ingredient(ingredient.scale(factor))
+                }
+                ParsedLine::Yield(recipe_yield) => ParsedLine::Yield(Yield {
+                    amount: recipe_yield.amount * factor,
+                    ..recipe_yield.clone()
+                }),
+                line => line.clone(),
+            })
+            .collect();
+        Self { lines }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_nice_fraction() {
+        assert_eq!(nice_fraction(1.5), "1 1/2");
+        assert_eq!(nice_fraction(0.25), "1/4");
+        assert_eq!(nice_fraction(2. / 3.), "2/3");
+        assert_eq!(nice_fraction(3.), "3");
+        assert_eq!(nice_fraction(1.999), "2");
+        assert_eq!(nice_fraction(0.375), "3/8");
+        assert_eq!(nice_fraction(1.15), "1.15");
+    }
+
+    #[test]
+    fn test_scale() {
+        let ingredient = Ingredient::parse("1 1/2 cups flour").unwrap().scale(2.);
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 3.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
+        assert_eq!(ingredient.ingredient, Some("flour".to_string()));
+
+        let ingredient = Ingredient::parse("3 eggs").unwrap().scale(0.5);
+        assert_eq!(ingredient.quantities[0].nice_amount(), "1 1/2");
+    }
+
+    #[test]
+    fn test_scale_multipart() {
+        let ingredient = Ingredient::parse("1lb 8oz potatoes").unwrap().scale(2.);
+        assert_eq!(ingredient.quantities.len(), 1);
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 3.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Pound));
+
+        let ingredient = Ingredient::parse("2lb 4oz potatoes").unwrap().scale(0.5);
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 1.);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Pound));
+        assert_relative_eq!(ingredient.quantities[1].amount.value(), 2., epsilon = 1e-9);
+        assert_eq!(ingredient.quantities[1].unit, Some(Unit::Ounce));
+
+        let ingredient = Ingredient::parse("2lb 4oz potatoes").unwrap().scale(0.25);
+        assert_eq!(ingredient.quantities.len(), 1);
+        assert_relative_eq!(ingredient.quantities[0].amount.value(), 9., epsilon = 1e-9);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Ounce));
+    }
+
+    #[test]
+    fn test_scale_recipe() {
+        let recipe = Recipe::parse("For the dough:\n2 cups flour\n1 tsp salt").scale(3.);
+        assert!(matches!(&recipe.lines[0], ParsedLine::Header(_)));
+        let ingredients: Vec<_> = recipe.ingredients().collect();
+        assert_relative_eq!(ingredients[0].quantities[0].amount.value(), 6.);
+        assert_relative_eq!(ingredients[1].quantities[0].amount.value(), 3.);
+    }
+
+    #[test]
+    fn test_scale_to_servings() {
+        let recipe = Recipe::parse("Serves 4\n2 cups flour\n2 eggs")
+            .scale_to_servings(6.)
+            .unwrap();
+        assert_relative_eq!(recipe.recipe_yield().unwrap().amount, 6.);
+        let ingredients: Vec<_> = recipe.ingredients().collect();
+        assert_relative_eq!(ingredients[0].quantities[0].amount.value(), 3.);
+        assert_relative_eq!(ingredients[1].quantities[0].amount.value(), 3.);
+        assert_eq!(recipe.to_string(), "Serves 6\n3 cups flour\n3 eggs");
+        assert!(Recipe::parse("2 cups flour").scale_to_servings(6.).is_err());
+    }
+}
@@ -0,0 +1,70 @@
+//! JSON Schema of the parser's output, for validating it in other languages
+
+use crate::{Ingredient, Recipe, UnitType, SCHEMA_VERSION};
+use schemars::{json_schema, schema_for, JsonSchema, Schema, SchemaGenerator};
+use std::borrow::Cow;
+
+impl JsonSchema for UnitType {
+    fn schema_name() -> Cow<'static, str> {
+        "UnitType".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        let known = [
+            UnitType::English,
+            UnitType::Metric,
+            UnitType::Imprecise,
+            UnitType::Container,
+            UnitType::Temperature,
+            UnitType::Time,
+            UnitType::Imperial,
+            UnitType::Traditional,
+            UnitType::Count,
+        ]
+        .map(String::from);
+        json_schema!({
+            "description": "System of unit used for a quantity; names other than these come from newer versions",
+            "type": "string",
+            "examples": known,
+        })
+    }
+}
+
+/// Schema of a type, marked with `SCHEMA_VERSION` under "version"
+fn versioned<T: JsonSchema>() -> Schema {
+    let mut schema = schema_for!(T);
+    schema.insert("version".into(), SCHEMA_VERSION.into());
+    schema
+}
+
+/// JSON Schema of a serialized `Ingredient`, as printed by the CLI
+pub fn json_schema() -> Schema {
+    versioned::<Ingredient>()
+}
+
+/// JSON Schema of a serialized `Recipe`
+pub fn recipe_json_schema() -> Schema {
+    versioned::<Recipe>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_schema() {
+        let schema = serde_json::to_value(json_schema()).unwrap();
+        assert_eq!(schema["title"], "Ingredient");
+        let properties = &schema["properties"];
+        assert!(properties["quantities"].is_object());
+        assert!(properties["ingredient"].is_object());
+        let quantity = &schema["$defs"]["Quantity"]["properties"];
+        assert!(quantity["amount"].is_object());
+        assert!(quantity.get("exact_amount").is_none());
+        assert_eq!(schema["$defs"]["Unit"]["type"], "string");
+        assert_eq!(schema["version"], SCHEMA_VERSION);
+        assert_eq!(schema["$defs"]["UnitType"]["examples"][1], "metric");
+        let recipe = serde_json::to_value(recipe_json_schema()).unwrap();
+        assert!(recipe["$defs"]["ParsedLine"].is_object());
+    }
+}
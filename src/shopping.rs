@@ -0,0 +1,151 @@
+//! Aggregation of ingredients into a shopping list
+
+use crate::{clean_ingredient_name, ingredient_key, Ingredient, Quantity};
+use serde::{Deserialize, Serialize};
+
+/// An ingredient on a shopping list with its summed quantities
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShoppingItem {
+    /// Ingredient name as first added (cleaned of descriptors like "chopped")
+    pub name: String,
+    /// Summed quantities, one per group of units that can't be converted into each other
+    /// (e.g. "2 cups" and "3" eggs stay separate)
+    pub quantities: Vec<Quantity>,
+}
+
+/// Ingredients merged by name, in the order they were first added
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+pub struct ShoppingList {
+    items: Vec<ShoppingItem>,
+}
+
+/// Add a quantity to the first quantity it has the same unit as or converts to
+fn add_quantity(quantities: &mut Vec<Quantity>, quantity: Quantity) {
+    for existing in quantities.iter_mut() {
+        let converted = match (&existing.unit, &quantity.unit) {
+            (Some(existing_unit), Some(unit)) if existing_unit == unit => Some(quantity.clone()),
+            (None, None) => Some(quantity.clone()),
+            (Some(existing_unit), Some(_)) => quantity.convert_to(existing_unit.clone()).ok(),
+            _ => None,
+        };
+        if let Some(converted) = converted {
+            existing.amount = existing.amount + converted.amount;
+            #[cfg(feature = "rational")]
+            {
+                existing.exact_amount =
+                    crate::rational::add(existing.exact_amount, converted.exact_amount);
+                if let Some(exact) = existing.exact_amount {
+                    existing.amount = existing.amount.map(|_| crate::rational::to_f64(exact));
+                }
+            }
+            return;
+        }
+    }
+    quantities.push(quantity);
+}
+
+impl ShoppingList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an ingredient, merging it with an earlier one of the same name
+    /// and converting between units where possible (e.g. "1 cup milk" and "250 ml milk").
+    ///
+    /// Ingredients without a name are ignored, as are temperatures and durations
+    /// (e.g. "water (110°F)" or "dough, proofed 2 hours").
+    pub fn add(&mut self, ingredient: Ingredient) {
+        let name = match &ingredient.ingredient {
+            Some(name) => name,
+            None => return,
+        };
+        let key = ingredient_key(name);
+        let index = match self
+            .items
+            .iter()
+            .position(|item| ingredient_key(&item.name) == key)
+        {
+            Some(index) => index,
+            None => {
+                self.items.push(ShoppingItem {
+                    name: clean_ingredient_name(name),
+                    quantities: Vec::new(),
+                });
+                self.items.len() - 1
+            }
+        };
+        for quantity in ingredient.quantities {
+            if !quantity.measures_ingredient() {
+                continue;
+            }
+            add_quantity(&mut self.items[index].quantities, quantity);
+        }
+    }
+
+    /// Items in the order they were first added
+    pub fn items(&self) -> &[ShoppingItem] {
+        &self.items
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl Extend<Ingredient> for ShoppingList {
+    fn extend<T: IntoIterator<Item = Ingredient>>(&mut self, ingredients: T) {
+        for ingredient in ingredients {
+            self.add(ingredient);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Unit;
+    use approx::assert_relative_eq;
+
+    fn list(lines: &[&str]) -> ShoppingList {
+        let mut list = ShoppingList::new();
+        list.extend(lines.iter().map(|line| Ingredient::parse(line).unwrap()));
+        list
+    }
+
+    #[test]
+    fn test_shopping_list() {
+        let list = list(&[
+            "1 cup milk",
+            "2 potatoes",
+            "250 ml milk",
+            "1 chopped potato",
+            "3 eggs",
+            "2 cups flour",
+            "100 g flour",
+        ]);
+        assert_eq!(list.len(), 4);
+        let milk = &list.items()[0];
+        assert_eq!(milk.name, "milk");
+        assert_eq!(milk.quantities.len(), 1);
+        assert_eq!(milk.quantities[0].unit, Some(Unit::Cup));
+        assert_relative_eq!(milk.quantities[0].amount.value(), 2.056_7, epsilon = 1e-4);
+        let potatoes = &list.items()[1];
+        assert_eq!(potatoes.name, "potatoes");
+        assert_relative_eq!(potatoes.quantities[0].amount.value(), 3.);
+        let flour = &list.items()[3];
+        assert_eq!(flour.quantities.len(), 2);
+    }
+
+    #[test]
+    fn test_shopping_list_multipart() {
+        let list = list(&["2lb 4oz potatoes", "12 oz potatoes"]);
+        let potatoes = &list.items()[0];
+        assert_eq!(potatoes.quantities.len(), 1);
+        assert_eq!(potatoes.quantities[0].unit, Some(Unit::Pound));
+        assert_relative_eq!(potatoes.quantities[0].amount.value(), 3., epsilon = 1e-9);
+    }
+}
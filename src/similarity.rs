@@ -0,0 +1,89 @@
+//! Scoring how alike two parsed ingredients are, for deduplicating recipes
+
+use crate::{ingredient_key, Ingredient, Quantity};
+use std::collections::HashSet;
+
+/// Share of the score given to the ingredient names; the rest goes to the quantities
+const NAME_WEIGHT: f32 = 0.75;
+
+/// Score of quantities that can't be compared because one side has none
+const UNKNOWN_QUANTITY_SCORE: f32 = 0.5;
+
+/// Overlap of the normalized name words (Jaccard index), 1 for identical names
+fn name_similarity(a: Option<&str>, b: Option<&str>) -> f32 {
+    let (a, b) = match (a, b) {
+        (Some(a), Some(b)) => (ingredient_key(a), ingredient_key(b)),
+        (None, None) => return 1.,
+        _ => return 0.,
+    };
+    if a == b {
+        return 1.;
+    }
+    let a: HashSet<_> = a.split_whitespace().collect();
+    let b: HashSet<_> = b.split_whitespace().collect();
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.;
+    }
+    a.intersection(&b).count() as f32 / union as f32
+}
+
+/// Ratio of the smaller to the larger amount, after converting to a common unit;
+/// 0 for quantities of different kinds (e.g. "2 cups" and "3" eggs)
+fn quantity_similarity(a: &Quantity, b: &Quantity) -> f32 {
+    let b_amount = match (&a.unit, &b.unit) {
+        (None, None) => b.amount.value(),
+        (Some(a_unit), Some(b_unit)) if a_unit == b_unit => b.amount.value(),
+        (Some(a_unit), Some(_)) => match b.convert_to(a_unit.clone()) {
+            Ok(converted) => converted.amount.value(),
+            Err(_) => return 0.,
+        },
+        _ => return 0.,
+    };
+    let a_amount = a.amount.value();
+    let (min, max) = (a_amount.min(b_amount), a_amount.max(b_amount));
+    if max <= 0. {
+        1.
+    } else {
+        (min / max) as f32
+    }
+}
+
+impl Ingredient {
+    /// Score from 0 to 1 of how likely two parsed lines refer to the same thing,
+    /// combining the overlap of their normalized names with how compatible
+    /// their first quantities are (e.g. "1 cup milk" and "250 ml whole milk" score high)
+    pub fn similarity(&self, other: &Self) -> f32 {
+        let names = name_similarity(self.ingredient.as_deref(), other.ingredient.as_deref());
+        let quantities = match (self.quantities.first(), other.quantities.first()) {
+            (Some(a), Some(b)) => quantity_similarity(a, b),
+            (None, None) => 1.,
+            _ => UNKNOWN_QUANTITY_SCORE,
+        };
+        NAME_WEIGHT * names + (1. - NAME_WEIGHT) * quantities
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Ingredient;
+    use approx::assert_relative_eq;
+
+    fn similarity(a: &str, b: &str) -> f32 {
+        Ingredient::parse(a)
+            .unwrap()
+            .similarity(&Ingredient::parse(b).unwrap())
+    }
+
+    #[test]
+    fn test_similarity() {
+        assert_relative_eq!(similarity("2 cups flour", "2 cups flour"), 1.);
+        assert_relative_eq!(similarity("2 Chopped Onions", "2 onion"), 1.);
+        assert!(similarity("1 cup milk", "250 ml whole milk") > 0.6);
+        assert!(similarity("1 cup milk", "250 ml milk") > 0.95);
+        assert_relative_eq!(similarity("2 cups flour", "3 eggs"), 0.);
+        assert!(similarity("2 cups flour", "1 cup flour") > similarity("2 cups flour", "3 flour"));
+        assert_relative_eq!(similarity("salt", "salt"), 1.);
+        assert_relative_eq!(similarity("salt", "1 tsp salt"), 0.875);
+    }
+}
@@ -0,0 +1,233 @@
+//! Byte offsets of parsed components in the input
+
+use crate::{
+    parser::IngredientParser, rule_of, temperature, Ingredient, IngreedyError, Locale,
+    ParseOptions, Quantity, Rule, Unit,
+};
+use pest::iterators::Pair;
+use pest::Parser;
+use serde::{Deserialize, Serialize};
+
+/// Byte range of a parsed component in the input
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone, Copy)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// Text of the span in the input it was parsed from
+    pub fn as_str<'a>(&self, input: &'a str) -> &'a str {
+        &input[self.start..self.end]
+    }
+}
+
+impl From<pest::Span<'_>> for Span {
+    fn from(span: pest::Span<'_>) -> Self {
+        Self {
+            start: span.start(),
+            end: span.end(),
+        }
+    }
+}
+
+/// Spans of a quantity and its parts
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone, Copy)]
+pub struct QuantitySpan {
+    /// The whole quantity (e.g. "2 (28 ounce)")
+    pub quantity: Span,
+    /// The amount (e.g. "2"), absent for imprecise units like "pinch"
+    pub amount: Option<Span>,
+    /// The unit (e.g. "ounce")
+    pub unit: Option<Span>,
+}
+
+impl QuantitySpan {
+    /// Spans of a `quantity_fragment` pair, taking its first amount and unit
+    pub(crate) fn from_pair(pair: &Pair<Rule>) -> Self {
+        let find = |rules: &[Rule]| {
+            pair.clone()
+                .into_inner()
+                .flatten()
+                .find(|pair| rules.contains(&rule_of(pair)))
+                .map(|pair| Span::from(pair.as_span()))
+        };
+        Self {
+            quantity: pair.as_span().into(),
+            amount: find(&[Rule::amount]),
+            unit: find(&[Rule::unit, Rule::imprecise_unit]),
+        }
+    }
+
+    /// The same spans moved by an offset, for spans found in part of the input
+    pub(crate) fn shift(self, offset: usize) -> Self {
+        let shift = |span: Span| Span {
+            start: span.start + offset,
+            end: span.end + offset,
+        };
+        Self {
+            quantity: shift(self.quantity),
+            amount: self.amount.map(shift),
+            unit: self.unit.map(shift),
+        }
+    }
+
+    /// Spans of a bare amount merged into the quantity after it ("2 1 cup")
+    pub(crate) fn merge(self, next: Self) -> Self {
+        let cover = |a: Span, b: Span| Span {
+            start: a.start.min(b.start),
+            end: a.end.max(b.end),
+        };
+        Self {
+            quantity: cover(self.quantity, next.quantity),
+            amount: match (self.amount, next.amount) {
+                (Some(a), Some(b)) => Some(cover(a, b)),
+                (a, b) => a.or(b),
+            },
+            unit: next.unit,
+        }
+    }
+}
+
+/// Ingredient with the byte offsets of its components in the input
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+pub struct SpannedIngredient {
+    pub ingredient: Ingredient,
+    /// Spans of each of `ingredient.quantities`, in the same order
+    pub quantities: Vec<QuantitySpan>,
+    /// Span of the ingredient name
+    pub name: Option<Span>,
+}
+
+impl Ingredient {
+    /// Parse a single line of input, keeping the byte offsets of the quantities,
+    /// units and ingredient name (e.g. to highlight them in an editor).
+    ///
+    /// The input isn't Unicode normalized, so that offsets refer to it unchanged.
+    pub fn parse_with_spans(input: &str) -> Result<SpannedIngredient, IngreedyError> {
+        Self::parse_spanned(
+            IngredientParser::parse(Locale::En.ingredient_rule(), input)?,
+            &ParseOptions::default(),
+        )
+    }
+}
+
+/// Find every quantity with a unit in free text, such as a recipe's instructions
+/// (e.g. "2 tbsp" and "a pinch" in "add 2 tbsp oil and a pinch of salt").
+///
+/// Quantities are matched with the ingredient grammar at each word start, so temperatures
+/// and durations ("bake at 180C for 20 minutes") are found too. Bare numbers are skipped,
+/// and the text isn't Unicode normalized, so that spans refer to it unchanged.
+pub fn find_quantities(text: &str) -> Vec<(Span, Quantity)> {
+    scan_quantities(text)
+        .into_iter()
+        .map(|(span, quantity)| (span.quantity, quantity))
+        .collect()
+}
+
+/// Quantities with a unit in free text, with the spans of their parts
+pub(crate) fn scan_quantities(text: &str) -> Vec<(QuantitySpan, Quantity)> {
+    let mut found = Vec::new();
+    let mut previous = None;
+    let mut next_start = 0;
+    for (start, c) in text.char_indices() {
+        let word_start = !previous.is_some_and(char::is_alphanumeric);
+        previous = Some(c);
+        if start < next_start || !word_start || c.is_whitespace() {
+            continue;
+        }
+        let pair = match IngredientParser::parse(Rule::quantity_fragment, &text[start..]) {
+            Ok(mut pairs) => match pairs.next() {
+                Some(pair) => pair,
+                None => continue,
+            },
+            Err(_) => continue,
+        };
+        let end = start + pair.as_str().trim_end().len();
+        if text[end..].starts_with(char::is_alphanumeric) {
+            continue;
+        }
+        let span = QuantitySpan::from_pair(&pair).shift(start);
+        if let Ok(mut quantity) = Quantity::parse_fragment(pair) {
+            // A bare "C" is a cup before a name, but a temperature in "bake at 180C for 20 minutes"
+            let bare_c = span.unit.is_some_and(|unit| unit.as_str(text) == "C");
+            if bare_c
+                && temperature::joining_word_start(&text[..start], temperature::JOINING_WORDS)
+                    .is_some()
+            {
+                quantity.set_unit(Unit::Celsius);
+            }
+            if quantity.unit.is_some() {
+                found.push((
+                    QuantitySpan {
+                        quantity: Span { start, end },
+                        ..span
+                    },
+                    quantity,
+                ));
+                next_start = end;
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Amount, Unit};
+
+    #[test]
+    fn test_parse_with_spans() {
+        let input = "2 (28 ounce) cans of tomatoes";
+        let spanned = Ingredient::parse_with_spans(input).unwrap();
+        let quantity = spanned.quantities[0];
+        assert_eq!(quantity.quantity.as_str(input), "2 (28 ounce)");
+        assert_eq!(quantity.amount.unwrap().as_str(input), "2");
+        assert_eq!(quantity.unit.unwrap().as_str(input), "ounce");
+        assert_eq!(spanned.name.unwrap().as_str(input), "cans of tomatoes");
+
+        let input = "2lb 4oz of potatoes";
+        let spanned = Ingredient::parse_with_spans(input).unwrap();
+        assert_eq!(spanned.quantities.len(), 2);
+        assert_eq!(spanned.quantities[1].unit.unwrap().as_str(input), "oz");
+        assert_eq!(spanned.name.unwrap().as_str(input), "potatoes");
+
+        let input = "pinch salt";
+        let spanned = Ingredient::parse_with_spans(input).unwrap();
+        assert_eq!(spanned.quantities[0].amount, None);
+        assert_eq!(spanned.quantities[0].unit.unwrap().as_str(input), "pinch");
+    }
+
+    #[test]
+    fn test_find_quantities() {
+        let text = "Bake at 180C for 20 minutes, then add 2 tbsp oil, about 1-2 cups of stock \
+                    and a pinch of salt.";
+        let found: Vec<_> = find_quantities(text)
+            .into_iter()
+            .map(|(span, quantity)| (span.as_str(text), quantity))
+            .collect();
+        let spans: Vec<_> = found.iter().map(|(span, _)| *span).collect();
+        assert_eq!(
+            spans,
+            ["180C", "20 minutes", "2 tbsp", "about 1-2 cups", "a pinch"]
+        );
+        assert_eq!(found[0].1.unit, Some(Unit::Celsius));
+        assert_eq!(found[1].1.unit, Some(Unit::Minute));
+        assert_eq!(found[2].1.amount, Amount::Exact(2.));
+        assert_eq!(found[2].1.unit, Some(Unit::Tablespoon));
+        assert_eq!(
+            found[3].1.amount,
+            Amount::Range {
+                min: 1.,
+                max: 2.,
+                approximate: true
+            }
+        );
+        assert_eq!(found[3].1.unit, Some(Unit::Cup));
+        assert_eq!(found[4].1.unit, Some(Unit::Pinch));
+
+        assert!(find_quantities("Stir until smooth").is_empty());
+        assert!(find_quantities("Serves 4").is_empty());
+    }
+}
@@ -0,0 +1,169 @@
+//! Splitting lines that list several ingredients ("salt and pepper to taste")
+
+use crate::{Ingredient, IngreedyError, ParseOptions};
+
+/// Names of single ingredients with "and" or "or" in them
+const COMPOUND_NAMES: &[&str] = &[
+    "half and half",
+    "macaroni and cheese",
+    "mac and cheese",
+    "sweet and sour",
+    "salt and vinegar",
+    "cookies and cream",
+    "fish and chips",
+    "bread and butter",
+    "peanut butter and jelly",
+];
+
+fn is_conjunction(word: &str, split_or: bool) -> bool {
+    word.eq_ignore_ascii_case("and") || word == "&" || (split_or && word.eq_ignore_ascii_case("or"))
+}
+
+/// Names of the ingredients listed in a name (e.g. "salt", "pepper" and "paprika" in
+/// "salt, pepper and paprika"), or just the name if it doesn't list several
+pub(crate) fn split_names(name: &str, split_or: bool) -> Vec<String> {
+    let lowercase = name.to_lowercase();
+    if COMPOUND_NAMES
+        .iter()
+        .any(|compound| lowercase.contains(compound))
+    {
+        return vec![name.to_owned()];
+    }
+    let mut parts = vec![Vec::new()];
+    for word in name.split_whitespace() {
+        if is_conjunction(word, split_or) {
+            parts.push(Vec::new());
+        } else if let Some(part) = parts.last_mut() {
+            part.push(word);
+        }
+    }
+    let last = parts.pop().unwrap_or_default().join(" ");
+    // commas only separate names before the last conjunction ("salt, pepper, and paprika")
+    let mut names: Vec<String> = parts
+        .iter()
+        .flat_map(|part| {
+            part.join(" ")
+                .split(',')
+                .map(str::to_owned)
+                .collect::<Vec<_>>()
+        })
+        .chain(std::iter::once(last))
+        .map(|name| name.trim().to_owned())
+        .filter(|name| !name.is_empty())
+        .collect();
+    if names.len() < 2 {
+        names = vec![name.to_owned()];
+    }
+    names
+}
+
+impl Ingredient {
+    /// Parse a line that may list several ingredients into one `Ingredient` each
+    /// ("salt and pepper to taste" gives "salt" and "pepper").
+    ///
+    /// Names joined by "and", "&" or, with `ParseOptions::split_or`, "or" are split.
+    /// A part starting with its own amount ("1 cup milk" in "2 eggs and 1 cup milk") is parsed
+    /// on its own; the other parts share the quantities of the line, so "2 cups flour and sugar"
+    /// gives 2 cups of each, and adding them up counts 4 cups. Every ingredient keeps the
+    /// usage note and preparation of the line.
+    pub fn parse_many(input: &str) -> Result<Vec<Self>, IngreedyError> {
+        Self::parse_many_with(input, &ParseOptions::default())
+    }
+
+    /// Parse a line that may list several ingredients like `parse_many`, with the given options
+    pub fn parse_many_with(
+        input: &str,
+        options: &ParseOptions,
+    ) -> Result<Vec<Self>, IngreedyError> {
+        let ingredient = Self::parse_with(input, options)?;
+        let names = match &ingredient.ingredient {
+            Some(name) => split_names(name, options.split_or),
+            None => return Ok(vec![ingredient]),
+        };
+        Ok(names
+            .into_iter()
+            .map(|name| match Self::parse_with(&name, options) {
+                Ok(part)
+                    if part
+                        .quantities
+                        .first()
+                        .is_some_and(|quantity| name.starts_with(quantity.raw.as_str())) =>
+                {
+                    Self {
+                        quantities: part.quantities,
+                        alternatives: part.alternatives,
+                        ingredient: part.ingredient,
+                        size: part.size,
+                        warnings: part.warnings,
+                        ..ingredient.clone()
+                    }
+                }
+                _ => Self {
+                    ingredient: Some(name),
+                    ..ingredient.clone()
+                },
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Unit;
+
+    #[test]
+    fn test_split_names() {
+        assert_eq!(split_names("salt and pepper", true), ["salt", "pepper"]);
+        assert_eq!(
+            split_names("salt, pepper, and paprika", true),
+            ["salt", "pepper", "paprika"]
+        );
+        assert_eq!(split_names("oil or butter", true), ["oil", "butter"]);
+        assert_eq!(split_names("oil or butter", false), ["oil or butter"]);
+        assert_eq!(
+            split_names("Macaroni and Cheese", true),
+            ["Macaroni and Cheese"]
+        );
+        assert_eq!(split_names("onion, chopped", true), ["onion, chopped"]);
+        assert_eq!(split_names("and pepper", true), ["and pepper"]);
+    }
+
+    #[test]
+    fn test_parse_many() {
+        let ingredients =
+            Ingredient::parse_many("salt and freshly ground pepper to taste").unwrap();
+        assert_eq!(ingredients.len(), 2);
+        assert_eq!(ingredients[0].ingredient.as_deref(), Some("salt"));
+        assert_eq!(
+            ingredients[1].ingredient.as_deref(),
+            Some("freshly ground pepper")
+        );
+        assert_eq!(ingredients[1].usage_note.as_deref(), Some("to taste"));
+        assert_eq!(ingredients[1].quantities[0].raw, "to taste");
+
+        let ingredients = Ingredient::parse_many("2 tbsp oil or butter, for frying").unwrap();
+        assert_eq!(ingredients.len(), 2);
+        assert_eq!(ingredients[1].ingredient.as_deref(), Some("butter"));
+        assert_eq!(ingredients[1].quantities[0].unit, Some(Unit::Tablespoon));
+        assert_eq!(ingredients[1].usage_note.as_deref(), Some("for frying"));
+
+        let options = ParseOptions::new().split_or(false);
+        let ingredients = Ingredient::parse_many_with("oil or butter", &options).unwrap();
+        assert_eq!(ingredients.len(), 1);
+        assert_eq!(ingredients[0].ingredient.as_deref(), Some("oil or butter"));
+        assert_eq!(Ingredient::parse_many("2 cups flour").unwrap().len(), 1);
+
+        let ingredients = Ingredient::parse_many("2 eggs and 1 cup milk").unwrap();
+        assert_eq!(ingredients[0].ingredient.as_deref(), Some("eggs"));
+        assert_eq!(ingredients[0].quantities[0].amount.value(), 2.);
+        assert_eq!(ingredients[0].quantities[0].unit, None);
+        assert_eq!(ingredients[1].ingredient.as_deref(), Some("milk"));
+        assert_eq!(ingredients[1].quantities[0].amount.value(), 1.);
+        assert_eq!(ingredients[1].quantities[0].unit, Some(Unit::Cup));
+        let ingredients = Ingredient::parse_many("2 cups flour and sugar").unwrap();
+        assert_eq!(ingredients[1].ingredient.as_deref(), Some("sugar"));
+        assert_eq!(ingredients[1].quantities[0].unit, Some(Unit::Cup));
+        assert_eq!(Ingredient::parse_many("2 cups").unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,297 @@
+//! Oven, frying and liquid temperatures (e.g. "350°F", "warm water (110°F)" or "water at 110°F")
+
+use crate::span::scan_quantities;
+use crate::{
+    parser::IngredientParser, rule_of, Quantity, QuantitySpan, Rule, Span, Unit, UnitCategory,
+};
+use pest::Parser;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Temperature scale of a `Temperature`
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum TemperatureScale {
+    Fahrenheit,
+    Celsius,
+}
+
+impl TemperatureScale {
+    /// Unit of the scale
+    pub fn unit(&self) -> Unit {
+        match self {
+            Self::Fahrenheit => Unit::Fahrenheit,
+            Self::Celsius => Unit::Celsius,
+        }
+    }
+
+    /// Scale measured by a unit, if it's a temperature unit
+    pub fn from_unit(unit: &Unit) -> Option<Self> {
+        match unit {
+            Unit::Fahrenheit => Some(Self::Fahrenheit),
+            Unit::Celsius => Some(Self::Celsius),
+            _ => None,
+        }
+    }
+}
+
+/// A temperature in degrees of a scale
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Copy)]
+pub struct Temperature {
+    pub degrees: f64,
+    pub scale: TemperatureScale,
+}
+
+impl Temperature {
+    pub fn new(degrees: f64, scale: TemperatureScale) -> Self {
+        Self { degrees, scale }
+    }
+
+    /// Degrees Celsius
+    pub fn celsius(&self) -> f64 {
+        match self.scale {
+            TemperatureScale::Celsius => self.degrees,
+            TemperatureScale::Fahrenheit => (self.degrees - 32.) * 5. / 9.,
+        }
+    }
+
+    /// Degrees Fahrenheit
+    pub fn fahrenheit(&self) -> f64 {
+        match self.scale {
+            TemperatureScale::Fahrenheit => self.degrees,
+            TemperatureScale::Celsius => self.degrees * 9. / 5. + 32.,
+        }
+    }
+
+    /// The same temperature on another scale (e.g. 350°F as about 177°C)
+    pub fn to_scale(&self, scale: TemperatureScale) -> Self {
+        let degrees = match scale {
+            TemperatureScale::Celsius => self.celsius(),
+            TemperatureScale::Fahrenheit => self.fahrenheit(),
+        };
+        Self::new(degrees, scale)
+    }
+}
+
+impl fmt::Display for Temperature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.degrees, self.scale.unit().abbreviation())
+    }
+}
+
+impl Quantity {
+    /// The quantity as a temperature, if its unit is degrees Fahrenheit or Celsius,
+    /// taking the middle of a range
+    pub fn temperature(&self) -> Option<Temperature> {
+        let scale = TemperatureScale::from_unit(self.unit.as_ref()?)?;
+        Some(Temperature::new(self.amount.value(), scale))
+    }
+
+    /// Whether the quantity is a temperature rather than an amount of the ingredient
+    pub fn is_temperature(&self) -> bool {
+        self.unit
+            .as_ref()
+            .is_some_and(|unit| unit.category() == UnitCategory::Temperature)
+    }
+}
+
+/// Spellings of temperature units, to skip scanning names without one
+const TEMPERATURE_WORDS: &[&str] = &[
+    "F",
+    "C",
+    "degree",
+    "degrees",
+    "fahrenheit",
+    "celsius",
+    "centigrade",
+];
+
+/// Whether the name has a degree sign or a temperature unit word (also after digits, as in "110F")
+fn has_temperature_word(name: &str) -> bool {
+    name.contains(&['°', 'º', '℉', '℃'][..])
+        || name
+            .split(|c: char| !c.is_alphanumeric())
+            .map(|word| word.trim_start_matches(|c: char| c.is_ascii_digit()))
+            .any(|word| {
+                TEMPERATURE_WORDS.iter().any(|temperature_word| {
+                    *temperature_word == word
+                        || temperature_word.len() > 1 && temperature_word.eq_ignore_ascii_case(word)
+                })
+            })
+}
+
+/// Words joining a temperature to the text before it (e.g. "heated to 110°F")
+pub(crate) const JOINING_WORDS: &[&str] = &["at", "to"];
+
+/// Start of the joining word out of `words` ending `before` (e.g. "at" in "bake at "),
+/// if there is one
+pub(crate) fn joining_word_start(before: &str, words: &[&str]) -> Option<usize> {
+    let before = before.trim_end();
+    words.iter().find_map(|word| {
+        before
+            .strip_suffix(word)
+            .filter(|rest| rest.is_empty() || rest.ends_with(char::is_whitespace))
+            .map(str::len)
+    })
+}
+
+/// First temperature in an ingredient name, in parentheses (e.g. "warm water (110°F)")
+/// or not (e.g. "water at 110 degrees F"), with the spans of it and of the text to strip
+/// from the name: the parentheses around it, or a joining word before it
+pub(crate) fn find_temperature(name: &str) -> Option<(Quantity, QuantitySpan, Span)> {
+    if !has_temperature_word(name) {
+        return None;
+    }
+    let mut search = 0;
+    while let Some(open) = name[search..].find('(').map(|index| search + index) {
+        let close = match name[open..].find(')') {
+            Some(close) => open + close,
+            None => break,
+        };
+        if let Some((quantity, span)) = parse_temperature(&name[open + 1..close]) {
+            let parenthesized = Span {
+                start: open,
+                end: close + 1,
+            };
+            return Some((quantity, span.shift(open + 1), parenthesized));
+        }
+        search = close;
+    }
+    let (span, quantity) = scan_quantities(name)
+        .into_iter()
+        .find(|(_, quantity)| quantity.is_temperature())?;
+    let stripped = Span {
+        start: joining_word_start(&name[..span.quantity.start], JOINING_WORDS)
+            .unwrap_or(span.quantity.start),
+        end: span.quantity.end,
+    };
+    Some((quantity, span, stripped))
+}
+
+fn parse_temperature(text: &str) -> Option<(Quantity, QuantitySpan)> {
+    let pair = IngredientParser::parse(Rule::quantity_input, text)
+        .ok()?
+        .find(|pair| rule_of(pair) == Rule::quantity_fragment)?;
+    let span = QuantitySpan::from_pair(&pair);
+    let quantity = Quantity::parse_fragment(pair).ok()?;
+    quantity.is_temperature().then_some((quantity, span))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Ingredient, UnitType};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_parse_temperature() {
+        for (input, degrees, scale) in [
+            ("350°F", 350., TemperatureScale::Fahrenheit),
+            ("180 C", 180., TemperatureScale::Celsius),
+            ("200 °C", 200., TemperatureScale::Celsius),
+            ("375 degrees F", 375., TemperatureScale::Fahrenheit),
+            ("220 degrees Celsius", 220., TemperatureScale::Celsius),
+        ] {
+            let quantity = Quantity::parse_str(input).unwrap();
+            assert_eq!(quantity.unit_type, Some(UnitType::Temperature), "{}", input);
+            assert_eq!(
+                quantity.temperature(),
+                Some(Temperature::new(degrees, scale)),
+                "{}",
+                input
+            );
+        }
+        assert_eq!(Quantity::parse_str("2 cups").unwrap().temperature(), None);
+        let cup = Ingredient::parse("1 C sugar").unwrap();
+        assert_eq!(cup.quantities[0].unit, Some(Unit::Cup));
+        assert_eq!(cup.quantities[0].unit_type, Some(UnitType::English));
+        for input in ["20 C water", "30 C butter"] {
+            let cup = Ingredient::parse(input).unwrap();
+            assert_eq!(cup.quantities[0].unit, Some(Unit::Cup), "{}", input);
+        }
+        assert_eq!(
+            Quantity::parse_str("180 degrees C").unwrap().unit,
+            Some(Unit::Celsius)
+        );
+        let cup = Ingredient::parse("1 C. milk").unwrap();
+        assert_eq!(cup.quantities[0].unit, Some(Unit::Cup));
+        assert_eq!(cup.ingredient.as_deref(), Some("milk"));
+    }
+
+    #[test]
+    fn test_temperature_in_name() {
+        let ingredient = Ingredient::parse("1 cup warm water (110°F)").unwrap();
+        assert_eq!(ingredient.ingredient.as_deref(), Some("warm water"));
+        assert_eq!(ingredient.quantities.len(), 2);
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Cup));
+        assert_eq!(
+            ingredient.quantities[1].temperature(),
+            Some(Temperature::new(110., TemperatureScale::Fahrenheit))
+        );
+        assert_eq!(ingredient.quantities[1].raw, "110°F");
+        let scaled = ingredient.scale(2.);
+        assert_relative_eq!(scaled.quantities[0].amount.value(), 2.);
+        assert_relative_eq!(scaled.quantities[1].amount.value(), 110.);
+
+        let ingredient = Ingredient::parse("2 cups milk (at 40 C), warmed").unwrap();
+        assert_eq!(
+            ingredient.ingredient.as_deref(),
+            Some("milk (at 40 C), warmed")
+        );
+        let ingredient = Ingredient::parse("oil (350 degrees F) for frying").unwrap();
+        assert_eq!(ingredient.ingredient.as_deref(), Some("oil"));
+        assert!(ingredient.quantities[0].is_temperature());
+
+        let ingredient = Ingredient::parse("1 cup water at 110 degrees F").unwrap();
+        assert_eq!(ingredient.ingredient.as_deref(), Some("water"));
+        assert_eq!(
+            ingredient.quantities[1].temperature(),
+            Some(Temperature::new(110., TemperatureScale::Fahrenheit))
+        );
+        let spanned = Ingredient::parse_with_spans("1 cup water at 110 degrees F").unwrap();
+        assert_eq!(
+            spanned.quantities[1]
+                .quantity
+                .as_str("1 cup water at 110 degrees F"),
+            "110 degrees F"
+        );
+        let ingredient = Ingredient::parse("water, heated to 110°F").unwrap();
+        assert_eq!(ingredient.ingredient.as_deref(), Some("water, heated"));
+        assert!(ingredient.quantities[0].is_temperature());
+        let ingredient = Ingredient::parse("1 cup water at 110°F, divided").unwrap();
+        assert_eq!(ingredient.ingredient.as_deref(), Some("water, divided"));
+    }
+
+    #[test]
+    fn test_has_temperature_word() {
+        assert!(has_temperature_word("water, heated to 110°F"));
+        assert!(has_temperature_word("oil at 180C"));
+        assert!(has_temperature_word("375 degrees"));
+        assert!(!has_temperature_word("flour, sifted"));
+        assert!(!has_temperature_word("Cheddar cheese"));
+    }
+
+    #[test]
+    fn test_convert_temperature() {
+        let oven = Temperature::new(350., TemperatureScale::Fahrenheit);
+        assert_relative_eq!(oven.celsius(), 176.666_666, epsilon = 1e-5);
+        assert_relative_eq!(
+            Temperature::new(100., TemperatureScale::Celsius).fahrenheit(),
+            212.
+        );
+        let converted = Quantity::parse_str("180°C")
+            .unwrap()
+            .convert_to(Unit::Fahrenheit)
+            .unwrap();
+        assert_relative_eq!(converted.amount.value(), 356.);
+        assert_eq!(converted.unit, Some(Unit::Fahrenheit));
+        let converted = Quantity::parse_str("400°F").unwrap().to_metric().unwrap();
+        assert_eq!(converted.unit, Some(Unit::Celsius));
+        assert!(Quantity::parse_str("350°F")
+            .unwrap()
+            .convert_to(Unit::Cup)
+            .is_err());
+    }
+}
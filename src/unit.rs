@@ -0,0 +1,903 @@
+//! Canonical units of measurement
+
+use crate::{parser::IngredientParser, IngreedyError, Rule, UnitType};
+use pest::Parser;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Physical quantity measured by a unit
+#[derive(Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Clone, Copy)]
+pub enum UnitCategory {
+    Mass,
+    Volume,
+    Energy,
+    Temperature,
+    Time,
+    Length,
+    /// Units that count a portion or a container rather than measure it (pinch, can, ...)
+    Count,
+}
+
+/// Unit of measurement recognized by the grammar
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Clone)]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
+pub enum Unit {
+    Calorie,
+    Cup,
+    FluidOunce,
+    Gallon,
+    Ounce,
+    Pint,
+    Pound,
+    Quart,
+    Tablespoon,
+    Teaspoon,
+    Gram,
+    Joule,
+    Kilogram,
+    Kilojoule,
+    Liter,
+    Milligram,
+    Microgram,
+    Milliliter,
+    Centiliter,
+    Deciliter,
+    Fahrenheit,
+    Celsius,
+    Hour,
+    Minute,
+    Second,
+    Inch,
+    Centimeter,
+    Millimeter,
+    /// The Chinese market jin or catty, 500 g
+    Jin,
+    /// The Chinese market liang or tael, 50 g
+    Liang,
+    /// The Japanese gō of rice and sake, about 180 ml
+    Go,
+    Dash,
+    Drop,
+    Smidgen,
+    Handful,
+    Pinch,
+    Touch,
+    Splash,
+    Knob,
+    Glug,
+    /// A portion whose size depends on the ingredient or the recipe (e.g. "2 servings cooked rice")
+    Serving,
+    Scoop,
+    Square,
+    Can,
+    Jar,
+    Package,
+    Stick,
+    Bag,
+    Bottle,
+    Bar,
+    Tub,
+    Clove,
+    Sprig,
+    Slice,
+    Stalk,
+    Rib,
+    Head,
+    Ear,
+    Fillet,
+    Bunch,
+    Piece,
+    /// Unit registered at runtime through a `UnitRegistry` (e.g. "dollop"),
+    /// or deserialized from a name unknown to this version
+    Other(String),
+}
+
+/// Units built into the grammar, in declaration order
+pub(crate) const BUILTIN_UNITS: &[Unit] = &[
+    Unit::Calorie,
+    Unit::Cup,
+    Unit::FluidOunce,
+    Unit::Gallon,
+    Unit::Ounce,
+    Unit::Pint,
+    Unit::Pound,
+    Unit::Quart,
+    Unit::Tablespoon,
+    Unit::Teaspoon,
+    Unit::Gram,
+    Unit::Joule,
+    Unit::Kilogram,
+    Unit::Kilojoule,
+    Unit::Liter,
+    Unit::Milligram,
+    Unit::Microgram,
+    Unit::Milliliter,
+    Unit::Centiliter,
+    Unit::Deciliter,
+    Unit::Fahrenheit,
+    Unit::Celsius,
+    Unit::Hour,
+    Unit::Minute,
+    Unit::Second,
+    Unit::Inch,
+    Unit::Centimeter,
+    Unit::Millimeter,
+    Unit::Jin,
+    Unit::Liang,
+    Unit::Go,
+    Unit::Dash,
+    Unit::Drop,
+    Unit::Smidgen,
+    Unit::Handful,
+    Unit::Pinch,
+    Unit::Touch,
+    Unit::Splash,
+    Unit::Knob,
+    Unit::Glug,
+    Unit::Serving,
+    Unit::Scoop,
+    Unit::Square,
+    Unit::Can,
+    Unit::Jar,
+    Unit::Package,
+    Unit::Stick,
+    Unit::Bag,
+    Unit::Bottle,
+    Unit::Bar,
+    Unit::Tub,
+    Unit::Clove,
+    Unit::Sprig,
+    Unit::Slice,
+    Unit::Stalk,
+    Unit::Rib,
+    Unit::Head,
+    Unit::Ear,
+    Unit::Fillet,
+    Unit::Bunch,
+    Unit::Piece,
+];
+
+/// Spellings accepted by `Unit::from_str`, mirroring the grammar's unit rules
+const ALIASES: &[(&str, Unit)] = &[
+    ("calories", Unit::Calorie),
+    ("calorie", Unit::Calorie),
+    ("cal", Unit::Calorie),
+    ("kilocalories", Unit::Calorie),
+    ("kilocalorie", Unit::Calorie),
+    ("kcal", Unit::Calorie),
+    ("cups", Unit::Cup),
+    ("cup", Unit::Cup),
+    ("c.", Unit::Cup),
+    ("C.", Unit::Cup),
+    ("c", Unit::Cup),
+    ("fluid ounces", Unit::FluidOunce),
+    ("fluid ounce", Unit::FluidOunce),
+    ("fluid_ounce", Unit::FluidOunce),
+    ("fl oz", Unit::FluidOunce),
+    ("fl. oz.", Unit::FluidOunce),
+    ("gallons", Unit::Gallon),
+    ("gallon", Unit::Gallon),
+    ("gal.", Unit::Gallon),
+    ("gal", Unit::Gallon),
+    ("ounces", Unit::Ounce),
+    ("ounce", Unit::Ounce),
+    ("ozs.", Unit::Ounce),
+    ("ozs", Unit::Ounce),
+    ("oz.", Unit::Ounce),
+    ("oz", Unit::Ounce),
+    ("pints", Unit::Pint),
+    ("pint", Unit::Pint),
+    ("pt.", Unit::Pint),
+    ("pt", Unit::Pint),
+    ("pounds", Unit::Pound),
+    ("pound", Unit::Pound),
+    ("lbs.", Unit::Pound),
+    ("lbs", Unit::Pound),
+    ("lb.", Unit::Pound),
+    ("lb", Unit::Pound),
+    ("quarts", Unit::Quart),
+    ("quart", Unit::Quart),
+    ("qts.", Unit::Quart),
+    ("qts", Unit::Quart),
+    ("qt.", Unit::Quart),
+    ("qt", Unit::Quart),
+    ("tablespoons", Unit::Tablespoon),
+    ("tablespoon", Unit::Tablespoon),
+    ("tbsps.", Unit::Tablespoon),
+    ("tbsps", Unit::Tablespoon),
+    ("tbsp.", Unit::Tablespoon),
+    ("tbsp", Unit::Tablespoon),
+    ("tbs.", Unit::Tablespoon),
+    ("tbs", Unit::Tablespoon),
+    ("T.", Unit::Tablespoon),
+    ("T", Unit::Tablespoon),
+    ("teaspoons", Unit::Teaspoon),
+    ("teaspoon", Unit::Teaspoon),
+    ("tsps.", Unit::Teaspoon),
+    ("tsps", Unit::Teaspoon),
+    ("tsp.", Unit::Teaspoon),
+    ("tsp", Unit::Teaspoon),
+    ("t.", Unit::Teaspoon),
+    ("t", Unit::Teaspoon),
+    ("grams", Unit::Gram),
+    ("gram", Unit::Gram),
+    ("gr.", Unit::Gram),
+    ("gr", Unit::Gram),
+    ("g.", Unit::Gram),
+    ("g", Unit::Gram),
+    ("joules", Unit::Joule),
+    ("joule", Unit::Joule),
+    ("j", Unit::Joule),
+    ("kilograms", Unit::Kilogram),
+    ("kilogram", Unit::Kilogram),
+    ("kg.", Unit::Kilogram),
+    ("kg", Unit::Kilogram),
+    ("kilojoules", Unit::Kilojoule),
+    ("kilojoule", Unit::Kilojoule),
+    ("kj", Unit::Kilojoule),
+    ("liters", Unit::Liter),
+    ("liter", Unit::Liter),
+    ("l.", Unit::Liter),
+    ("l", Unit::Liter),
+    ("milligrams", Unit::Milligram),
+    ("milligram", Unit::Milligram),
+    ("mg.", Unit::Milligram),
+    ("mg", Unit::Milligram),
+    ("micrograms", Unit::Microgram),
+    ("microgram", Unit::Microgram),
+    ("mcg.", Unit::Microgram),
+    ("mcg", Unit::Microgram),
+    ("µg", Unit::Microgram),
+    ("μg", Unit::Microgram),
+    ("milliliters", Unit::Milliliter),
+    ("milliliter", Unit::Milliliter),
+    ("ml.", Unit::Milliliter),
+    ("ml", Unit::Milliliter),
+    ("cc", Unit::Milliliter),
+    ("cm3", Unit::Milliliter),
+    ("cm³", Unit::Milliliter),
+    ("centiliters", Unit::Centiliter),
+    ("centiliter", Unit::Centiliter),
+    ("centilitres", Unit::Centiliter),
+    ("centilitre", Unit::Centiliter),
+    ("cl.", Unit::Centiliter),
+    ("cL", Unit::Centiliter),
+    ("cl", Unit::Centiliter),
+    ("deciliters", Unit::Deciliter),
+    ("deciliter", Unit::Deciliter),
+    ("decilitres", Unit::Deciliter),
+    ("decilitre", Unit::Deciliter),
+    ("dl.", Unit::Deciliter),
+    ("dL", Unit::Deciliter),
+    ("dl", Unit::Deciliter),
+    ("degrees Fahrenheit", Unit::Fahrenheit),
+    ("fahrenheit", Unit::Fahrenheit),
+    ("°F", Unit::Fahrenheit),
+    ("℉", Unit::Fahrenheit),
+    ("F", Unit::Fahrenheit),
+    ("degrees Celsius", Unit::Celsius),
+    ("celsius", Unit::Celsius),
+    ("centigrade", Unit::Celsius),
+    ("°C", Unit::Celsius),
+    ("℃", Unit::Celsius),
+    ("C", Unit::Celsius),
+    ("hours", Unit::Hour),
+    ("hour", Unit::Hour),
+    ("hrs.", Unit::Hour),
+    ("hrs", Unit::Hour),
+    ("hr.", Unit::Hour),
+    ("hr", Unit::Hour),
+    ("minutes", Unit::Minute),
+    ("minute", Unit::Minute),
+    ("mins.", Unit::Minute),
+    ("mins", Unit::Minute),
+    ("min.", Unit::Minute),
+    ("min", Unit::Minute),
+    ("seconds", Unit::Second),
+    ("second", Unit::Second),
+    ("secs.", Unit::Second),
+    ("secs", Unit::Second),
+    ("sec.", Unit::Second),
+    ("sec", Unit::Second),
+    ("inches", Unit::Inch),
+    ("inch", Unit::Inch),
+    ("in.", Unit::Inch),
+    ("\"", Unit::Inch),
+    ("″", Unit::Inch),
+    ("centimeters", Unit::Centimeter),
+    ("centimeter", Unit::Centimeter),
+    ("centimetres", Unit::Centimeter),
+    ("centimetre", Unit::Centimeter),
+    ("cm.", Unit::Centimeter),
+    ("cm", Unit::Centimeter),
+    ("millimeters", Unit::Millimeter),
+    ("millimeter", Unit::Millimeter),
+    ("millimetres", Unit::Millimeter),
+    ("millimetre", Unit::Millimeter),
+    ("mm.", Unit::Millimeter),
+    ("mm", Unit::Millimeter),
+    ("catties", Unit::Jin),
+    ("catty", Unit::Jin),
+    ("jin", Unit::Jin),
+    ("斤", Unit::Jin),
+    ("taels", Unit::Liang),
+    ("tael", Unit::Liang),
+    ("liang", Unit::Liang),
+    ("两", Unit::Liang),
+    ("兩", Unit::Liang),
+    ("gō", Unit::Go),
+    ("go", Unit::Go),
+    ("合", Unit::Go),
+    ("dashes", Unit::Dash),
+    ("dash", Unit::Dash),
+    ("drops", Unit::Drop),
+    ("drop", Unit::Drop),
+    ("smidgens", Unit::Smidgen),
+    ("smidgen", Unit::Smidgen),
+    ("handfuls", Unit::Handful),
+    ("handful", Unit::Handful),
+    ("pinches", Unit::Pinch),
+    ("pinch", Unit::Pinch),
+    ("touches", Unit::Touch),
+    ("touch", Unit::Touch),
+    ("splashes", Unit::Splash),
+    ("splash", Unit::Splash),
+    ("knobs", Unit::Knob),
+    ("knob", Unit::Knob),
+    ("glugs", Unit::Glug),
+    ("glug", Unit::Glug),
+    ("servings", Unit::Serving),
+    ("serving", Unit::Serving),
+    ("scoops", Unit::Scoop),
+    ("scoop", Unit::Scoop),
+    ("squares", Unit::Square),
+    ("square", Unit::Square),
+    ("cans", Unit::Can),
+    ("can", Unit::Can),
+    ("jars", Unit::Jar),
+    ("jar", Unit::Jar),
+    ("packages", Unit::Package),
+    ("package", Unit::Package),
+    ("pkgs.", Unit::Package),
+    ("pkgs", Unit::Package),
+    ("pkg.", Unit::Package),
+    ("pkg", Unit::Package),
+    ("sticks", Unit::Stick),
+    ("stick", Unit::Stick),
+    ("bags", Unit::Bag),
+    ("bag", Unit::Bag),
+    ("bottles", Unit::Bottle),
+    ("bottle", Unit::Bottle),
+    ("bars", Unit::Bar),
+    ("bar", Unit::Bar),
+    ("tubs", Unit::Tub),
+    ("tub", Unit::Tub),
+    ("cloves", Unit::Clove),
+    ("clove", Unit::Clove),
+    ("sprigs", Unit::Sprig),
+    ("sprig", Unit::Sprig),
+    ("slices", Unit::Slice),
+    ("slice", Unit::Slice),
+    ("stalks", Unit::Stalk),
+    ("stalk", Unit::Stalk),
+    ("ribs", Unit::Rib),
+    ("rib", Unit::Rib),
+    ("heads", Unit::Head),
+    ("head", Unit::Head),
+    ("ears", Unit::Ear),
+    ("ear", Unit::Ear),
+    ("fillets", Unit::Fillet),
+    ("fillet", Unit::Fillet),
+    ("filets", Unit::Fillet),
+    ("filet", Unit::Fillet),
+    ("bunches", Unit::Bunch),
+    ("bunch", Unit::Bunch),
+    ("pieces", Unit::Piece),
+    ("piece", Unit::Piece),
+    ("pcs.", Unit::Piece),
+    ("pcs", Unit::Piece),
+    ("pc.", Unit::Piece),
+    ("pc", Unit::Piece),
+];
+
+/// Whether the grammar reads a token as a unit (e.g. "tbsp", "fl. oz.");
+/// case only matters for single letters ("T" is a tablespoon, "t" a teaspoon)
+pub fn is_unit_token(token: &str) -> bool {
+    IngredientParser::parse(Rule::unit_input, token).is_ok()
+}
+
+impl Unit {
+    /// Units built into the grammar, not counting those registered at runtime
+    pub fn all() -> &'static [Self] {
+        BUILTIN_UNITS
+    }
+
+    /// Spellings of the unit that `from_str` accepts (e.g. "tablespoons", "tbsp", "T");
+    /// none for `Unit::Other`
+    pub fn aliases(&self) -> impl Iterator<Item = &'static str> + '_ {
+        ALIASES
+            .iter()
+            .filter(move |(_, unit)| unit == self)
+            .map(|(alias, _)| *alias)
+    }
+
+    /// Map a leaf unit rule of the grammar to its unit
+    pub(crate) fn from_rule(rule: Rule) -> Option<Self> {
+        let unit = match rule {
+            Rule::calorie => Self::Calorie,
+            Rule::cup => Self::Cup,
+            Rule::fluid_ounce => Self::FluidOunce,
+            Rule::gallon => Self::Gallon,
+            Rule::ounce => Self::Ounce,
+            Rule::pint => Self::Pint,
+            Rule::pound => Self::Pound,
+            Rule::quart => Self::Quart,
+            Rule::tablespoon => Self::Tablespoon,
+            Rule::teaspoon => Self::Teaspoon,
+            Rule::gram => Self::Gram,
+            Rule::joule => Self::Joule,
+            Rule::kilogram => Self::Kilogram,
+            Rule::kilojoule => Self::Kilojoule,
+            Rule::liter => Self::Liter,
+            Rule::milligram => Self::Milligram,
+            Rule::microgram => Self::Microgram,
+            Rule::milliliter => Self::Milliliter,
+            Rule::centiliter => Self::Centiliter,
+            Rule::deciliter => Self::Deciliter,
+            Rule::fahrenheit => Self::Fahrenheit,
+            Rule::celsius => Self::Celsius,
+            Rule::hour => Self::Hour,
+            Rule::minute => Self::Minute,
+            Rule::second => Self::Second,
+            Rule::inch => Self::Inch,
+            Rule::centimeter => Self::Centimeter,
+            Rule::millimeter => Self::Millimeter,
+            Rule::jin => Self::Jin,
+            Rule::liang => Self::Liang,
+            Rule::go => Self::Go,
+            Rule::dash => Self::Dash,
+            Rule::drop => Self::Drop,
+            Rule::smidgen => Self::Smidgen,
+            Rule::handful => Self::Handful,
+            Rule::pinch => Self::Pinch,
+            Rule::touch => Self::Touch,
+            Rule::splash => Self::Splash,
+            Rule::knob => Self::Knob,
+            Rule::glug => Self::Glug,
+            Rule::serving => Self::Serving,
+            Rule::scoop => Self::Scoop,
+            Rule::square => Self::Square,
+            Rule::can => Self::Can,
+            Rule::jar => Self::Jar,
+            Rule::package => Self::Package,
+            Rule::stick => Self::Stick,
+            Rule::bag => Self::Bag,
+            Rule::bottle => Self::Bottle,
+            Rule::bar => Self::Bar,
+            Rule::tub => Self::Tub,
+            Rule::clove => Self::Clove,
+            Rule::sprig => Self::Sprig,
+            Rule::slice => Self::Slice,
+            Rule::stalk => Self::Stalk,
+            Rule::rib => Self::Rib,
+            Rule::head => Self::Head,
+            Rule::ear => Self::Ear,
+            Rule::fillet => Self::Fillet,
+            Rule::bunch => Self::Bunch,
+            Rule::piece => Self::Piece,
+            _ => return None,
+        };
+        Some(unit)
+    }
+
+    /// Canonical name, matching the serialized form (e.g. "fluid_ounce")
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Calorie => "calorie",
+            Self::Cup => "cup",
+            Self::FluidOunce => "fluid_ounce",
+            Self::Gallon => "gallon",
+            Self::Ounce => "ounce",
+            Self::Pint => "pint",
+            Self::Pound => "pound",
+            Self::Quart => "quart",
+            Self::Tablespoon => "tablespoon",
+            Self::Teaspoon => "teaspoon",
+            Self::Gram => "gram",
+            Self::Joule => "joule",
+            Self::Kilogram => "kilogram",
+            Self::Kilojoule => "kilojoule",
+            Self::Liter => "liter",
+            Self::Milligram => "milligram",
+            Self::Microgram => "microgram",
+            Self::Milliliter => "milliliter",
+            Self::Centiliter => "centiliter",
+            Self::Deciliter => "deciliter",
+            Self::Fahrenheit => "fahrenheit",
+            Self::Celsius => "celsius",
+            Self::Hour => "hour",
+            Self::Minute => "minute",
+            Self::Second => "second",
+            Self::Inch => "inch",
+            Self::Centimeter => "centimeter",
+            Self::Millimeter => "millimeter",
+            Self::Jin => "jin",
+            Self::Liang => "liang",
+            Self::Go => "go",
+            Self::Dash => "dash",
+            Self::Drop => "drop",
+            Self::Smidgen => "smidgen",
+            Self::Handful => "handful",
+            Self::Pinch => "pinch",
+            Self::Touch => "touch",
+            Self::Splash => "splash",
+            Self::Knob => "knob",
+            Self::Glug => "glug",
+            Self::Serving => "serving",
+            Self::Scoop => "scoop",
+            Self::Square => "square",
+            Self::Can => "can",
+            Self::Jar => "jar",
+            Self::Package => "package",
+            Self::Stick => "stick",
+            Self::Bag => "bag",
+            Self::Bottle => "bottle",
+            Self::Bar => "bar",
+            Self::Tub => "tub",
+            Self::Clove => "clove",
+            Self::Sprig => "sprig",
+            Self::Slice => "slice",
+            Self::Stalk => "stalk",
+            Self::Rib => "rib",
+            Self::Head => "head",
+            Self::Ear => "ear",
+            Self::Fillet => "fillet",
+            Self::Bunch => "bunch",
+            Self::Piece => "piece",
+            Self::Other(name) => name,
+        }
+    }
+
+    /// Common abbreviation (e.g. "tbsp"), or the full name for units without one
+    pub fn abbreviation(&self) -> &str {
+        match self {
+            Self::Calorie => "kcal",
+            Self::Cup => "c",
+            Self::FluidOunce => "fl oz",
+            Self::Gallon => "gal",
+            Self::Ounce => "oz",
+            Self::Pint => "pt",
+            Self::Pound => "lb",
+            Self::Quart => "qt",
+            Self::Tablespoon => "tbsp",
+            Self::Teaspoon => "tsp",
+            Self::Gram => "g",
+            Self::Joule => "J",
+            Self::Kilogram => "kg",
+            Self::Kilojoule => "kJ",
+            Self::Liter => "l",
+            Self::Milligram => "mg",
+            Self::Microgram => "µg",
+            Self::Milliliter => "ml",
+            Self::Centiliter => "cl",
+            Self::Deciliter => "dl",
+            Self::Fahrenheit => "°F",
+            Self::Celsius => "°C",
+            Self::Hour => "hr",
+            Self::Minute => "min",
+            Self::Second => "sec",
+            Self::Inch => "in.",
+            Self::Centimeter => "cm",
+            Self::Millimeter => "mm",
+            Self::Jin => "斤",
+            Self::Liang => "两",
+            Self::Go => "合",
+            Self::Dash => "dash",
+            Self::Drop => "drop",
+            Self::Smidgen => "smidgen",
+            Self::Handful => "handful",
+            Self::Pinch => "pinch",
+            Self::Touch => "touch",
+            Self::Splash => "splash",
+            Self::Knob => "knob",
+            Self::Glug => "glug",
+            Self::Serving => "serving",
+            Self::Scoop => "scoop",
+            Self::Square => "square",
+            Self::Can => "can",
+            Self::Jar => "jar",
+            Self::Package => "pkg",
+            Self::Stick => "stick",
+            Self::Bag => "bag",
+            Self::Bottle => "bottle",
+            Self::Bar => "bar",
+            Self::Tub => "tub",
+            Self::Clove => "clove",
+            Self::Sprig => "sprig",
+            Self::Slice => "slice",
+            Self::Stalk => "stalk",
+            Self::Rib => "rib",
+            Self::Head => "head",
+            Self::Ear => "ear",
+            Self::Fillet => "fillet",
+            Self::Bunch => "bunch",
+            Self::Piece => "pc",
+            Self::Other(name) => name,
+        }
+    }
+
+    /// System of units the unit belongs to.
+    ///
+    /// Units registered at runtime are imprecise; the registry records their actual type.
+    pub fn unit_type(&self) -> UnitType {
+        match self {
+            Self::Calorie
+            | Self::Cup
+            | Self::FluidOunce
+            | Self::Gallon
+            | Self::Ounce
+            | Self::Pint
+            | Self::Pound
+            | Self::Quart
+            | Self::Tablespoon
+            | Self::Teaspoon
+            | Self::Inch => UnitType::English,
+            Self::Gram
+            | Self::Joule
+            | Self::Kilogram
+            | Self::Kilojoule
+            | Self::Liter
+            | Self::Milligram
+            | Self::Microgram
+            | Self::Milliliter
+            | Self::Centiliter
+            | Self::Deciliter
+            | Self::Centimeter
+            | Self::Millimeter => UnitType::Metric,
+            Self::Fahrenheit | Self::Celsius => UnitType::Temperature,
+            Self::Hour | Self::Minute | Self::Second => UnitType::Time,
+            Self::Jin | Self::Liang | Self::Go => UnitType::Traditional,
+            Self::Dash
+            | Self::Drop
+            | Self::Smidgen
+            | Self::Handful
+            | Self::Pinch
+            | Self::Touch
+            | Self::Splash
+            | Self::Knob
+            | Self::Glug
+            | Self::Serving
+            | Self::Scoop
+            | Self::Square
+            | Self::Other(_) => UnitType::Imprecise,
+            Self::Can
+            | Self::Jar
+            | Self::Package
+            | Self::Stick
+            | Self::Bag
+            | Self::Bottle
+            | Self::Bar
+            | Self::Tub => UnitType::Container,
+            Self::Clove
+            | Self::Sprig
+            | Self::Slice
+            | Self::Stalk
+            | Self::Rib
+            | Self::Head
+            | Self::Ear
+            | Self::Fillet
+            | Self::Bunch
+            | Self::Piece => UnitType::Count,
+        }
+    }
+
+    /// Physical quantity measured by the unit
+    pub fn category(&self) -> UnitCategory {
+        match self {
+            Self::Ounce
+            | Self::Pound
+            | Self::Gram
+            | Self::Kilogram
+            | Self::Milligram
+            | Self::Microgram
+            | Self::Jin
+            | Self::Liang => UnitCategory::Mass,
+            Self::Cup
+            | Self::FluidOunce
+            | Self::Gallon
+            | Self::Pint
+            | Self::Quart
+            | Self::Tablespoon
+            | Self::Teaspoon
+            | Self::Liter
+            | Self::Milliliter
+            | Self::Centiliter
+            | Self::Deciliter
+            | Self::Go => UnitCategory::Volume,
+            Self::Calorie | Self::Joule | Self::Kilojoule => UnitCategory::Energy,
+            Self::Fahrenheit | Self::Celsius => UnitCategory::Temperature,
+            Self::Hour | Self::Minute | Self::Second => UnitCategory::Time,
+            Self::Inch | Self::Centimeter | Self::Millimeter => UnitCategory::Length,
+            Self::Dash
+            | Self::Drop
+            | Self::Smidgen
+            | Self::Handful
+            | Self::Pinch
+            | Self::Touch
+            | Self::Splash
+            | Self::Knob
+            | Self::Glug
+            | Self::Serving
+            | Self::Scoop
+            | Self::Square
+            | Self::Can
+            | Self::Jar
+            | Self::Package
+            | Self::Stick
+            | Self::Bag
+            | Self::Bottle
+            | Self::Bar
+            | Self::Tub
+            | Self::Clove
+            | Self::Sprig
+            | Self::Slice
+            | Self::Stalk
+            | Self::Rib
+            | Self::Head
+            | Self::Ear
+            | Self::Fillet
+            | Self::Bunch
+            | Self::Piece
+            | Self::Other(_) => UnitCategory::Count,
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name().replace('_', " "))
+    }
+}
+
+impl From<String> for Unit {
+    /// Unit with the given canonical name, or `Unit::Other` for unknown names
+    fn from(name: String) -> Self {
+        ALIASES
+            .iter()
+            .map(|(_, unit)| unit)
+            .find(|unit| unit.name() == name)
+            .cloned()
+            .unwrap_or(Self::Other(name))
+    }
+}
+
+impl From<Unit> for String {
+    fn from(unit: Unit) -> Self {
+        match unit {
+            Unit::Other(name) => name,
+            unit => unit.name().to_owned(),
+        }
+    }
+}
+
+impl FromStr for Unit {
+    type Err = IngreedyError;
+
+    /// Parse a unit from its name or any spelling the grammar accepts.
+    ///
+    /// Matching is case-sensitive first (so "T" is a tablespoon and "t" a teaspoon),
+    /// then case-insensitive.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == s)
+            .or_else(|| {
+                ALIASES
+                    .iter()
+                    .find(|(alias, _)| alias.eq_ignore_ascii_case(s))
+            })
+            .map(|(_, unit)| unit.clone())
+            .ok_or_else(|| IngreedyError::UnknownUnit(s.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_from_str() {
+        assert_eq!("cups".parse::<Unit>().unwrap(), Unit::Cup);
+        assert_eq!("T".parse::<Unit>().unwrap(), Unit::Tablespoon);
+        assert_eq!("t".parse::<Unit>().unwrap(), Unit::Teaspoon);
+        assert_eq!("KG".parse::<Unit>().unwrap(), Unit::Kilogram);
+        assert_eq!("fluid_ounce".parse::<Unit>().unwrap(), Unit::FluidOunce);
+        assert!(matches!(
+            "furlong".parse::<Unit>(),
+            Err(IngreedyError::UnknownUnit(_))
+        ));
+    }
+
+    #[test]
+    fn test_unit_display_round_trip() {
+        for (_, unit) in ALIASES {
+            assert_eq!(&unit.to_string().parse::<Unit>().unwrap(), unit);
+            assert_eq!(&unit.abbreviation().parse::<Unit>().unwrap(), unit);
+        }
+        assert_eq!(Unit::FluidOunce.to_string(), "fluid ounce");
+    }
+
+    #[test]
+    fn test_unit_serde() {
+        assert_eq!(
+            serde_json::to_string(&Unit::FluidOunce).unwrap(),
+            "\"fluid_ounce\""
+        );
+        assert_eq!(
+            serde_json::from_str::<Unit>("\"fluid_ounce\"").unwrap(),
+            Unit::FluidOunce
+        );
+        assert_eq!(
+            serde_json::to_string(&Unit::Other("dollop".into())).unwrap(),
+            "\"dollop\""
+        );
+        assert_eq!(
+            serde_json::from_str::<Unit>("\"dollop\"").unwrap(),
+            Unit::Other("dollop".into())
+        );
+        assert_eq!(
+            serde_json::to_string(&UnitType::Metric).unwrap(),
+            "\"metric\""
+        );
+        assert_eq!(
+            serde_json::from_str::<UnitType>("\"metric\"").unwrap(),
+            UnitType::Metric
+        );
+        assert_eq!(
+            serde_json::from_str::<UnitType>("\"Metric\"").unwrap(),
+            UnitType::Metric
+        );
+        assert_eq!(
+            serde_json::from_str::<UnitType>("\"Nautical\"").unwrap(),
+            UnitType::Other("Nautical".into())
+        );
+    }
+
+    #[test]
+    fn test_unit_introspection() {
+        assert_eq!(Unit::all().len(), BUILTIN_UNITS.len());
+        assert!(Unit::all().iter().all(|unit| unit.aliases().count() > 0));
+        assert_eq!(
+            Unit::Stick.aliases().collect::<Vec<_>>(),
+            vec!["sticks", "stick"]
+        );
+        assert_eq!(Unit::Other("dollop".into()).aliases().count(), 0);
+        for (alias, _) in ALIASES.iter().filter(|(alias, _)| !alias.contains('_')) {
+            assert!(is_unit_token(alias), "{}", alias);
+        }
+        assert!(!is_unit_token("furlong"));
+        assert!(!is_unit_token("cupboard"));
+        assert!(!is_unit_token(" cup"));
+        let ingredient = crate::Ingredient::parse("2 touches salt").unwrap();
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Touch));
+        let ingredient = crate::Ingredient::parse("a splash of olive oil").unwrap();
+        assert_eq!(ingredient.quantities[0].unit, Some(Unit::Splash));
+        assert_eq!(ingredient.quantities[0].amount.value(), 1.);
+        assert_eq!(ingredient.ingredient.as_deref(), Some("olive oil"));
+    }
+
+    #[test]
+    fn test_unit_category() {
+        assert_eq!(Unit::Cup.category(), UnitCategory::Volume);
+        assert_eq!(Unit::Pound.category(), UnitCategory::Mass);
+        assert_eq!(Unit::Calorie.category(), UnitCategory::Energy);
+        assert_eq!(Unit::Pinch.category(), UnitCategory::Count);
+        assert_eq!(Unit::Pinch.unit_type(), UnitType::Imprecise);
+        assert_eq!(Unit::Clove.unit_type(), UnitType::Count);
+        assert_eq!(Unit::Clove.category(), UnitCategory::Count);
+    }
+}
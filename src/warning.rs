@@ -0,0 +1,133 @@
+//! Non-fatal warnings about suspicious parse results
+
+use crate::{Quantity, Unit, UnitCategory};
+use serde::{Deserialize, Serialize};
+
+/// Kind of problem a warning flags
+#[non_exhaustive]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningCategory {
+    /// An amount of zero (e.g. "0 g salt")
+    ZeroAmount,
+    /// An amount too large for a recipe (e.g. "12000 cups flour")
+    ImplausiblyLarge,
+    /// An amount too small to measure (e.g. "0.00001 g sugar")
+    ImplausiblySmall,
+    /// The line couldn't be parsed and was kept whole as the ingredient name
+    Unparsed,
+}
+
+/// Warning attached to a parsed ingredient, typically a sign of OCR or scraper corruption
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct Warning {
+    pub category: WarningCategory,
+    pub message: String,
+}
+
+/// Largest plausible amount per category, in grams, milliliters, joules, degrees, seconds,
+/// centimeters or pieces
+fn max_amount(category: Option<UnitCategory>) -> f64 {
+    match category {
+        Some(UnitCategory::Mass) => 25_000.,
+        Some(UnitCategory::Volume) => 20_000.,
+        Some(UnitCategory::Energy) => 42_000_000.,
+        Some(UnitCategory::Temperature) => 1000.,
+        Some(UnitCategory::Time) => 604_800.,
+        Some(UnitCategory::Length) => 1000.,
+        Some(UnitCategory::Count) | None => 1000.,
+    }
+}
+
+/// Smallest plausible amount per category, in grams, milliliters, joules, degrees, seconds,
+/// centimeters or pieces
+fn min_amount(category: Option<UnitCategory>) -> f64 {
+    match category {
+        Some(UnitCategory::Mass) => 0.000_001,
+        Some(UnitCategory::Volume) => 0.01,
+        Some(UnitCategory::Energy) => 1.,
+        Some(UnitCategory::Temperature) => -100.,
+        Some(UnitCategory::Time) => 1.,
+        Some(UnitCategory::Length) => 0.01,
+        Some(UnitCategory::Count) | None => 0.01,
+    }
+}
+
+/// Check a quantity for implausible amounts
+pub(crate) fn check_quantity(quantity: &Quantity) -> Option<Warning> {
+    let unit = quantity
+        .unit
+        .as_ref()
+        .map(|unit| format!(" {}", unit))
+        .unwrap_or_default();
+    if quantity.amount.is_unspecified() {
+        return None;
+    }
+    let (min, max) = quantity.amount.bounds();
+    if max == 0. && !quantity.is_temperature() {
+        return Some(Warning {
+            category: WarningCategory::ZeroAmount,
+            message: format!("Amount of 0{} is zero", unit),
+        });
+    }
+    let category = quantity.unit.as_ref().map(Unit::category);
+    let factor = quantity.factor().unwrap_or(1.);
+    if max * factor > max_amount(category) {
+        Some(Warning {
+            category: WarningCategory::ImplausiblyLarge,
+            message: format!("Amount of {}{} is implausibly large", max, unit),
+        })
+    } else if min * factor < min_amount(category) {
+        Some(Warning {
+            category: WarningCategory::ImplausiblySmall,
+            message: format!("Amount of {}{} is implausibly small", min, unit),
+        })
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Ingredient, WarningCategory};
+
+    fn categories(input: &str) -> Vec<WarningCategory> {
+        Ingredient::parse(input)
+            .unwrap()
+            .warnings
+            .iter()
+            .map(|warning| warning.category)
+            .collect()
+    }
+
+    #[test]
+    fn test_warnings() {
+        assert_eq!(
+            categories("12000 cups flour"),
+            vec![WarningCategory::ImplausiblyLarge]
+        );
+        assert_eq!(categories("0 g salt"), vec![WarningCategory::ZeroAmount]);
+        assert_eq!(
+            categories("0.0000001 g sugar"),
+            vec![WarningCategory::ImplausiblySmall]
+        );
+        assert_eq!(
+            categories("5000 eggs"),
+            vec![WarningCategory::ImplausiblyLarge]
+        );
+        assert!(categories("2 cups flour").is_empty());
+        assert!(categories("2lb 4oz potatoes").is_empty());
+        assert!(categories("salt").is_empty());
+    }
+
+    #[test]
+    fn test_warning_message() {
+        let ingredient = Ingredient::parse("12000 cups flour").unwrap();
+        assert_eq!(
+            ingredient.warnings[0].message,
+            "Amount of 12000 cup is implausibly large"
+        );
+    }
+}
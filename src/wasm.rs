@@ -0,0 +1,56 @@
+//! JavaScript bindings through wasm-bindgen, matching the ingreedy-js API
+//!
+//! Built as a WebAssembly module by the `ingreedy-wasm` crate with `wasm-pack build`.
+
+use crate::Ingredient;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// Ingredient in the shape ingreedy-js returns
+/// (e.g. `{ amount: 2, unit: "cup", ingredient: "flour" }`)
+#[derive(Serialize)]
+struct JsIngredient {
+    amount: Option<f64>,
+    unit: Option<String>,
+    ingredient: Option<String>,
+}
+
+impl From<Ingredient> for JsIngredient {
+    fn from(ingredient: Ingredient) -> Self {
+        let quantity = ingredient.quantities.into_iter().next();
+        Self {
+            amount: quantity.as_ref().map(|quantity| quantity.amount.value()),
+            unit: quantity
+                .and_then(|quantity| quantity.unit)
+                .map(|unit| unit.to_string()),
+            ingredient: ingredient.ingredient,
+        }
+    }
+}
+
+/// Parse an ingredient line, throwing a JavaScript error if it can't be parsed
+#[wasm_bindgen]
+pub fn parse(input: &str) -> Result<JsValue, JsValue> {
+    let ingredient =
+        Ingredient::parse(input).map_err(|error| JsValue::from_str(&error.to_string()))?;
+    serde_wasm_bindgen::to_value(&JsIngredient::from(ingredient)).map_err(JsValue::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ingreedy_js_fields() {
+        let ingredient = JsIngredient::from(Ingredient::parse("2 cups flour").unwrap());
+        assert_eq!(
+            serde_json::to_value(ingredient).unwrap(),
+            serde_json::json!({ "amount": 2., "unit": "cup", "ingredient": "flour" })
+        );
+        let ingredient = JsIngredient::from(Ingredient::parse("salt").unwrap());
+        assert_eq!(
+            serde_json::to_value(ingredient).unwrap(),
+            serde_json::json!({ "amount": null, "unit": null, "ingredient": "salt" })
+        );
+    }
+}
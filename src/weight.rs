@@ -0,0 +1,594 @@
+//! Resolving quantities to grams, the basis for nutrition calculation
+
+#[cfg(feature = "reference-tables")]
+use crate::Ingredient;
+use crate::{clean_ingredient_name, IngreedyError, Quantity, Size, Unit, UnitCategory};
+#[cfg(feature = "reference-tables")]
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::fmt;
+
+#[cfg(feature = "reference-tables")]
+lazy_static! {
+    static ref BUILTIN_DENSITIES: DensityTable = DensityTable::builtin();
+    static ref BUILTIN_PIECE_WEIGHTS: PieceWeights = PieceWeights::builtin();
+}
+
+/// Reference data needed to resolve a quantity to grams
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum MissingData {
+    /// Grams per milliliter, for volumes
+    Density,
+    /// Grams per piece, for counted quantities
+    PieceWeight,
+}
+
+impl fmt::Display for MissingData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Density => write!(f, "density"),
+            Self::PieceWeight => write!(f, "piece weight"),
+        }
+    }
+}
+
+/// Key used to look up an ingredient name in the reference tables
+fn table_key(name: &str) -> String {
+    clean_ingredient_name(name).to_lowercase()
+}
+
+/// Look up a name, falling back to simple singular forms ("eggs" -> "egg")
+fn lookup(table: &HashMap<String, f64>, name: &str) -> Option<f64> {
+    let key = table_key(name);
+    table.get(&key).copied().or_else(|| {
+        ["es", "s"]
+            .iter()
+            .filter_map(|suffix| key.strip_suffix(suffix))
+            .find_map(|singular| table.get(singular).copied())
+    })
+}
+
+/// A source of ingredient densities, for converting between volumes and masses
+pub trait DensityProvider {
+    /// Density of an ingredient in grams per milliliter, given its name as parsed
+    fn density(&self, ingredient_name: &str) -> Option<f64>;
+}
+
+impl<F: Fn(&str) -> Option<f64>> DensityProvider for F {
+    fn density(&self, ingredient_name: &str) -> Option<f64> {
+        self(ingredient_name)
+    }
+}
+
+/// Ingredient densities in grams per milliliter, keyed by ingredient name
+/// normalized with `clean_ingredient_name` and lowercased
+#[derive(Default, Debug, Clone)]
+pub struct DensityTable(HashMap<String, f64>);
+
+impl DensityProvider for DensityTable {
+    fn density(&self, ingredient_name: &str) -> Option<f64> {
+        self.get(ingredient_name)
+    }
+}
+
+impl DensityTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the density of an ingredient in grams per milliliter
+    pub fn insert(&mut self, name: &str, grams_per_milliliter: f64) {
+        self.0.insert(table_key(name), grams_per_milliliter);
+    }
+
+    /// Density of an ingredient in grams per milliliter
+    pub fn get(&self, name: &str) -> Option<f64> {
+        lookup(&self.0, name)
+    }
+
+    /// Built-in densities of common baking and cooking staples, from their usual weight per cup
+    #[cfg(feature = "reference-tables")]
+    pub fn builtin() -> Self {
+        let mut densities = Self::new();
+        // (name, grams per US cup)
+        let grams_per_cup: &[(&str, f64)] = &[
+            ("water", 236.6),
+            ("milk", 244.),
+            ("cream", 238.),
+            ("heavy cream", 238.),
+            ("buttermilk", 245.),
+            ("yogurt", 245.),
+            ("flour", 120.),
+            ("all-purpose flour", 120.),
+            ("bread flour", 127.),
+            ("whole wheat flour", 113.),
+            ("sugar", 200.),
+            ("granulated sugar", 200.),
+            ("brown sugar", 220.),
+            ("powdered sugar", 120.),
+            ("butter", 227.),
+            ("rice", 185.),
+            ("oats", 90.),
+            ("rolled oats", 90.),
+            ("cocoa powder", 85.),
+            ("honey", 340.),
+            ("maple syrup", 315.),
+            ("oil", 218.),
+            ("olive oil", 216.),
+            ("vegetable oil", 218.),
+            ("salt", 288.),
+        ];
+        let cup = Unit::Cup.factor().unwrap_or_default();
+        for (name, grams) in grams_per_cup {
+            densities.insert(name, grams / cup);
+        }
+        densities
+    }
+}
+
+/// Size descriptors distinguishing piece weights, longest first
+const SIZES: &[&str] = &[
+    "extra large",
+    "extra-large",
+    "small",
+    "medium",
+    "large",
+    "jumbo",
+];
+
+/// Split a leading size descriptor off an ingredient name ("large eggs" -> ("large", "eggs"))
+fn split_size(name: &str) -> (Option<&'static str>, &str) {
+    let name = name.trim_start();
+    SIZES
+        .iter()
+        .find_map(|size| {
+            let rest = name.get(size.len()..)?;
+            (name[..size.len()].eq_ignore_ascii_case(size) && rest.starts_with(' '))
+                .then(|| (Some(*size), rest.trim_start()))
+        })
+        .unwrap_or((None, name))
+}
+
+/// Key used to look up a size descriptor ("Extra-Large" -> "extra large")
+fn size_key(size: &str) -> String {
+    size.trim().replace('-', " ").to_lowercase()
+}
+
+/// Average weights of single pieces (an egg, a garlic clove, ...) in grams,
+/// keyed by ingredient name and optionally a size descriptor ("small", "large", ...)
+#[derive(Default, Debug, Clone)]
+pub struct PieceWeights {
+    weights: HashMap<String, f64>,
+    sized_weights: HashMap<String, HashMap<String, f64>>,
+}
+
+impl PieceWeights {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the weight of one piece of an ingredient in grams
+    pub fn insert(&mut self, name: &str, grams: f64) {
+        self.weights.insert(table_key(name), grams);
+    }
+
+    /// Set the weight of one piece of an ingredient of a given size in grams
+    pub fn insert_sized(&mut self, name: &str, size: &str, grams: f64) {
+        self.sized_weights
+            .entry(size_key(size))
+            .or_default()
+            .insert(table_key(name), grams);
+    }
+
+    /// Weight of one piece of an ingredient in grams.
+    ///
+    /// A leading size descriptor in the name ("large eggs") selects the weight for that size,
+    /// falling back to the unsized weight.
+    pub fn get(&self, name: &str) -> Option<f64> {
+        match split_size(name) {
+            (Some(size), name) => self.get_sized(name, size),
+            (None, name) => lookup(&self.weights, name),
+        }
+    }
+
+    /// Weight of one piece of an ingredient of a given size in grams,
+    /// falling back to the unsized weight
+    pub fn get_sized(&self, name: &str, size: &str) -> Option<f64> {
+        self.sized_weights
+            .get(&size_key(size))
+            .and_then(|weights| lookup(weights, name))
+            .or_else(|| lookup(&self.weights, name))
+    }
+
+    /// Built-in average weights of common counted produce, eggs and garlic
+    #[cfg(feature = "reference-tables")]
+    pub fn builtin() -> Self {
+        let mut piece_weights = Self::new();
+        type Sized = &'static [(&'static str, f64)];
+        // (name, unsized grams, [(size, grams)])
+        let weights: &[(&str, f64, Sized)] = &[
+            (
+                "egg",
+                50.,
+                &[
+                    ("small", 38.),
+                    ("medium", 44.),
+                    ("large", 50.),
+                    ("extra large", 56.),
+                    ("jumbo", 63.),
+                ],
+            ),
+            (
+                "onion",
+                110.,
+                &[("small", 70.), ("medium", 110.), ("large", 150.)],
+            ),
+            ("garlic clove", 5., &[("small", 3.), ("large", 7.)]),
+            ("clove garlic", 5., &[("small", 3.), ("large", 7.)]),
+            ("shallot", 25., &[]),
+            (
+                "lemon",
+                100.,
+                &[("small", 70.), ("medium", 100.), ("large", 130.)],
+            ),
+            ("lime", 67., &[]),
+            (
+                "orange",
+                130.,
+                &[("small", 100.), ("medium", 130.), ("large", 180.)],
+            ),
+            (
+                "apple",
+                180.,
+                &[("small", 150.), ("medium", 180.), ("large", 220.)],
+            ),
+            (
+                "banana",
+                120.,
+                &[("small", 100.), ("medium", 120.), ("large", 135.)],
+            ),
+            (
+                "potato",
+                170.,
+                &[("small", 120.), ("medium", 170.), ("large", 300.)],
+            ),
+            (
+                "tomato",
+                120.,
+                &[("small", 90.), ("medium", 120.), ("large", 180.)],
+            ),
+            (
+                "carrot",
+                60.,
+                &[("small", 50.), ("medium", 60.), ("large", 72.)],
+            ),
+            ("avocado", 170., &[]),
+            (
+                "bell pepper",
+                120.,
+                &[("small", 75.), ("medium", 120.), ("large", 165.)],
+            ),
+            (
+                "zucchini",
+                200.,
+                &[("small", 120.), ("medium", 200.), ("large", 320.)],
+            ),
+            (
+                "cucumber",
+                300.,
+                &[("small", 160.), ("medium", 300.), ("large", 400.)],
+            ),
+            (
+                "sweet potato",
+                130.,
+                &[("small", 60.), ("medium", 130.), ("large", 180.)],
+            ),
+            ("peach", 150., &[("small", 130.), ("large", 175.)]),
+            ("pear", 180., &[("small", 150.), ("large", 230.)]),
+        ];
+        for (name, grams, sized) in weights {
+            piece_weights.insert(name, *grams);
+            for (size, grams) in sized.iter() {
+                piece_weights.insert_sized(name, size, *grams);
+            }
+        }
+        piece_weights
+    }
+}
+
+impl Quantity {
+    /// Resolve the quantity of an ingredient to grams.
+    ///
+    /// Masses are converted directly, volumes go through the ingredient's density
+    /// and unitless counts through its piece weight; ranges resolve through their midpoint.
+    /// Containers with a size ("1 can (15 oz)") resolve through their size. Returns `IngreedyError::NeedsData`
+    /// if the ingredient is missing from the table required.
+    pub fn to_grams(
+        &self,
+        ingredient_name: &str,
+        densities: &(impl DensityProvider + ?Sized),
+        piece_weights: &PieceWeights,
+    ) -> Result<f64, IngreedyError> {
+        self.to_grams_sized(ingredient_name, None, densities, piece_weights)
+    }
+
+    /// Resolve the quantity of an ingredient to grams like `to_grams`, weighing counted pieces
+    /// by their size (`Ingredient::size`, e.g. "small" in "2 small eggs") where the table has it
+    pub fn to_grams_sized(
+        &self,
+        ingredient_name: &str,
+        size: Option<Size>,
+        densities: &(impl DensityProvider + ?Sized),
+        piece_weights: &PieceWeights,
+    ) -> Result<f64, IngreedyError> {
+        let needs_data = |data| IngreedyError::NeedsData {
+            data,
+            ingredient: ingredient_name.into(),
+        };
+        if let Some(size) = self
+            .container_size
+            .as_ref()
+            .filter(|size| !size.is_length())
+        {
+            return Ok(
+                self.amount.value() * size.to_grams(ingredient_name, densities, piece_weights)?
+            );
+        }
+        let unit = match &self.unit {
+            Some(unit) => unit,
+            None => {
+                let grams = match size {
+                    Some(size) => piece_weights.get_sized(ingredient_name, size.name()),
+                    None => piece_weights.get(ingredient_name),
+                }
+                .ok_or_else(|| needs_data(MissingData::PieceWeight))?;
+                return Ok(self.amount.value() * grams);
+            }
+        };
+        match unit.category() {
+            UnitCategory::Mass => Ok(self.convert_to(Unit::Gram)?.amount.value()),
+            UnitCategory::Volume => {
+                let density = densities
+                    .density(ingredient_name)
+                    .ok_or_else(|| needs_data(MissingData::Density))?;
+                Ok(self.convert_to(Unit::Milliliter)?.amount.value() * density)
+            }
+            UnitCategory::Energy
+            | UnitCategory::Temperature
+            | UnitCategory::Time
+            | UnitCategory::Length => Err(IngreedyError::IncompatibleUnits {
+                from: unit.to_string(),
+                to: Unit::Gram.to_string(),
+            }),
+            UnitCategory::Count => Err(IngreedyError::UnconvertibleUnit(unit.to_string())),
+        }
+    }
+
+    /// Convert the quantity of an ingredient to another unit like `Quantity::convert_to`,
+    /// also going between volumes and masses through the ingredient's density
+    /// (e.g. "1 cup flour" to about 120 grams).
+    ///
+    /// Returns `IngreedyError::NeedsData` if the densities don't have the ingredient.
+    pub fn convert_with_density(
+        &self,
+        unit: Unit,
+        ingredient_name: &str,
+        densities: &(impl DensityProvider + ?Sized),
+    ) -> Result<Self, IngreedyError> {
+        let from_unit = self.unit.as_ref().ok_or(IngreedyError::MissingUnit)?;
+        let (base_unit, other_base_unit) = match (from_unit.category(), unit.category()) {
+            (UnitCategory::Volume, UnitCategory::Mass) => (Unit::Milliliter, Unit::Gram),
+            (UnitCategory::Mass, UnitCategory::Volume) => (Unit::Gram, Unit::Milliliter),
+            _ => return self.convert_to(unit),
+        };
+        let density =
+            densities
+                .density(ingredient_name)
+                .ok_or_else(|| IngreedyError::NeedsData {
+                    data: MissingData::Density,
+                    ingredient: ingredient_name.into(),
+                })?;
+        let factor = if base_unit == Unit::Milliliter {
+            density
+        } else {
+            density.recip()
+        };
+        let mut quantity = self.convert_to(base_unit)?.scale(factor);
+        quantity.set_unit(other_base_unit);
+        quantity.convert_to(unit)
+    }
+}
+
+#[cfg(feature = "reference-tables")]
+impl Quantity {
+    /// Approximate weight in grams of the quantity of an ingredient from the built-in tables,
+    /// with counted pieces ("2 large eggs") weighed by their size where the table has it.
+    ///
+    /// Returns `None` for ingredients the tables don't have and for unspecified amounts.
+    pub fn estimate_weight(&self, name: &str, size: Option<Size>) -> Option<f64> {
+        if self.amount.is_unspecified() {
+            return None;
+        }
+        self.to_grams_sized(name, size, &*BUILTIN_DENSITIES, &BUILTIN_PIECE_WEIGHTS)
+            .ok()
+    }
+}
+
+#[cfg(feature = "reference-tables")]
+impl Ingredient {
+    /// Approximate weight in grams of the ingredient's first quantity,
+    /// like `Quantity::estimate_weight`
+    pub fn estimate_weight(&self) -> Option<f64> {
+        self.quantities
+            .first()?
+            .estimate_weight(self.ingredient.as_deref()?, self.size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ingredient;
+    use approx::assert_relative_eq;
+
+    fn tables() -> (DensityTable, PieceWeights) {
+        let mut densities = DensityTable::new();
+        densities.insert("flour", 0.53);
+        let mut piece_weights = PieceWeights::new();
+        piece_weights.insert("egg", 50.);
+        piece_weights.insert("garlic clove", 5.);
+        (densities, piece_weights)
+    }
+
+    fn to_grams(input: &str) -> Result<f64, IngreedyError> {
+        let (densities, piece_weights) = tables();
+        let ingredient = Ingredient::parse(input)?;
+        ingredient.quantities[0].to_grams(
+            ingredient.ingredient.as_deref().unwrap_or_default(),
+            &densities,
+            &piece_weights,
+        )
+    }
+
+    #[test]
+    fn test_to_grams() {
+        assert_relative_eq!(to_grams("1 kg potatoes").unwrap(), 1000.);
+        assert_relative_eq!(to_grams("1 cup flour").unwrap(), 125.39176, epsilon = 1e-5);
+        assert_relative_eq!(to_grams("2 eggs, beaten").unwrap(), 100.);
+        assert_relative_eq!(to_grams("3 garlic cloves, minced").unwrap(), 15.);
+        assert_relative_eq!(
+            to_grams("2 cans (15 oz) black beans").unwrap(),
+            850.49,
+            epsilon = 0.01
+        );
+    }
+
+    #[test]
+    fn test_piece_weights_sized() {
+        let mut piece_weights = PieceWeights::new();
+        piece_weights.insert("egg", 50.);
+        piece_weights.insert_sized("egg", "Extra-Large", 56.);
+        assert_relative_eq!(piece_weights.get("extra large eggs").unwrap(), 56.);
+        assert_relative_eq!(piece_weights.get_sized("egg", "extra-large").unwrap(), 56.);
+        assert_relative_eq!(piece_weights.get("small eggs").unwrap(), 50.);
+        assert_relative_eq!(piece_weights.get("eggs").unwrap(), 50.);
+        assert!(piece_weights.get("large onion").is_none());
+    }
+
+    #[cfg(feature = "reference-tables")]
+    #[test]
+    fn test_piece_weights_builtin() {
+        let piece_weights = PieceWeights::builtin();
+        let ingredient = Ingredient::parse("2 small eggs").unwrap();
+        assert_eq!(ingredient.size, Some(Size::Small));
+        let name = ingredient.ingredient.as_deref().unwrap();
+        let grams = ingredient.quantities[0]
+            .to_grams_sized(name, ingredient.size, &DensityTable::new(), &piece_weights)
+            .unwrap();
+        assert_relative_eq!(grams, 76.);
+        let grams = ingredient.quantities[0]
+            .to_grams(name, &DensityTable::new(), &piece_weights)
+            .unwrap();
+        assert_relative_eq!(grams, 100.);
+        assert_relative_eq!(piece_weights.get("medium onion").unwrap(), 110.);
+        assert_relative_eq!(piece_weights.get("lemons").unwrap(), 100.);
+    }
+
+    #[cfg(feature = "reference-tables")]
+    #[test]
+    fn test_estimate_weight() {
+        let weight = |input| Ingredient::parse(input).unwrap().estimate_weight();
+        assert_relative_eq!(weight("2 large eggs").unwrap(), 100.);
+        assert_relative_eq!(weight("3 jumbo eggs").unwrap(), 189.);
+        assert_relative_eq!(weight("1 medium onion, diced").unwrap(), 110.);
+        assert_relative_eq!(weight("2 small bell peppers").unwrap(), 150.);
+        assert_relative_eq!(weight("1 cup sugar").unwrap(), 200., epsilon = 1e-9);
+        assert_relative_eq!(weight("1 lb potatoes").unwrap(), 453.592_37);
+        assert_eq!(weight("2 large dragon fruit"), None);
+        assert_eq!(weight("salt to taste"), None);
+        let quantity = Quantity::parse_str("2").unwrap();
+        assert_relative_eq!(
+            quantity
+                .estimate_weight("lemons", Some(Size::Large))
+                .unwrap(),
+            260.
+        );
+        assert_relative_eq!(quantity.estimate_weight("lemons", None).unwrap(), 200.);
+    }
+
+    #[cfg(feature = "reference-tables")]
+    #[test]
+    fn test_densities_builtin() {
+        let densities = DensityTable::builtin();
+        let ingredient = Ingredient::parse("1 cup all-purpose flour").unwrap();
+        let grams = ingredient.quantities[0]
+            .to_grams(
+                ingredient.ingredient.as_deref().unwrap(),
+                &densities,
+                &PieceWeights::new(),
+            )
+            .unwrap();
+        assert_relative_eq!(grams, 120., epsilon = 1e-9);
+        assert_relative_eq!(densities.get("Water").unwrap(), 1., epsilon = 0.001);
+    }
+
+    #[test]
+    fn test_convert_with_density() {
+        let (densities, _) = tables();
+        let flour = Quantity::parse_str("1 cup").unwrap();
+        let grams = flour
+            .convert_with_density(Unit::Gram, "Flour", &densities)
+            .unwrap();
+        assert_eq!(grams.unit, Some(Unit::Gram));
+        assert_relative_eq!(grams.amount.value(), 125.39176, epsilon = 1e-5);
+        let cups = grams
+            .convert_with_density(Unit::Cup, "flour", &densities)
+            .unwrap();
+        assert_relative_eq!(cups.amount.value(), 1., epsilon = 1e-9);
+        let honey = |name: &str| (name == "honey").then_some(1.42);
+        let ounces = Quantity::parse_str("2 tbsp")
+            .unwrap()
+            .convert_with_density(Unit::Ounce, "honey", &honey)
+            .unwrap();
+        assert_relative_eq!(ounces.amount.value(), 1.481, epsilon = 1e-3);
+        assert!(matches!(
+            flour.convert_with_density(Unit::Gram, "sugar", &densities),
+            Err(IngreedyError::NeedsData {
+                data: MissingData::Density,
+                ..
+            })
+        ));
+    }
+
+    #[cfg(feature = "reference-tables")]
+    #[test]
+    fn test_convert_with_builtin_densities() {
+        let ingredient = Ingredient::parse("1 cup flour").unwrap();
+        let grams = ingredient.quantities[0]
+            .convert_with_density(Unit::Gram, "flour", &DensityTable::builtin())
+            .unwrap();
+        assert_relative_eq!(grams.amount.value(), 120., epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_to_grams_needs_data() {
+        assert!(matches!(
+            to_grams("1 cup sugar"),
+            Err(IngreedyError::NeedsData {
+                data: MissingData::Density,
+                ..
+            })
+        ));
+        assert!(matches!(
+            to_grams("2 onions"),
+            Err(IngreedyError::NeedsData {
+                data: MissingData::PieceWeight,
+                ..
+            })
+        ));
+        assert!(matches!(
+            to_grams("pinch salt"),
+            Err(IngreedyError::UnconvertibleUnit(_))
+        ));
+    }
+}